@@ -0,0 +1,7 @@
+-- Written to be valid for both Postgres and SQLite.
+CREATE TABLE todos
+(
+    id          BIGINT  PRIMARY KEY NOT NULL,
+    description TEXT    NOT NULL,
+    priority    INTEGER NOT NULL
+);
@@ -0,0 +1,35 @@
+use sqlx::AnyPool;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    // Either a Postgres or a SQLite database; the queries are checked against both.
+    let pool = AnyPool::connect(&std::env::var("DATABASE_URL")?).await?;
+
+    sqlx::migrate!().run(&pool).await?;
+
+    // `$N` parameters are understood by both Postgres and SQLite.
+    sqlx::query!(
+        "INSERT INTO todos (id, description, priority) VALUES ($1, $2, $3)",
+        1i64,
+        "check queries against every backend",
+        1i32
+    )
+    .execute(&pool)
+    .await?;
+
+    // `priority` is `INTEGER` in Postgres but a 64-bit integer in SQLite, so it is read as `i64`.
+    let todos = sqlx::query!("SELECT id, description, priority FROM todos ORDER BY id")
+        .fetch_all(&pool)
+        .await?;
+
+    for todo in todos {
+        println!(
+            "- {}: {} (priority {})",
+            todo.id, todo.description, todo.priority
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+// Recompile when a migration is added, as the queries are checked against the migrations.
+// Changes to existing migrations are tracked by the macros.
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+}
@@ -0,0 +1,6 @@
+CREATE TABLE todos
+(
+    id          INTEGER PRIMARY KEY NOT NULL,
+    description TEXT                NOT NULL,
+    done        BOOLEAN             NOT NULL DEFAULT 0
+);
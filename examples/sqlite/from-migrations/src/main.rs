@@ -0,0 +1,36 @@
+use sqlx::SqlitePool;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> anyhow::Result<()> {
+    let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+    sqlx::migrate!().run(&pool).await?;
+
+    // `priority` only exists after the second migration, which the macro knows about
+    // without a database to connect to.
+    let id = sqlx::query!(
+        "INSERT INTO todos (description, priority) VALUES (?, ?)",
+        "check queries without a database",
+        1
+    )
+    .execute(&pool)
+    .await?
+    .last_insert_rowid();
+
+    let todo = sqlx::query!(
+        "SELECT id, description, done, priority FROM todos WHERE id = ?",
+        id
+    )
+    .fetch_one(&pool)
+    .await?;
+
+    println!(
+        "- [{}] {}: {} (priority {})",
+        if todo.done { "x" } else { " " },
+        todo.id,
+        todo.description,
+        todo.priority
+    );
+
+    Ok(())
+}
@@ -0,0 +1,6 @@
+-- SQLite can't add a `NOT NULL` column without a default, but the application always sets it.
+ALTER TABLE users ADD COLUMN email TEXT;
+
+CREATE VIEW user_emails AS
+SELECT id, email
+FROM users;
@@ -0,0 +1,67 @@
+use sqlx::SqlitePool;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> anyhow::Result<()> {
+    let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+    sqlx::migrate!().run(&pool).await?;
+
+    sqlx::query!(
+        "INSERT INTO users (name, email) VALUES (?, ?)",
+        "Herp Derpinson",
+        "herp@example.com"
+    )
+    .execute(&pool)
+    .await?;
+
+    // `email` is `String` rather than `Option<String>`, as configured in `sqlx.toml`.
+    for user in sqlx::query!("SELECT id, email FROM user_emails")
+        .fetch_all(&pool)
+        .await?
+    {
+        println!("{}: {}", user.id, user.email);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::SqlitePool;
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn nullability_overrides() -> anyhow::Result<()> {
+        let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+        sqlx::migrate!().run(&pool).await?;
+
+        sqlx::query!(
+            "INSERT INTO users (name, email) VALUES (?, ?)",
+            "Herp Derpinson",
+            "herp@example.com"
+        )
+        .execute(&pool)
+        .await?;
+
+        // Both would be `Option<_>` without the overrides in `sqlx.toml`.
+        let user = sqlx::query!("SELECT id, email FROM user_emails")
+            .fetch_one(&pool)
+            .await?;
+
+        let email: String = user.email;
+        assert_eq!(email, "herp@example.com");
+
+        let max_id: i64 = sqlx::query_scalar!("SELECT max(id) FROM users")
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(max_id, user.id);
+
+        // An override in the query takes precedence.
+        let email: Option<String> = sqlx::query_scalar!(r#"SELECT email as "email?" FROM users"#)
+            .fetch_one(&pool)
+            .await?;
+        assert_eq!(email.as_deref(), Some("herp@example.com"));
+
+        Ok(())
+    }
+}
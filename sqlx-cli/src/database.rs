@@ -1,10 +1,14 @@
 use crate::opt::{ConnectOpts, MigrationSourceOpt};
+use crate::schema::Schema;
 use crate::{migrate, Config};
+use anyhow::{bail, Context};
 use console::{style, Term};
 use dialoguer::Confirm;
 use sqlx::any::Any;
 use sqlx::migrate::MigrateDatabase;
-use std::{io, mem};
+use sqlx::Connection;
+use std::path::Path;
+use std::{fs, io, mem};
 use tokio::task;
 
 pub async fn create(connect_opts: &ConnectOpts) -> anyhow::Result<()> {
@@ -26,7 +30,13 @@ pub async fn create(connect_opts: &ConnectOpts) -> anyhow::Result<()> {
 }
 
 pub async fn drop(connect_opts: &ConnectOpts, confirm: bool, force: bool) -> anyhow::Result<()> {
-    if confirm && !ask_to_continue_drop(connect_opts.expect_db_url()?.to_owned()).await {
+    if confirm
+        && !ask_to_continue(format!(
+            "Drop database at {}?",
+            style(connect_opts.expect_db_url()?).cyan()
+        ))
+        .await
+    {
         return Ok(());
     }
 
@@ -62,12 +72,53 @@ pub async fn setup(
     connect_opts: &ConnectOpts,
 ) -> anyhow::Result<()> {
     create(connect_opts).await?;
-    migrate::run(config, migration_source, connect_opts, false, false, None).await
+    migrate::run(
+        config,
+        migration_source,
+        connect_opts,
+        migrate::DryRun::Off,
+        false,
+        None,
+    )
+    .await
+}
+
+pub async fn dump_schema(
+    config: &Config,
+    connect_opts: &ConnectOpts,
+    output: &Path,
+    check: bool,
+) -> anyhow::Result<()> {
+    let mut conn = crate::connect(config, connect_opts).await?;
+    let schema = Schema::introspect(&mut conn, config.migrate.table_name()).await?;
+    let _ = conn.close().await;
+
+    let rendered = schema.to_string();
+
+    if check {
+        let existing = fs::read_to_string(output)
+            .with_context(|| format!("failed to read {}", output.display()))?;
+
+        if existing != rendered {
+            bail!(
+                "{} does not match the database schema; run `sqlx database dump-schema` to update it",
+                output.display()
+            );
+        }
+
+        return Ok(());
+    }
+
+    fs::write(output, rendered).with_context(|| format!("failed to write {}", output.display()))?;
+
+    println!("Wrote schema to {}", style(output.display()).cyan());
+
+    Ok(())
 }
 
-async fn ask_to_continue_drop(db_url: String) -> bool {
-    // If the setup operation is cancelled while we are waiting for the user to decide whether
-    // or not to drop the database, this will restore the terminal's cursor to its normal state.
+pub(crate) async fn ask_to_continue(prompt: String) -> bool {
+    // If the operation is cancelled while we are waiting for the user to decide whether
+    // or not to continue, this will restore the terminal's cursor to its normal state.
     struct RestoreCursorGuard {
         disarmed: bool,
     }
@@ -84,7 +135,7 @@ async fn ask_to_continue_drop(db_url: String) -> bool {
 
     let decision_result = task::spawn_blocking(move || {
         Confirm::new()
-            .with_prompt(format!("Drop database at {}?", style(&db_url).cyan()))
+            .with_prompt(prompt)
             .wait_for_newline(true)
             .default(false)
             .show_default(true)
@@ -0,0 +1,1117 @@
+//! Generation of Rust types for the tables, views and user-defined types of a live database.
+//!
+//! SQL types are mapped to the same Rust types as in the query macros, taking the overrides and
+//! preferred crates in `[macros]` of `sqlx.toml` into account, so the generated structs can be
+//! used with `query_as!()`. Everything is sorted by name so that the same schema always generates
+//! the same code.
+
+// Catalog rows are decoded into tuples since `sqlx-cli` does not enable `derive`.
+#![allow(clippy::type_complexity)]
+
+use crate::config::macros::{Config as MacrosConfig, DateTimeCrate, Nullability, NumericCrate};
+use crate::config::Config;
+use crate::opt::ConnectOpts;
+use crate::schema::{is_migrations_table, pg_user_objects};
+use anyhow::{bail, Context};
+use console::style;
+use sqlx::{AnyConnection, AssertSqlSafe, Connection};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The header written at the top of each generated file.
+const HEADER: &str = "// This file is generated by `sqlx generate`. Do not edit it by hand.\n";
+
+/// The module for the enums and composite types of the database.
+const TYPES_MODULE: &str = "types";
+
+pub async fn run(
+    config: &Config,
+    connect_opts: &ConnectOpts,
+    output: &Path,
+    check: bool,
+) -> anyhow::Result<()> {
+    let mut conn = crate::connect(config, connect_opts).await?;
+    let database = Database::introspect(&mut conn, config.migrate.table_name()).await?;
+    let _ = conn.close().await;
+
+    let files = database.generate(&config.macros)?;
+
+    // Generated files that are no longer generated, e.g. for a table that was dropped.
+    let stale: Vec<PathBuf> = generated_files(output)?
+        .into_iter()
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_none_or(|name| !files.contains_key(name))
+        })
+        .collect();
+
+    if check {
+        let mut outdated: Vec<PathBuf> = files
+            .iter()
+            .map(|(name, contents)| (output.join(name), contents))
+            .filter(|(path, contents)| fs::read_to_string(path).ok().as_ref() != Some(*contents))
+            .map(|(path, _)| path)
+            .collect();
+
+        outdated.extend(stale);
+
+        if !outdated.is_empty() {
+            for path in &outdated {
+                println!("out of date: {}", path.display());
+            }
+
+            bail!(
+                "{} does not match the database schema; run `sqlx generate` to update it",
+                output.display()
+            );
+        }
+
+        return Ok(());
+    }
+
+    fs::create_dir_all(output).with_context(|| format!("failed to create {}", output.display()))?;
+
+    for (name, contents) in &files {
+        let path = output.join(name);
+
+        if fs::read_to_string(&path).ok().as_ref() != Some(contents) {
+            fs::write(&path, contents)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+        }
+    }
+
+    for path in stale {
+        fs::remove_file(&path).with_context(|| format!("failed to remove {}", path.display()))?;
+    }
+
+    println!(
+        "Generated {} modules in {}",
+        files.len(),
+        style(output.display()).cyan()
+    );
+
+    Ok(())
+}
+
+/// The `.rs` files in `dir` that were written by `sqlx generate`.
+fn generated_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut files = Vec::new();
+
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))? {
+        let path = entry?.path();
+
+        if path.extension().is_some_and(|ext| ext == "rs")
+            && fs::read_to_string(&path).is_ok_and(|contents| contents.starts_with(HEADER))
+        {
+            files.push(path);
+        }
+    }
+
+    Ok(files)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Backend {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+/// The tables, views and user-defined types of a database.
+#[derive(Debug)]
+pub struct Database {
+    backend: Backend,
+    /// The name tables are qualified with in `[macros]` of `sqlx.toml`, if it's not part of
+    /// their names, i.e. the name of a MySQL database.
+    qualifier: Option<String>,
+    /// Tables and views, keyed by name as the query macros report them, e.g. only
+    /// schema-qualified in Postgres if they are not in the `search_path`.
+    relations: BTreeMap<String, Relation>,
+    /// Postgres enums and composite types, keyed by name.
+    types: BTreeMap<String, UserType>,
+}
+
+#[derive(Debug)]
+struct Relation {
+    view: bool,
+    fields: Vec<Field>,
+}
+
+#[derive(Debug)]
+struct Field {
+    name: String,
+    ty: SqlType,
+    not_null: bool,
+}
+
+#[derive(Debug)]
+struct SqlType {
+    /// The name of the type as the query macros report it, e.g. `INT4[]`,
+    /// which is also the name it is looked up by in `[macros.type-overrides]`.
+    name: String,
+    /// The name of the type, or of the element type of an array, that it is mapped by.
+    base: String,
+    array: bool,
+}
+
+#[derive(Debug)]
+enum UserType {
+    /// The labels of an enum, in declaration order.
+    Enum(Vec<String>),
+    /// The attributes of a composite type, in order.
+    Composite(Vec<Field>),
+}
+
+/// Why a SQL type could not be mapped to a Rust type.
+enum TypeError {
+    NoMapping,
+    DateTimeCrate,
+    NumericCrate,
+}
+
+impl Database {
+    /// Introspect the database `conn` is connected to.
+    ///
+    /// The migrations table, `migrations_table`, is left out since it is managed by SQLx.
+    pub async fn introspect(
+        conn: &mut AnyConnection,
+        migrations_table: &str,
+    ) -> anyhow::Result<Self> {
+        let mut database = match conn.backend_name() {
+            "PostgreSQL" => introspect_postgres(conn).await?,
+            "MySQL" => introspect_mysql(conn).await?,
+            "SQLite" => introspect_sqlite(conn).await?,
+            backend => bail!("generating code for a {backend} database is not supported"),
+        };
+
+        database
+            .relations
+            .retain(|name, _| !is_migrations_table(name, migrations_table));
+
+        Ok(database)
+    }
+
+    /// Generate a module for each table and view, and one for the user-defined types,
+    /// returning the contents of their files keyed by file name.
+    pub fn generate(&self, config: &MacrosConfig) -> anyhow::Result<BTreeMap<String, String>> {
+        let mut files = BTreeMap::new();
+        let mut errors = Vec::new();
+
+        let mut modules = BTreeMap::new();
+
+        for (name, relation) in &self.relations {
+            let unquoted = unquote(name).replace('.', "_");
+            let module = module_name(&snake_case(&unquoted));
+            let type_name = pascal_case(&unquoted);
+
+            if let Some(other) = modules.insert(module.clone(), (name, type_name.clone())) {
+                bail!(
+                    "{} and {name} would both be generated as the module `{module}`",
+                    other.0
+                );
+            }
+
+            let mut code = format!(
+                "{HEADER}\n/// The `{name}` {}.\n",
+                if relation.view { "view" } else { "table" }
+            );
+
+            let mut derives = vec!["Debug", "Clone", "sqlx::FromRow"];
+
+            for derive in &config.record_derives {
+                if !derives.contains(&&**derive) {
+                    derives.push(derive);
+                }
+            }
+
+            writeln!(code, "#[derive({})]", derives.join(", "))?;
+            writeln!(code, "pub struct {type_name} {{")?;
+
+            for field in &relation.fields {
+                let ty = match self.rust_type(config, Some(name), field, "super::types::") {
+                    Ok(ty) => ty,
+                    Err(e) => {
+                        errors.push(e);
+                        continue;
+                    }
+                };
+
+                let ident = field_ident(&field.name);
+
+                if ident.trim_start_matches("r#") != field.name {
+                    writeln!(code, "    #[sqlx(rename = {:?})]", field.name)?;
+                }
+
+                writeln!(code, "    pub {ident}: {ty},")?;
+            }
+
+            code.push_str("}\n");
+
+            files.insert(format!("{module}.rs"), code);
+        }
+
+        if !self.types.is_empty() {
+            let mut code = HEADER.to_owned();
+
+            for (name, ty) in &self.types {
+                let type_name = pascal_case(&unquote(name));
+
+                match ty {
+                    UserType::Enum(labels) => {
+                        writeln!(code, "\n/// The `{name}` enum.")?;
+                        writeln!(
+                            code,
+                            "#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]"
+                        )?;
+                        writeln!(code, "#[sqlx(type_name = {name:?})]")?;
+                        writeln!(code, "pub enum {type_name} {{")?;
+
+                        let mut variants = BTreeMap::new();
+
+                        for label in labels {
+                            let variant = pascal_case(label);
+
+                            if let Some(other) = variants.insert(variant.clone(), label) {
+                                bail!(
+                                    "the labels {other:?} and {label:?} of enum {name} would both \
+                                     be generated as the variant `{variant}`"
+                                );
+                            }
+
+                            writeln!(code, "    #[sqlx(rename = {label:?})]")?;
+                            writeln!(code, "    {variant},")?;
+                        }
+
+                        code.push_str("}\n");
+                    }
+                    UserType::Composite(fields) => {
+                        writeln!(code, "\n/// The `{name}` composite type.")?;
+                        writeln!(code, "#[derive(Debug, Clone, sqlx::Type)]")?;
+                        writeln!(code, "#[sqlx(type_name = {name:?})]")?;
+                        writeln!(code, "pub struct {type_name} {{")?;
+
+                        // Composite types are encoded by position, so fields need not be renamed.
+                        for field in fields {
+                            match self.rust_type(config, None, field, "") {
+                                Ok(ty) => {
+                                    writeln!(code, "    pub {}: {ty},", field_ident(&field.name))?
+                                }
+                                Err(e) => errors.push(e),
+                            }
+                        }
+
+                        code.push_str("}\n");
+                    }
+                }
+            }
+
+            files.insert(format!("{TYPES_MODULE}.rs"), code);
+        }
+
+        if !errors.is_empty() {
+            bail!(
+                "could not map the types of {} columns:\n{}",
+                errors.len(),
+                errors.join("\n")
+            );
+        }
+
+        let mut code = format!("{HEADER}\n");
+
+        if !self.types.is_empty() {
+            writeln!(code, "pub mod {TYPES_MODULE};\n")?;
+        }
+
+        for module in modules.keys() {
+            writeln!(code, "pub mod {module};")?;
+        }
+
+        if !modules.is_empty() {
+            code.push('\n');
+        }
+
+        for (module, (_, type_name)) in &modules {
+            writeln!(code, "pub use {module}::{type_name};")?;
+        }
+
+        files.insert("mod.rs".to_owned(), code.trim_end().to_owned() + "\n");
+
+        Ok(files)
+    }
+
+    /// The Rust type of `field`, which is a column of `relation` or otherwise an attribute of
+    /// a composite type; user-defined types are referred to through `types_path`.
+    fn rust_type(
+        &self,
+        config: &MacrosConfig,
+        relation: Option<&str>,
+        field: &Field,
+        types_path: &str,
+    ) -> Result<String, String> {
+        // Overrides may name the table as the query macros report it, or qualified.
+        let tables: Vec<String> = relation
+            .into_iter()
+            .map(str::to_owned)
+            .chain(
+                relation
+                    .zip(self.qualifier.as_ref())
+                    .map(|(relation, qualifier)| format!("{qualifier}.{relation}")),
+            )
+            .collect();
+
+        let nullable = match tables
+            .iter()
+            .find_map(|table| config.column_nullability(table, &field.name))
+        {
+            Some(nullability) => nullability == Nullability::Nullable,
+            None => !field.not_null,
+        };
+
+        let column_override = tables
+            .iter()
+            .find_map(|table| config.column_override(table, &field.name));
+
+        let ty = if let Some(ty) = column_override.or_else(|| config.type_override(&field.ty.name))
+        {
+            ty.to_owned()
+        } else {
+            let element = match self.types.get(&field.ty.base) {
+                Some(_) => Ok(format!(
+                    "{types_path}{}",
+                    pascal_case(&unquote(&field.ty.base))
+                )),
+                None => builtin_type(self.backend, &field.ty.base, config),
+            };
+
+            let element = element.map_err(|e| {
+                let name = &field.ty.name;
+                let column = match relation {
+                    Some(relation) => format!("column {relation}.{}", field.name),
+                    None => format!("attribute {} of a composite type", field.name),
+                };
+
+                match e {
+                    TypeError::NoMapping => format!(
+                        "{column}: no built-in mapping found for SQL type `{name}`; \
+                         a type override may be required"
+                    ),
+                    TypeError::DateTimeCrate => format!(
+                        "{column}: SQL type `{name}` may be mapped with `chrono` or `time`; \
+                         set `macros.preferred-crates.date-time` to choose"
+                    ),
+                    TypeError::NumericCrate => format!(
+                        "{column}: SQL type `{name}` may be mapped with `bigdecimal` or \
+                         `rust_decimal`; set `macros.preferred-crates.numeric` to choose"
+                    ),
+                }
+            })?;
+
+            if field.ty.array {
+                format!("Vec<{element}>")
+            } else {
+                element
+            }
+        };
+
+        Ok(if nullable {
+            format!("Option<{ty}>")
+        } else {
+            ty
+        })
+    }
+}
+
+/// The Rust types for the SQL types of a date/time crate.
+struct DateTimeTypes {
+    date: &'static str,
+    time: &'static str,
+    date_time: &'static str,
+    date_time_tz: &'static str,
+    offset: &'static str,
+}
+
+const CHRONO_TYPES: DateTimeTypes = DateTimeTypes {
+    date: "sqlx::types::chrono::NaiveDate",
+    time: "sqlx::types::chrono::NaiveTime",
+    date_time: "sqlx::types::chrono::NaiveDateTime",
+    date_time_tz: "sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>",
+    offset: "sqlx::types::chrono::FixedOffset",
+};
+
+const TIME_TYPES: DateTimeTypes = DateTimeTypes {
+    date: "sqlx::types::time::Date",
+    time: "sqlx::types::time::Time",
+    date_time: "sqlx::types::time::PrimitiveDateTime",
+    date_time_tz: "sqlx::types::time::OffsetDateTime",
+    offset: "sqlx::types::time::UtcOffset",
+};
+
+fn date_time_types(config: &MacrosConfig) -> Result<&'static DateTimeTypes, TypeError> {
+    // Unlike the macros, this can't tell which crate is enabled.
+    match config.preferred_crates.date_time {
+        DateTimeCrate::Chrono => Ok(&CHRONO_TYPES),
+        DateTimeCrate::Time => Ok(&TIME_TYPES),
+        DateTimeCrate::Inferred => Err(TypeError::DateTimeCrate),
+    }
+}
+
+fn numeric_type(config: &MacrosConfig) -> Result<&'static str, TypeError> {
+    match config.preferred_crates.numeric {
+        NumericCrate::BigDecimal => Ok("sqlx::types::BigDecimal"),
+        NumericCrate::RustDecimal => Ok("sqlx::types::Decimal"),
+        NumericCrate::Inferred => Err(TypeError::NumericCrate),
+    }
+}
+
+/// The Rust type the query macros map the built-in SQL type `base` to.
+fn builtin_type(backend: Backend, base: &str, config: &MacrosConfig) -> Result<String, TypeError> {
+    let ty = match backend {
+        Backend::Postgres => {
+            let pg = |ty: &str| format!("sqlx::postgres::types::{ty}");
+
+            match base {
+                "bool" => "bool".to_owned(),
+                "char" => "i8".to_owned(),
+                "int2" => "i16".to_owned(),
+                "int4" => "i32".to_owned(),
+                "int8" => "i64".to_owned(),
+                "float4" => "f32".to_owned(),
+                "float8" => "f64".to_owned(),
+                "text" | "varchar" | "bpchar" | "name" | "citext" => "String".to_owned(),
+                "bytea" => "Vec<u8>".to_owned(),
+                "uuid" => "sqlx::types::Uuid".to_owned(),
+                "json" | "jsonb" => "sqlx::types::JsonValue".to_owned(),
+                "inet" | "cidr" => "sqlx::types::ipnetwork::IpNetwork".to_owned(),
+                "macaddr" => "sqlx::types::mac_address::MacAddress".to_owned(),
+                "bit" | "varbit" => "sqlx::types::BitVec".to_owned(),
+                "oid" => pg("Oid"),
+                "interval" => pg("PgInterval"),
+                "money" => pg("PgMoney"),
+                "ltree" => pg("PgLTree"),
+                "lquery" => pg("PgLQuery"),
+                "cube" => pg("PgCube"),
+                "point" => pg("PgPoint"),
+                "line" => pg("PgLine"),
+                "lseg" => pg("PgLSeg"),
+                "box" => pg("PgBox"),
+                "path" => pg("PgPath"),
+                "polygon" => pg("PgPolygon"),
+                "circle" => pg("PgCircle"),
+                "hstore" => pg("PgHstore"),
+                "int4range" => pg("PgRange<i32>"),
+                "int8range" => pg("PgRange<i64>"),
+                "numeric" => numeric_type(config)?.to_owned(),
+                "numrange" => pg(&format!("PgRange<{}>", numeric_type(config)?)),
+                "date" => date_time_types(config)?.date.to_owned(),
+                "time" => date_time_types(config)?.time.to_owned(),
+                "timestamp" => date_time_types(config)?.date_time.to_owned(),
+                "timestamptz" => date_time_types(config)?.date_time_tz.to_owned(),
+                "timetz" => {
+                    let types = date_time_types(config)?;
+                    pg(&format!("PgTimeTz<{}, {}>", types.time, types.offset))
+                }
+                "daterange" => pg(&format!("PgRange<{}>", date_time_types(config)?.date)),
+                "tsrange" => pg(&format!("PgRange<{}>", date_time_types(config)?.date_time)),
+                "tstzrange" => pg(&format!(
+                    "PgRange<{}>",
+                    date_time_types(config)?.date_time_tz
+                )),
+                _ => return Err(TypeError::NoMapping),
+            }
+        }
+        Backend::MySql => match base {
+            "boolean" => "bool",
+            "tinyint" => "i8",
+            "tinyint unsigned" => "u8",
+            "smallint" => "i16",
+            "smallint unsigned" => "u16",
+            "mediumint" | "int" => "i32",
+            "mediumint unsigned" | "int unsigned" => "u32",
+            "bigint" => "i64",
+            "bigint unsigned" => "u64",
+            "float" => "f32",
+            "double" | "real" => "f64",
+            "char" | "varchar" | "tinytext" | "text" | "mediumtext" | "longtext" | "enum"
+            | "set" => "String",
+            "binary" | "varbinary" | "tinyblob" | "blob" | "mediumblob" | "longblob" => "Vec<u8>",
+            "json" => "sqlx::types::JsonValue",
+            "decimal" | "numeric" => numeric_type(config)?,
+            "date" => date_time_types(config)?.date,
+            "time" => date_time_types(config)?.time,
+            "datetime" => date_time_types(config)?.date_time,
+            "timestamp" => date_time_types(config)?.date_time_tz,
+            _ => return Err(TypeError::NoMapping),
+        }
+        .to_owned(),
+        // Follows how the SQLite driver maps declared types.
+        Backend::Sqlite => match base {
+            "int4" => "i32",
+            "int8" => "i64",
+            "boolean" | "bool" => "bool",
+            "date" => date_time_types(config)?.date,
+            "time" => date_time_types(config)?.time,
+            "datetime" | "timestamp" => match config.preferred_crates.date_time {
+                // `OffsetDateTime` is listed first for the macros.
+                DateTimeCrate::Time => TIME_TYPES.date_time_tz,
+                _ => date_time_types(config)?.date_time,
+            },
+            _ if base.contains("int") => "i64",
+            _ if base.contains("char") || base.contains("clob") || base.contains("text") => {
+                "String"
+            }
+            _ if base.contains("blob") => "Vec<u8>",
+            _ if base.contains("real") || base.contains("floa") || base.contains("doub") => "f64",
+            _ => return Err(TypeError::NoMapping),
+        }
+        .to_owned(),
+    };
+
+    Ok(ty)
+}
+
+/// Strip the quotes from a (possibly qualified) name.
+fn unquote(name: &str) -> String {
+    name.replace(['"', '`'], "")
+}
+
+/// Convert `name` to `snake_case`, as used for modules and fields.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len());
+    let mut prev: Option<char> = None;
+
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase()
+                && prev.is_some_and(|prev| prev.is_ascii_lowercase() || prev.is_ascii_digit())
+            {
+                snake.push('_');
+            }
+
+            snake.push(c.to_ascii_lowercase());
+            prev = Some(c);
+        } else {
+            if !snake.is_empty() && !snake.ends_with('_') {
+                snake.push('_');
+            }
+
+            prev = None;
+        }
+    }
+
+    let snake = snake.trim_end_matches('_');
+
+    match snake.chars().next() {
+        None => "_".to_owned(),
+        Some(c) if c.is_ascii_digit() => format!("_{snake}"),
+        Some(_) => snake.to_owned(),
+    }
+}
+
+/// Convert `name` to `PascalCase`, as used for types and enum variants.
+fn pascal_case(name: &str) -> String {
+    let pascal: String = snake_case(name)
+        .split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect();
+
+    match pascal.chars().next() {
+        None => "Empty".to_owned(),
+        Some(c) if c.is_ascii_digit() => format!("_{pascal}"),
+        Some(_) => pascal,
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// The identifier of the field for the column `name`, which is a raw identifier for keywords.
+fn field_ident(name: &str) -> String {
+    let ident = snake_case(name);
+
+    match &*ident {
+        // These can't be raw identifiers.
+        "crate" | "self" | "super" => format!("{ident}_"),
+        _ if KEYWORDS.contains(&&*ident) => format!("r#{ident}"),
+        _ => ident,
+    }
+}
+
+/// The name of a module, which is also the name of its file.
+fn module_name(snake: &str) -> String {
+    if snake == TYPES_MODULE || KEYWORDS.contains(&snake) {
+        format!("{snake}_")
+    } else {
+        snake.to_owned()
+    }
+}
+
+/// Selects the type of column `a` as (schema, type name, type as `regtype`, is array),
+/// with the tables joined by [`PG_TYPE_JOINS`].
+///
+/// Domains are replaced by their base type, and arrays by their element type.
+const PG_TYPE_COLUMNS: &str =
+    "en.nspname::text, et.typname::text, et.oid::regtype::text, bt.typcategory = 'A'";
+
+const PG_TYPE_JOINS: &str = "JOIN pg_catalog.pg_type t ON t.oid = a.atttypid \
+    JOIN pg_catalog.pg_type bt \
+        ON bt.oid = CASE WHEN t.typtype = 'd' THEN t.typbasetype ELSE t.oid END \
+    JOIN pg_catalog.pg_type et \
+        ON et.oid = CASE WHEN bt.typcategory = 'A' THEN bt.typelem ELSE bt.oid END \
+    JOIN pg_catalog.pg_namespace en ON en.oid = et.typnamespace";
+
+fn pg_type(schema: &str, type_name: String, regtype: String, array: bool) -> SqlType {
+    let (mut name, base) = if schema == "pg_catalog" {
+        let name = match &*type_name {
+            "bpchar" => "CHAR".to_owned(),
+            "char" => "\"CHAR\"".to_owned(),
+            _ => type_name.to_uppercase(),
+        };
+
+        (name, type_name)
+    } else {
+        // User-defined and extension types are named as they are found by the `search_path`.
+        (regtype.clone(), regtype)
+    };
+
+    if array {
+        name.push_str("[]");
+    }
+
+    SqlType { name, base, array }
+}
+
+async fn introspect_postgres(conn: &mut AnyConnection) -> anyhow::Result<Database> {
+    let mut database = Database {
+        backend: Backend::Postgres,
+        qualifier: None,
+        relations: BTreeMap::new(),
+        types: BTreeMap::new(),
+    };
+
+    // language=PostgreSQL
+    let columns: Vec<(String, bool, String, bool, String, String, String, bool)> =
+        sqlx::query_as(AssertSqlSafe(format!(
+            "SELECT c.oid::regclass::text, \
+                 c.relkind IN ('v', 'm'), \
+                 a.attname::text, \
+                 a.attnotnull, \
+                 {PG_TYPE_COLUMNS} \
+             FROM pg_catalog.pg_attribute a \
+             JOIN pg_catalog.pg_class c ON c.oid = a.attrelid \
+             JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace \
+             {PG_TYPE_JOINS} \
+             WHERE a.attnum > 0 AND NOT a.attisdropped AND c.relkind IN ('r', 'p', 'v', 'm') \
+                 AND {} \
+             ORDER BY 1, a.attnum",
+            pg_user_objects("c.oid")
+        )))
+        .fetch_all(&mut *conn)
+        .await?;
+
+    for (relation, view, name, not_null, schema, type_name, regtype, array) in columns {
+        database
+            .relations
+            .entry(relation)
+            .or_insert_with(|| Relation {
+                view,
+                fields: Vec::new(),
+            })
+            .fields
+            .push(Field {
+                name,
+                ty: pg_type(&schema, type_name, regtype, array),
+                not_null,
+            });
+    }
+
+    // language=PostgreSQL
+    let enums: Vec<(String, String)> = sqlx::query_as(AssertSqlSafe(format!(
+        "SELECT t.oid::regtype::text, e.enumlabel::text \
+         FROM pg_catalog.pg_enum e \
+         JOIN pg_catalog.pg_type t ON t.oid = e.enumtypid \
+         JOIN pg_catalog.pg_namespace n ON n.oid = t.typnamespace \
+         WHERE {} \
+         ORDER BY 1, e.enumsortorder",
+        pg_user_objects("t.oid")
+    )))
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (name, label) in enums {
+        if let UserType::Enum(labels) = database
+            .types
+            .entry(name)
+            .or_insert_with(|| UserType::Enum(Vec::new()))
+        {
+            labels.push(label);
+        }
+    }
+
+    // Only standalone composite types; those of tables are covered by the tables.
+    // language=PostgreSQL
+    let attributes: Vec<(String, String, String, String, String, bool)> =
+        sqlx::query_as(AssertSqlSafe(format!(
+            "SELECT ct.oid::regtype::text, \
+                 a.attname::text, \
+                 {PG_TYPE_COLUMNS} \
+             FROM pg_catalog.pg_type ct \
+             JOIN pg_catalog.pg_class c ON c.oid = ct.typrelid \
+             JOIN pg_catalog.pg_namespace n ON n.oid = ct.typnamespace \
+             JOIN pg_catalog.pg_attribute a ON a.attrelid = c.oid \
+             {PG_TYPE_JOINS} \
+             WHERE ct.typtype = 'c' AND c.relkind = 'c' AND a.attnum > 0 AND NOT a.attisdropped \
+                 AND {} \
+             ORDER BY 1, a.attnum",
+            pg_user_objects("ct.oid")
+        )))
+        .fetch_all(&mut *conn)
+        .await?;
+
+    for (name, attribute, schema, type_name, regtype, array) in attributes {
+        if let UserType::Composite(fields) = database
+            .types
+            .entry(name)
+            .or_insert_with(|| UserType::Composite(Vec::new()))
+        {
+            // The attributes of composite types are always nullable.
+            fields.push(Field {
+                name: attribute,
+                ty: pg_type(&schema, type_name, regtype, array),
+                not_null: false,
+            });
+        }
+    }
+
+    Ok(database)
+}
+
+async fn introspect_mysql(conn: &mut AnyConnection) -> anyhow::Result<Database> {
+    // language=MySQL
+    let (qualifier,): (String,) = sqlx::query_as("SELECT CAST(DATABASE() AS CHAR)")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    let mut database = Database {
+        backend: Backend::MySql,
+        qualifier: Some(qualifier),
+        relations: BTreeMap::new(),
+        types: BTreeMap::new(),
+    };
+
+    // Columns of `information_schema` are cast to `CHAR` as some servers report them as binary.
+    // language=MySQL
+    let columns: Vec<(String, String, String, String, String, String)> = sqlx::query_as(
+        "SELECT CAST(c.TABLE_NAME AS CHAR), \
+             CAST(t.TABLE_TYPE AS CHAR), \
+             CAST(c.COLUMN_NAME AS CHAR), \
+             CAST(c.DATA_TYPE AS CHAR), \
+             CAST(c.COLUMN_TYPE AS CHAR), \
+             CAST(c.IS_NULLABLE AS CHAR) \
+         FROM information_schema.COLUMNS c \
+         JOIN information_schema.TABLES t \
+             ON t.TABLE_SCHEMA = c.TABLE_SCHEMA AND t.TABLE_NAME = c.TABLE_NAME \
+         WHERE c.TABLE_SCHEMA = DATABASE() \
+         ORDER BY c.TABLE_NAME, c.ORDINAL_POSITION",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (relation, kind, name, data_type, column_type, nullable) in columns {
+        let data_type = data_type.to_lowercase();
+        let column_type = column_type.to_lowercase();
+
+        // The driver maps `TINYINT(1)` to `bool`.
+        let base = if column_type.starts_with("tinyint(1)") {
+            "boolean".to_owned()
+        } else if data_type.ends_with("int") && column_type.contains("unsigned") {
+            format!("{data_type} unsigned")
+        } else {
+            data_type
+        };
+
+        database
+            .relations
+            .entry(relation)
+            .or_insert_with(|| Relation {
+                view: kind == "VIEW",
+                fields: Vec::new(),
+            })
+            .fields
+            .push(Field {
+                name,
+                ty: SqlType {
+                    name: base.to_uppercase(),
+                    base,
+                    array: false,
+                },
+                not_null: nullable == "NO",
+            });
+    }
+
+    Ok(database)
+}
+
+async fn introspect_sqlite(conn: &mut AnyConnection) -> anyhow::Result<Database> {
+    let mut database = Database {
+        backend: Backend::Sqlite,
+        qualifier: None,
+        relations: BTreeMap::new(),
+        types: BTreeMap::new(),
+    };
+
+    // language=SQLite
+    let columns: Vec<(String, String, String, String, i64, i64)> = sqlx::query_as(
+        r#"SELECT m.name, m.type, p.name, p.type, p."notnull", p.pk
+           FROM sqlite_schema m
+           JOIN pragma_table_info(m.name) p
+           WHERE m.type IN ('table', 'view') AND m.name NOT LIKE 'sqlite\_%' ESCAPE '\'
+           ORDER BY m.name, p.cid"#,
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut primary_keys: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+
+    for (relation_name, kind, name, declared_type, not_null, pk) in columns {
+        let relation = database
+            .relations
+            .entry(relation_name.clone())
+            .or_insert_with(|| Relation {
+                view: kind == "view",
+                fields: Vec::new(),
+            });
+
+        if pk > 0 {
+            primary_keys
+                .entry(relation_name)
+                .or_default()
+                .push(relation.fields.len());
+        }
+
+        relation.fields.push(Field {
+            name,
+            ty: SqlType {
+                name: declared_type.to_uppercase(),
+                base: declared_type.to_lowercase(),
+                array: false,
+            },
+            not_null: not_null != 0,
+        });
+    }
+
+    // An `INTEGER PRIMARY KEY` is an alias for the `rowid`, which can't be `NULL`.
+    for (relation, columns) in primary_keys {
+        if let (Some(relation), [column]) = (database.relations.get_mut(&relation), &*columns) {
+            let field = &mut relation.fields[*column];
+
+            if field.ty.base == "integer" {
+                field.not_null = true;
+            }
+        }
+    }
+
+    Ok(database)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, ty: &str, base: &str, not_null: bool) -> Field {
+        Field {
+            name: name.to_owned(),
+            ty: SqlType {
+                name: ty.to_owned(),
+                base: base.to_owned(),
+                array: ty.ends_with("[]"),
+            },
+            not_null,
+        }
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(snake_case("userId"), "user_id");
+        assert_eq!(snake_case("User Name"), "user_name");
+        assert_eq!(snake_case("2fa_secret"), "_2fa_secret");
+        assert_eq!(pascal_case("order_items"), "OrderItems");
+        assert_eq!(pascal_case("in progress"), "InProgress");
+        assert_eq!(field_ident("type"), "r#type");
+        assert_eq!(field_ident("self"), "self_");
+        assert_eq!(module_name("types"), "types_");
+        assert_eq!(unquote(r#""My Schema".users"#), "My Schema.users");
+    }
+
+    #[test]
+    fn generate_postgres() {
+        let database = Database {
+            backend: Backend::Postgres,
+            qualifier: None,
+            relations: BTreeMap::from([
+                (
+                    "users".to_owned(),
+                    Relation {
+                        view: false,
+                        fields: vec![
+                            field("id", "INT8", "int8", true),
+                            field("type", "TEXT", "text", true),
+                            field("displayName", "VARCHAR", "varchar", false),
+                            field("mood", "mood", "mood", true),
+                            field("tags", "TEXT[]", "text", true),
+                            field("created_at", "TIMESTAMPTZ", "timestamptz", true),
+                            field("external_id", "UUID", "uuid", true),
+                        ],
+                    },
+                ),
+                (
+                    "active_users".to_owned(),
+                    Relation {
+                        view: true,
+                        fields: vec![field("id", "INT8", "int8", false)],
+                    },
+                ),
+            ]),
+            types: BTreeMap::from([
+                (
+                    "mood".to_owned(),
+                    UserType::Enum(vec!["happy".to_owned(), "not sure".to_owned()]),
+                ),
+                (
+                    "address".to_owned(),
+                    UserType::Composite(vec![
+                        field("street", "TEXT", "text", false),
+                        field("mood", "mood", "mood", false),
+                    ]),
+                ),
+            ]),
+        };
+
+        let mut config = MacrosConfig::default();
+        config.preferred_crates.date_time = DateTimeCrate::Chrono;
+        config
+            .type_overrides
+            .insert("UUID".into(), "crate::Uuid".into());
+        config.nullability_overrides.insert(
+            "active_users".into(),
+            BTreeMap::from([("id".into(), Nullability::NonNull)]),
+        );
+
+        let files = database.generate(&config).unwrap();
+
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            ["active_users.rs", "mod.rs", "types.rs", "users.rs"]
+        );
+
+        assert_eq!(
+            files["mod.rs"],
+            "// This file is generated by `sqlx generate`. Do not edit it by hand.
+
+pub mod types;
+
+pub mod active_users;
+pub mod users;
+
+pub use active_users::ActiveUsers;
+pub use users::Users;
+"
+        );
+
+        assert_eq!(
+            files["users.rs"],
+            "// This file is generated by `sqlx generate`. Do not edit it by hand.
+
+/// The `users` table.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Users {
+    pub id: i64,
+    pub r#type: String,
+    #[sqlx(rename = \"displayName\")]
+    pub display_name: Option<String>,
+    pub mood: super::types::Mood,
+    pub tags: Vec<String>,
+    pub created_at: sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>,
+    pub external_id: crate::Uuid,
+}
+"
+        );
+
+        assert!(files["active_users.rs"].contains("pub id: i64,"));
+
+        assert_eq!(
+            files["types.rs"],
+            "// This file is generated by `sqlx generate`. Do not edit it by hand.
+
+/// The `address` composite type.
+#[derive(Debug, Clone, sqlx::Type)]
+#[sqlx(type_name = \"address\")]
+pub struct Address {
+    pub street: Option<String>,
+    pub mood: Option<Mood>,
+}
+
+/// The `mood` enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = \"mood\")]
+pub enum Mood {
+    #[sqlx(rename = \"happy\")]
+    Happy,
+    #[sqlx(rename = \"not sure\")]
+    NotSure,
+}
+"
+        );
+
+        // `TIMESTAMPTZ` could be mapped with either crate.
+        config.preferred_crates.date_time = DateTimeCrate::Inferred;
+
+        let error = database.generate(&config).unwrap_err().to_string();
+        assert!(error.contains("column users.created_at"), "{error}");
+    }
+
+    #[test]
+    fn mysql_types() {
+        let mut config = MacrosConfig::default();
+
+        for (base, ty) in [
+            ("boolean", "bool"),
+            ("int unsigned", "u32"),
+            ("varchar", "String"),
+            ("longblob", "Vec<u8>"),
+        ] {
+            assert_eq!(
+                builtin_type(Backend::MySql, base, &config).ok().as_deref(),
+                Some(ty)
+            );
+        }
+
+        assert!(builtin_type(Backend::MySql, "decimal", &config).is_err());
+
+        config.preferred_crates.numeric = NumericCrate::RustDecimal;
+        assert_eq!(
+            builtin_type(Backend::MySql, "decimal", &config)
+                .ok()
+                .as_deref(),
+            Some("sqlx::types::Decimal")
+        );
+    }
+}
@@ -27,17 +27,21 @@ use futures_util::TryFutureExt;
 use sqlx::AnyConnection;
 use tokio::{select, signal};
 
+use crate::migrate::DryRun;
 use crate::opt::{Command, ConnectOpts, DatabaseCommand, MigrateCommand};
 
 pub mod database;
+pub mod generate;
 pub mod metadata;
 // mod migration;
 // mod migrator;
 #[cfg(feature = "completions")]
 pub mod completions;
+pub mod lint;
 pub mod migrate;
 pub mod opt;
 pub mod prepare;
+pub mod schema;
 
 pub use crate::opt::Opt;
 
@@ -84,6 +88,8 @@ async fn do_run(opt: Opt) -> anyhow::Result<()> {
                 source,
                 config,
                 dry_run,
+                sql,
+                lock_plan,
                 ignore_missing,
                 mut connect_opts,
                 target_version,
@@ -92,6 +98,12 @@ async fn do_run(opt: Opt) -> anyhow::Result<()> {
 
                 connect_opts.populate_db_url(&config)?;
 
+                let dry_run = match (dry_run, sql) {
+                    (false, _) => DryRun::Off,
+                    (true, false) => DryRun::List,
+                    (true, true) => DryRun::Sql { lock_plan },
+                };
+
                 migrate::run(
                     &config,
                     &source,
@@ -135,6 +147,75 @@ async fn do_run(opt: Opt) -> anyhow::Result<()> {
 
                 migrate::info(&config, &source, &connect_opts).await?
             }
+            MigrateCommand::Lint {
+                source,
+                config,
+                mut connect_opts,
+                all,
+            } => {
+                let config = config.load_config().await?;
+
+                connect_opts.populate_db_url(&config)?;
+
+                migrate::lint(&config, &source, &connect_opts, all).await?
+            }
+            MigrateCommand::Squash {
+                source,
+                config,
+                version,
+                description,
+            } => {
+                let config = config.load_config().await?;
+
+                migrate::squash(&config, &source, version, &description)?
+            }
+            MigrateCommand::Repair {
+                source,
+                config,
+                mut connect_opts,
+                yes,
+            } => {
+                let config = config.load_config().await?;
+
+                connect_opts.populate_db_url(&config)?;
+
+                migrate::repair(&config, &source, &connect_opts, !yes).await?
+            }
+            MigrateCommand::Baseline {
+                source,
+                config,
+                mut connect_opts,
+                version,
+            } => {
+                let config = config.load_config().await?;
+
+                connect_opts.populate_db_url(&config)?;
+
+                migrate::baseline(&config, &source, &connect_opts, version).await?
+            }
+            MigrateCommand::MarkDirtyResolved {
+                config,
+                mut connect_opts,
+                rolled_back,
+            } => {
+                let config = config.load_config().await?;
+
+                connect_opts.populate_db_url(&config)?;
+
+                migrate::mark_dirty_resolved(&config, &connect_opts, rolled_back).await?
+            }
+            MigrateCommand::Verify {
+                source,
+                config,
+                mut connect_opts,
+                scratch_database_url,
+            } => {
+                let config = config.load_config().await?;
+
+                connect_opts.populate_db_url(&config)?;
+
+                migrate::verify(&config, &source, &connect_opts, scratch_database_url).await?
+            }
             MigrateCommand::BuildScript {
                 source,
                 config,
@@ -189,8 +270,31 @@ async fn do_run(opt: Opt) -> anyhow::Result<()> {
                 connect_opts.populate_db_url(&config)?;
                 database::setup(&config, &source, &connect_opts).await?
             }
+            DatabaseCommand::DumpSchema {
+                config,
+                mut connect_opts,
+                output,
+                check,
+            } => {
+                let config = config.load_config().await?;
+
+                connect_opts.populate_db_url(&config)?;
+                database::dump_schema(&config, &connect_opts, &output, check).await?
+            }
         },
 
+        Command::Generate {
+            config,
+            mut connect_opts,
+            output,
+            check,
+        } => {
+            let config = config.load_config().await?;
+
+            connect_opts.populate_db_url(&config)?;
+            generate::run(&config, &connect_opts, &output, check).await?
+        }
+
         Command::Prepare {
             check,
             all,
@@ -0,0 +1,912 @@
+//! Checks for patterns in migrations that can lock busy tables or break running instances
+//! of an application, for `sqlx migrate lint`, and a preview of the locks a migration takes,
+//! for `sqlx migrate run --dry-run --sql --lock-plan`.
+//!
+//! This is not a full SQL parser: statements are split into tokens, with comments and
+//! quoted strings accounted for, and then matched against the patterns of each rule.
+
+use std::collections::HashSet;
+
+use anyhow::bail;
+
+use crate::config::migrate::LintRule;
+
+/// The SQL dialect a migration is written in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Dialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl Dialect {
+    /// Infer the dialect from the scheme of a database URL.
+    pub fn from_url(url: &str) -> anyhow::Result<Self> {
+        let scheme = url.split_once(':').map_or(url, |(scheme, _)| scheme);
+
+        match scheme {
+            "postgres" | "postgresql" => Ok(Self::Postgres),
+            "mysql" | "mariadb" => Ok(Self::MySql),
+            "sqlite" => Ok(Self::Sqlite),
+            _ => bail!("cannot lint migrations for database URL scheme {scheme:?}"),
+        }
+    }
+}
+
+/// A dangerous pattern found in a migration.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Lint {
+    pub rule: LintRule,
+    /// The line the statement starts on, counting from 1.
+    pub line: usize,
+}
+
+impl Lint {
+    /// Why the pattern is dangerous, and what to do instead.
+    pub fn message(&self) -> &'static str {
+        match self.rule {
+            LintRule::CreateIndexNotConcurrently => {
+                "`CREATE INDEX` blocks writes to the table until the index is built; \
+                 use `CREATE INDEX CONCURRENTLY` in a migration with `-- no-transaction`"
+            }
+            LintRule::ConcurrentlyInTransaction => {
+                "`CONCURRENTLY` cannot run in a transaction; put the statement alone \
+                 in a migration starting with `-- no-transaction`"
+            }
+            LintRule::AddColumnNotNull => {
+                "adding a `NOT NULL` column without a `DEFAULT` fails if the table has rows, \
+                 and breaks inserts from instances of the application that aren't updated yet"
+            }
+            LintRule::AlterColumnType => {
+                "changing the type of a column may rewrite the table while holding \
+                 an exclusive lock on it"
+            }
+            LintRule::DropColumn => {
+                "dropping a column breaks instances of the application still using it; \
+                 stop using it in a prior deploy, then allow this rule for the migration \
+                 in `[migrate.lint.allow-migrations]`"
+            }
+        }
+    }
+}
+
+/// Check the SQL of a migration.
+///
+/// `no_tx` is whether the migration starts with `-- no-transaction`.
+pub fn lint_sql(dialect: Dialect, sql: &str, no_tx: bool) -> Vec<Lint> {
+    let statements = statements(dialect, sql);
+
+    let mut lints = Vec::new();
+
+    // Tables created in the same migration are new, so nothing is using them yet.
+    let mut created_tables = HashSet::new();
+
+    for statement in &statements {
+        let tokens = &statement.tokens[..];
+
+        let mut lint = |rule| {
+            lints.push(Lint {
+                rule,
+                line: statement.line,
+            })
+        };
+
+        let concurrently = tokens.iter().any(|t| t.is("CONCURRENTLY"));
+
+        if dialect == Dialect::Postgres
+            && concurrently
+            && (!no_tx || statements.len() > 1)
+            && (starts_with(tokens, &["CREATE"])
+                || starts_with(tokens, &["DROP", "INDEX"])
+                || starts_with(tokens, &["REINDEX"]))
+        {
+            lint(LintRule::ConcurrentlyInTransaction);
+        }
+
+        if let Some(i) = create_table(tokens) {
+            if let Some((name, _)) = object_name(tokens, i) {
+                created_tables.insert(name);
+            }
+        } else if let Some(i) = create_index(tokens) {
+            let table = tokens[i..]
+                .iter()
+                .position(|t| t.is("ON"))
+                .map(|on| i + on + 1)
+                .map(|i| skip(tokens, i, "ONLY"))
+                .and_then(|i| object_name(tokens, i))
+                .map(|(name, _)| name);
+
+            let is_new = table.is_some_and(|table| created_tables.contains(&table));
+
+            if dialect == Dialect::Postgres && !concurrently && !is_new {
+                lint(LintRule::CreateIndexNotConcurrently);
+            }
+        } else if starts_with(tokens, &["ALTER", "TABLE"]) {
+            let i = skip_all(tokens, 2, &["IF", "EXISTS"]);
+            let i = skip(tokens, i, "ONLY");
+
+            let Some((table, i)) = object_name(tokens, i) else {
+                continue;
+            };
+
+            if created_tables.contains(&table) {
+                continue;
+            }
+
+            for action in split_top_level(&tokens[i..]) {
+                if let Some(rule) = alter_table_action(dialect, action) {
+                    lint(rule);
+                }
+            }
+        }
+    }
+
+    lints
+}
+
+fn alter_table_action(dialect: Dialect, action: &[Token<'_>]) -> Option<LintRule> {
+    const NOT_COLUMNS: &[&str] = &[
+        "CONSTRAINT",
+        "INDEX",
+        "KEY",
+        "PRIMARY",
+        "UNIQUE",
+        "FOREIGN",
+        "CHECK",
+        "FULLTEXT",
+        "SPATIAL",
+        "PARTITION",
+        "EXCLUDE",
+    ];
+
+    let first = action.first()?;
+
+    if first.is("ADD") {
+        let i = skip(action, 1, "COLUMN");
+
+        if i == 1 && action.get(1).is_some_and(|t| t.is_any(NOT_COLUMNS)) {
+            return None;
+        }
+
+        let not_null = action.windows(2).any(|w| w[0].is("NOT") && w[1].is("NULL"));
+
+        // These all give existing rows a value.
+        let has_default = action.iter().any(|t| {
+            t.is_any(&[
+                "DEFAULT",
+                "GENERATED",
+                "AUTO_INCREMENT",
+                "SERIAL",
+                "SMALLSERIAL",
+                "BIGSERIAL",
+            ])
+        });
+
+        return (not_null && !has_default).then_some(LintRule::AddColumnNotNull);
+    }
+
+    if first.is("DROP") {
+        let i = skip(action, 1, "COLUMN");
+
+        if i == 1 && action.get(1).is_some_and(|t| t.is_any(NOT_COLUMNS)) {
+            return None;
+        }
+
+        return Some(LintRule::DropColumn);
+    }
+
+    if dialect == Dialect::MySql && first.is_any(&["MODIFY", "CHANGE"]) {
+        return Some(LintRule::AlterColumnType);
+    }
+
+    if dialect == Dialect::Postgres && first.is("ALTER") {
+        let i = skip(action, 1, "COLUMN");
+        let (_, i) = object_name(action, i)?;
+        let i = skip_all(action, i, &["SET", "DATA"]);
+
+        return action
+            .get(i)
+            .is_some_and(|t| t.is("TYPE"))
+            .then_some(LintRule::AlterColumnType);
+    }
+
+    None
+}
+
+/// A table-level lock mode in Postgres, from weakest to strongest.
+///
+/// See <https://www.postgresql.org/docs/current/explicit-locking.html#LOCKING-TABLES>.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LockMode {
+    AccessShare,
+    RowShare,
+    RowExclusive,
+    ShareUpdateExclusive,
+    Share,
+    ShareRowExclusive,
+    Exclusive,
+    AccessExclusive,
+}
+
+impl LockMode {
+    const ALL: [LockMode; 8] = [
+        LockMode::AccessShare,
+        LockMode::RowShare,
+        LockMode::RowExclusive,
+        LockMode::ShareUpdateExclusive,
+        LockMode::Share,
+        LockMode::ShareRowExclusive,
+        LockMode::Exclusive,
+        LockMode::AccessExclusive,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LockMode::AccessShare => "ACCESS SHARE",
+            LockMode::RowShare => "ROW SHARE",
+            LockMode::RowExclusive => "ROW EXCLUSIVE",
+            LockMode::ShareUpdateExclusive => "SHARE UPDATE EXCLUSIVE",
+            LockMode::Share => "SHARE",
+            LockMode::ShareRowExclusive => "SHARE ROW EXCLUSIVE",
+            LockMode::Exclusive => "EXCLUSIVE",
+            LockMode::AccessExclusive => "ACCESS EXCLUSIVE",
+        }
+    }
+
+    /// Whether the mode blocks reads or writes of the table, in addition to schema changes.
+    pub fn blocks(self) -> Option<&'static str> {
+        match self {
+            LockMode::AccessExclusive => Some("blocks reads and writes"),
+            LockMode::Share | LockMode::ShareRowExclusive | LockMode::Exclusive => {
+                Some("blocks writes")
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse the mode of `LOCK TABLE ... IN <mode> MODE`.
+    fn parse(tokens: &[Token<'_>]) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| {
+            let words: Vec<_> = mode.name().split(' ').collect();
+            tokens.len() == words.len() && starts_with(tokens, &words)
+        })
+    }
+}
+
+/// A table lock taken by a statement in a migration.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Lock {
+    pub mode: LockMode,
+    pub relation: String,
+    /// The line the statement starts on, counting from 1.
+    pub line: usize,
+}
+
+/// Preview the table locks Postgres takes to run `sql`.
+///
+/// Like the lints, this goes by the kind of each statement rather than asking the database,
+/// so it is a best effort. Tables created in the same migration are left out, since nothing
+/// else can be using them yet.
+pub fn postgres_locks(sql: &str) -> Vec<Lock> {
+    let mut locks = Vec::new();
+    let mut created_tables = HashSet::new();
+
+    for statement in statements(Dialect::Postgres, sql) {
+        let tokens = &statement.tokens[..];
+        let concurrently = tokens.iter().any(|t| t.is("CONCURRENTLY"));
+
+        let mut lock = |mode, relation: String| {
+            if !created_tables.contains(&relation) {
+                locks.push(Lock {
+                    mode,
+                    relation,
+                    line: statement.line,
+                });
+            }
+        };
+
+        if let Some(i) = create_table(tokens) {
+            // Foreign keys lock the tables they reference.
+            for relation in referenced_tables(tokens) {
+                lock(LockMode::ShareRowExclusive, relation);
+            }
+
+            if let Some((name, _)) = object_name(tokens, i) {
+                created_tables.insert(name);
+            }
+        } else if let Some(i) = create_index(tokens) {
+            if let Some(table) = name_after(tokens, i, "ON") {
+                if concurrently {
+                    lock(LockMode::ShareUpdateExclusive, table);
+                } else {
+                    lock(LockMode::Share, table);
+                }
+            }
+        } else if starts_with(tokens, &["ALTER", "TABLE"]) {
+            let i = skip_all(tokens, 2, &["IF", "EXISTS"]);
+            let i = skip(tokens, i, "ONLY");
+
+            let Some((table, i)) = object_name(tokens, i) else {
+                continue;
+            };
+
+            let mode = split_top_level(&tokens[i..])
+                .into_iter()
+                .map(alter_table_lock)
+                .max()
+                .unwrap_or(LockMode::AccessExclusive);
+
+            lock(mode, table);
+
+            for relation in referenced_tables(&tokens[i..]) {
+                lock(LockMode::ShareRowExclusive, relation);
+            }
+        } else if starts_with(tokens, &["DROP", "TABLE"])
+            || starts_with(tokens, &["TRUNCATE"])
+            || starts_with(tokens, &["LOCK"])
+        {
+            let i = skip(tokens, 1, "TABLE");
+            let i = skip_all(tokens, i, &["IF", "EXISTS"]);
+            let i = skip(tokens, i, "ONLY");
+
+            let end = tokens
+                .iter()
+                .position(|t| t.is_any(&["IN", "NOWAIT", "CASCADE", "RESTRICT", "RESTART"]))
+                .unwrap_or(tokens.len());
+
+            let mode = if tokens[0].is("LOCK") {
+                let mode_end =
+                    tokens.len() - usize::from(tokens.last().is_some_and(|t| t.is("MODE")));
+
+                tokens
+                    .get(end + 1..mode_end)
+                    .and_then(LockMode::parse)
+                    .unwrap_or(LockMode::AccessExclusive)
+            } else {
+                LockMode::AccessExclusive
+            };
+
+            for table in split_top_level(tokens.get(i..end).unwrap_or_default()) {
+                if let Some((table, _)) = object_name(table, skip(table, 0, "ONLY")) {
+                    lock(mode, table);
+                }
+            }
+        } else if starts_with(tokens, &["DROP", "INDEX"]) {
+            let i = skip(tokens, 2, "CONCURRENTLY");
+            let i = skip_all(tokens, i, &["IF", "EXISTS"]);
+
+            if let Some((index, _)) = object_name(tokens, i) {
+                let mode = if concurrently {
+                    LockMode::ShareUpdateExclusive
+                } else {
+                    LockMode::AccessExclusive
+                };
+
+                lock(mode, format!("the table of index {index}"));
+            }
+        } else if starts_with(tokens, &["CREATE", "TRIGGER"])
+            || starts_with(tokens, &["CREATE", "OR", "REPLACE", "TRIGGER"])
+        {
+            if let Some(table) = name_after(tokens, 2, "ON") {
+                lock(LockMode::ShareRowExclusive, table);
+            }
+        } else if starts_with(tokens, &["REFRESH", "MATERIALIZED", "VIEW"]) {
+            let i = skip(tokens, 3, "CONCURRENTLY");
+
+            if let Some((view, _)) = object_name(tokens, i) {
+                let mode = if concurrently {
+                    LockMode::Exclusive
+                } else {
+                    LockMode::AccessExclusive
+                };
+
+                lock(mode, view);
+            }
+        } else if starts_with(tokens, &["INSERT", "INTO"])
+            || starts_with(tokens, &["DELETE", "FROM"])
+            || starts_with(tokens, &["MERGE", "INTO"])
+        {
+            let i = skip(tokens, 2, "ONLY");
+
+            if let Some((table, _)) = object_name(tokens, i) {
+                lock(LockMode::RowExclusive, table);
+            }
+        } else if starts_with(tokens, &["UPDATE"]) {
+            let i = skip(tokens, 1, "ONLY");
+
+            if let Some((table, _)) = object_name(tokens, i) {
+                lock(LockMode::RowExclusive, table);
+            }
+        }
+    }
+
+    locks
+}
+
+fn alter_table_lock(action: &[Token<'_>]) -> LockMode {
+    if action.first().is_some_and(|t| t.is("VALIDATE"))
+        || starts_with(action, &["SET", "STATISTICS"])
+        || starts_with(action, &["CLUSTER", "ON"])
+        || starts_with(action, &["SET", "WITHOUT", "CLUSTER"])
+    {
+        return LockMode::ShareUpdateExclusive;
+    }
+
+    // `ADD [CONSTRAINT <name>] FOREIGN KEY`
+    let i = skip(action, 1, "CONSTRAINT");
+    let i = if i > 1 { i + 1 } else { i };
+
+    if action.first().is_some_and(|t| t.is("ADD")) && skip_all(action, i, &["FOREIGN", "KEY"]) > i {
+        return LockMode::ShareRowExclusive;
+    }
+
+    LockMode::AccessExclusive
+}
+
+/// The tables after each `REFERENCES`.
+fn referenced_tables(tokens: &[Token<'_>]) -> Vec<String> {
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| t.is("REFERENCES"))
+        .filter_map(|(i, _)| object_name(tokens, i + 1))
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// The name following the first `keyword` at or after `i`, skipping `ONLY`.
+fn name_after(tokens: &[Token<'_>], i: usize, keyword: &str) -> Option<String> {
+    let keyword = i + tokens.get(i..)?.iter().position(|t| t.is(keyword))?;
+    let i = skip(tokens, keyword + 1, "ONLY");
+
+    object_name(tokens, i).map(|(name, _)| name)
+}
+
+/// `CREATE [TEMPORARY | UNLOGGED ...] TABLE [IF NOT EXISTS]`, returning the index of the name.
+fn create_table(tokens: &[Token<'_>]) -> Option<usize> {
+    if !tokens.first()?.is("CREATE") {
+        return None;
+    }
+
+    let table = tokens
+        .iter()
+        .take(4)
+        .position(|t| t.is("TABLE"))
+        .filter(|&i| {
+            tokens[1..i]
+                .iter()
+                .all(|t| t.is_any(&["TEMP", "TEMPORARY", "UNLOGGED", "GLOBAL", "LOCAL"]))
+        })?;
+
+    Some(skip_all(tokens, table + 1, &["IF", "NOT", "EXISTS"]))
+}
+
+/// `CREATE [UNIQUE] INDEX`, returning the index after `INDEX`.
+fn create_index(tokens: &[Token<'_>]) -> Option<usize> {
+    if starts_with(tokens, &["CREATE", "INDEX"]) {
+        Some(2)
+    } else if starts_with(tokens, &["CREATE", "UNIQUE", "INDEX"]) {
+        Some(3)
+    } else {
+        None
+    }
+}
+
+/// Parse a possibly schema-qualified name starting at `i`, returning it and the index after it.
+///
+/// Unquoted parts are lowercased, to match how Postgres folds them.
+fn object_name(tokens: &[Token<'_>], mut i: usize) -> Option<(String, usize)> {
+    let mut name = String::new();
+
+    loop {
+        match tokens.get(i)? {
+            Token::Word(word) => name.push_str(&word.to_lowercase()),
+            Token::Quoted(quoted) => name.push_str(&quoted[1..quoted.len() - 1]),
+            Token::Punct(_) => return None,
+        }
+
+        i += 1;
+
+        if !matches!(tokens.get(i), Some(Token::Punct('.'))) {
+            return Some((name, i));
+        }
+
+        name.push('.');
+        i += 1;
+    }
+}
+
+fn starts_with(tokens: &[Token<'_>], keywords: &[&str]) -> bool {
+    tokens.len() >= keywords.len() && tokens.iter().zip(keywords).all(|(t, k)| t.is(k))
+}
+
+/// Skip `keyword` at `i` if it's there.
+fn skip(tokens: &[Token<'_>], i: usize, keyword: &str) -> usize {
+    if tokens.get(i).is_some_and(|t| t.is(keyword)) {
+        i + 1
+    } else {
+        i
+    }
+}
+
+/// Skip the sequence of `keywords` at `i` if it's there.
+fn skip_all(tokens: &[Token<'_>], i: usize, keywords: &[&str]) -> usize {
+    if starts_with(tokens.get(i..).unwrap_or_default(), keywords) {
+        i + keywords.len()
+    } else {
+        i
+    }
+}
+
+/// Split on commas outside of parentheses.
+fn split_top_level<'t, 'a>(tokens: &'t [Token<'a>]) -> Vec<&'t [Token<'a>]> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Punct('(') => depth += 1,
+            Token::Punct(')') => depth = depth.saturating_sub(1),
+            Token::Punct(',') if depth == 0 => {
+                parts.push(&tokens[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+
+    parts.push(&tokens[start..]);
+    parts
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token<'a> {
+    /// A keyword or unquoted identifier.
+    Word(&'a str),
+    /// A quoted identifier or string, including the quotes.
+    Quoted(&'a str),
+    Punct(char),
+}
+
+impl Token<'_> {
+    fn is(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word(word) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn is_any(&self, keywords: &[&str]) -> bool {
+        keywords.iter().any(|keyword| self.is(keyword))
+    }
+}
+
+#[derive(Debug)]
+struct Statement<'a> {
+    line: usize,
+    tokens: Vec<Token<'a>>,
+}
+
+/// Split `sql` into statements of tokens, skipping comments.
+fn statements(dialect: Dialect, sql: &str) -> Vec<Statement<'_>> {
+    let bytes = sql.as_bytes();
+
+    let mut statements = Vec::new();
+    let mut current = Statement {
+        line: 1,
+        tokens: Vec::new(),
+    };
+
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+
+        let token = match bytes[i] {
+            b';' => {
+                i += 1;
+
+                if !current.tokens.is_empty() {
+                    statements.push(std::mem::replace(
+                        &mut current,
+                        Statement {
+                            line,
+                            tokens: Vec::new(),
+                        },
+                    ));
+                }
+
+                continue;
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = find(bytes, i, b"\n").unwrap_or(bytes.len());
+                continue;
+            }
+            b'#' if dialect == Dialect::MySql => {
+                i = find(bytes, i, b"\n").unwrap_or(bytes.len());
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = find(bytes, i + 2, b"*/").map_or(bytes.len(), |end| end + 2);
+                line += count_lines(&bytes[start..i]);
+                continue;
+            }
+            b if b.is_ascii_whitespace() => {
+                line += usize::from(b == b'\n');
+                i += 1;
+                continue;
+            }
+            quote @ (b'\'' | b'"' | b'`') => {
+                // MySQL strings, and Postgres strings prefixed with `E`, use backslash escapes.
+                let backslash_escapes = quote != b'`'
+                    && (dialect == Dialect::MySql
+                        || dialect == Dialect::Postgres
+                            && quote == b'\''
+                            && matches!(current.tokens.last(), Some(Token::Word("E" | "e")))
+                            && bytes[i - 1].is_ascii_alphabetic());
+
+                i = end_of_quoted(bytes, i, quote, backslash_escapes);
+                Token::Quoted(&sql[start..i])
+            }
+            b'$' if dialect == Dialect::Postgres => match dollar_quote_tag(bytes, i) {
+                Some(tag) => {
+                    i = find(bytes, i + tag.len(), tag).map_or(bytes.len(), |end| end + tag.len());
+                    Token::Quoted(&sql[start..i])
+                }
+                None => {
+                    i += 1;
+                    Token::Punct('$')
+                }
+            },
+            b if is_word_byte(b) => {
+                while i < bytes.len() && is_word_byte(bytes[i]) {
+                    i += 1;
+                }
+
+                Token::Word(&sql[start..i])
+            }
+            b => {
+                i += 1;
+                Token::Punct(b as char)
+            }
+        };
+
+        if current.tokens.is_empty() {
+            current.line = line;
+        }
+
+        line += count_lines(&bytes[start..i]);
+        current.tokens.push(token);
+    }
+
+    if !current.tokens.is_empty() {
+        statements.push(current);
+    }
+
+    statements
+}
+
+fn is_word_byte(b: u8) -> bool {
+    // Bytes of non-ASCII characters are all `>= 0x80`.
+    b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80
+}
+
+fn count_lines(bytes: &[u8]) -> usize {
+    bytes.iter().filter(|&&b| b == b'\n').count()
+}
+
+fn find(bytes: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    bytes
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|i| from + i)
+}
+
+/// Returns the index after the closing quote, where a doubled quote is an escaped one.
+fn end_of_quoted(bytes: &[u8], start: usize, quote: u8, backslash_escapes: bool) -> usize {
+    let mut i = start + 1;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if backslash_escapes => i += 2,
+            b if b == quote && bytes.get(i + 1) == Some(&quote) => i += 2,
+            b if b == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+
+    bytes.len()
+}
+
+/// The `$tag$` starting a dollar-quoted string at `start`, if any.
+fn dollar_quote_tag(bytes: &[u8], start: usize) -> Option<&[u8]> {
+    let len = bytes[start + 1..]
+        .iter()
+        .position(|&b| !(b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80))?;
+
+    // Otherwise, it's a parameter like `$1`.
+    if bytes[start + 1].is_ascii_digit() || bytes[start + 1 + len] != b'$' {
+        return None;
+    }
+
+    Some(&bytes[start..start + len + 2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locks(sql: &str) -> Vec<(&'static str, String, usize)> {
+        postgres_locks(sql)
+            .into_iter()
+            .map(|lock| (lock.mode.name(), lock.relation, lock.line))
+            .collect()
+    }
+
+    #[test]
+    fn lock_plan() {
+        assert_eq!(
+            locks(
+                "CREATE TABLE posts (id INT, user_id INT REFERENCES users (id));\n\
+                 CREATE INDEX ON posts (user_id);\n\
+                 CREATE INDEX CONCURRENTLY ON public.users (email);\n\
+                 ALTER TABLE users ADD COLUMN bio TEXT, VALIDATE CONSTRAINT foo;\n\
+                 ALTER TABLE ONLY comments ADD CONSTRAINT fk FOREIGN KEY (post_id) REFERENCES posts;\n\
+                 ALTER TABLE comments VALIDATE CONSTRAINT fk;\n\
+                 UPDATE users SET bio = '';\n\
+                 LOCK TABLE users, \"Orders\" IN SHARE ROW EXCLUSIVE MODE;\n\
+                 DROP TABLE IF EXISTS old_users, old_posts CASCADE;"
+            ),
+            [
+                ("SHARE ROW EXCLUSIVE", "users".into(), 1),
+                ("SHARE UPDATE EXCLUSIVE", "public.users".into(), 3),
+                ("ACCESS EXCLUSIVE", "users".into(), 4),
+                ("SHARE ROW EXCLUSIVE", "comments".into(), 5),
+                ("SHARE UPDATE EXCLUSIVE", "comments".into(), 6),
+                ("ROW EXCLUSIVE", "users".into(), 7),
+                ("SHARE ROW EXCLUSIVE", "users".into(), 8),
+                ("SHARE ROW EXCLUSIVE", "Orders".into(), 8),
+                ("ACCESS EXCLUSIVE", "old_users".into(), 9),
+                ("ACCESS EXCLUSIVE", "old_posts".into(), 9),
+            ]
+        );
+    }
+
+    fn rules(dialect: Dialect, sql: &str) -> Vec<(LintRule, usize)> {
+        lint_sql(dialect, sql, false)
+            .into_iter()
+            .map(|lint| (lint.rule, lint.line))
+            .collect()
+    }
+
+    #[test]
+    fn create_index() {
+        use LintRule::*;
+
+        assert_eq!(
+            rules(
+                Dialect::Postgres,
+                "CREATE INDEX foo_bar ON foo (bar);\n\
+                 create unique index on only public.foo (bar);"
+            ),
+            [
+                (CreateIndexNotConcurrently, 1),
+                (CreateIndexNotConcurrently, 2)
+            ]
+        );
+
+        // Indexes on new tables don't block anything.
+        assert_eq!(
+            rules(
+                Dialect::Postgres,
+                "CREATE TABLE IF NOT EXISTS \"Foo\" (bar INT);\n\
+                 CREATE INDEX ON \"Foo\" (bar);"
+            ),
+            []
+        );
+
+        // Other databases don't support `CONCURRENTLY`.
+        assert_eq!(
+            rules(Dialect::MySql, "CREATE INDEX foo_bar ON foo (bar);"),
+            []
+        );
+    }
+
+    #[test]
+    fn concurrently() {
+        use LintRule::*;
+
+        let sql = "CREATE INDEX CONCURRENTLY foo_bar ON foo (bar);";
+
+        assert_eq!(
+            rules(Dialect::Postgres, sql),
+            [(ConcurrentlyInTransaction, 1)]
+        );
+        assert_eq!(lint_sql(Dialect::Postgres, sql, true), []);
+
+        // Postgres runs multiple statements in one query in a transaction.
+        assert_eq!(
+            lint_sql(
+                Dialect::Postgres,
+                "DROP INDEX CONCURRENTLY foo_baz;\nCREATE INDEX CONCURRENTLY foo_bar ON foo (bar);",
+                true
+            ),
+            [
+                Lint {
+                    rule: ConcurrentlyInTransaction,
+                    line: 1
+                },
+                Lint {
+                    rule: ConcurrentlyInTransaction,
+                    line: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn alter_table() {
+        use LintRule::*;
+
+        let sql = "\
+            ALTER TABLE foo ADD COLUMN bar INT NOT NULL, ADD baz INT NOT NULL DEFAULT 0;\n\
+            ALTER TABLE foo ADD CONSTRAINT foo_bar CHECK (bar IS NOT NULL);\n\
+            ALTER TABLE foo DROP COLUMN bar, DROP CONSTRAINT foo_bar;\n\
+            ALTER TABLE foo ALTER COLUMN bar TYPE BIGINT, ALTER bar SET NOT NULL;\n\
+            ALTER TABLE foo ALTER COLUMN bar SET DATA TYPE TEXT;\n\
+        ";
+
+        assert_eq!(
+            rules(Dialect::Postgres, sql),
+            [
+                (AddColumnNotNull, 1),
+                (DropColumn, 3),
+                (AlterColumnType, 4),
+                (AlterColumnType, 5)
+            ]
+        );
+
+        assert_eq!(
+            rules(
+                Dialect::MySql,
+                "ALTER TABLE `foo` MODIFY bar BIGINT, DROP INDEX foo_bar, DROP baz;"
+            ),
+            [(AlterColumnType, 1), (DropColumn, 1)]
+        );
+
+        // Columns of new tables can't be in use.
+        assert_eq!(
+            rules(
+                Dialect::Sqlite,
+                "CREATE TABLE foo (id INTEGER PRIMARY KEY);\n\
+                 ALTER TABLE foo ADD COLUMN bar INT NOT NULL;"
+            ),
+            []
+        );
+    }
+
+    #[test]
+    fn comments_and_strings() {
+        let sql = "\
+            -- CREATE INDEX foo_bar ON foo (bar);\n\
+            /* DROP COLUMN; */\n\
+            INSERT INTO foo (bar) VALUES ('; CREATE INDEX foo_bar ON foo (bar);');\n\
+            CREATE FUNCTION f() RETURNS void AS $body$ ALTER TABLE foo DROP bar; $body$ LANGUAGE sql;\n\
+            SELECT E'\\'; CREATE INDEX foo_bar ON foo (bar); \\'', $1;\n\
+            CREATE INDEX foo_bar ON foo (bar);\n\
+        ";
+
+        assert_eq!(
+            rules(Dialect::Postgres, sql),
+            [(LintRule::CreateIndexNotConcurrently, 6)]
+        );
+
+        assert_eq!(
+            rules(
+                Dialect::MySql,
+                "# ALTER TABLE foo DROP bar;\nSELECT '\\'; ALTER TABLE foo DROP bar;';"
+            ),
+            []
+        );
+    }
+}
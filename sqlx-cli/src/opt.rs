@@ -76,6 +76,31 @@ pub enum Command {
     #[clap(alias = "mig")]
     Migrate(MigrateOpt),
 
+    /// Generate Rust types for the tables, views and user-defined types of the database.
+    ///
+    /// Writes a module for each table and view, containing a struct that can be used with
+    /// `query_as!()`, and a `types` module with the enums and composite types (Postgres only).
+    /// SQL types are mapped like in the query macros, including the `[macros]` overrides and
+    /// preferred crates in `sqlx.toml`.
+    Generate {
+        #[clap(flatten)]
+        config: ConfigOpt,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+
+        /// The directory to write the modules to.
+        #[clap(long, short, default_value = "src/models")]
+        output: PathBuf,
+
+        /// Instead of writing the modules, check that they match the database schema.
+        ///
+        /// Exits with a nonzero exit status if they do not. Intended for use in Continuous
+        /// Integration.
+        #[clap(long)]
+        check: bool,
+    },
+
     #[cfg(feature = "completions")]
     /// Generate shell completions for the specified shell
     Completions { shell: Shell },
@@ -145,6 +170,29 @@ pub enum DatabaseCommand {
         #[clap(flatten)]
         connect_opts: ConnectOpts,
     },
+
+    /// Writes a canonical description of the database schema to a file.
+    ///
+    /// The description is built from the database catalogs and sorted by name, so it can be
+    /// checked into version control to review schema changes.
+    DumpSchema {
+        #[clap(flatten)]
+        config: ConfigOpt,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+
+        /// The file to write the schema to.
+        #[clap(long, short, default_value = "schema.txt")]
+        output: PathBuf,
+
+        /// Instead of writing the file, check that it matches the database schema.
+        ///
+        /// Exits with a nonzero exit status if it does not. Intended for use in Continuous
+        /// Integration.
+        #[clap(long)]
+        check: bool,
+    },
 }
 
 /// Group of commands for creating and running migrations.
@@ -235,6 +283,18 @@ pub enum MigrateCommand {
         #[clap(long)]
         dry_run: bool,
 
+        /// With `--dry-run`, print the SQL of each migration to be run, in order, wrapped in the
+        /// transactions it would run in.
+        #[clap(long, requires = "dry_run")]
+        sql: bool,
+
+        /// With `--sql`, preview the tables each migration locks, and in what mode.
+        ///
+        /// PostgreSQL only. This is inferred from the kind of each statement, without asking the
+        /// database.
+        #[clap(long, requires = "sql")]
+        lock_plan: bool,
+
         #[clap(flatten)]
         ignore_missing: IgnoreMissing,
 
@@ -284,6 +344,175 @@ pub enum MigrateCommand {
         connect_opts: ConnectOpts,
     },
 
+    /// Check pending migrations for patterns that can lock busy tables or break
+    /// running instances of the application.
+    ///
+    /// --------------------------------
+    ///
+    /// Migrations are checked in the dialect of the database, and include:
+    ///
+    /// * `create-index-not-concurrently` (Postgres): `CREATE INDEX` without `CONCURRENTLY`.
+    ///
+    /// * `concurrently-in-transaction` (Postgres): `CONCURRENTLY` in a migration without
+    ///   `-- no-transaction`, or alongside other statements.
+    ///
+    /// * `add-column-not-null`: adding a `NOT NULL` column without a `DEFAULT`.
+    ///
+    /// * `alter-column-type`: changing the type of a column.
+    ///
+    /// * `drop-column`: dropping a column.
+    ///
+    /// Statements on tables created in the same migration are not checked.
+    ///
+    /// Rules may be allowed for all migrations or specific ones in `[migrate.lint]` in `sqlx.toml`.
+    ///
+    /// Exits with 1 if any issues are found.
+    Lint {
+        #[clap(flatten)]
+        source: MigrationSourceOpt,
+
+        #[clap(flatten)]
+        config: ConfigOpt,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+
+        /// Check all migrations, not just those not yet applied.
+        ///
+        /// The database is not connected to; its URL only determines the dialect.
+        #[clap(long)]
+        all: bool,
+    },
+
+    /// Squash the migrations up to a version into a single baseline migration.
+    ///
+    /// --------------------------------
+    ///
+    /// The baseline takes the version of the last migration it replaces, and contains the SQL
+    /// of each of them in order, so it reproduces the schema on a new database.
+    /// The files of the squashed migrations are removed once the baseline is written.
+    ///
+    /// The baseline is a simple migration, so it cannot be reverted: the `.down.sql` files
+    /// of squashed reversible migrations are removed as well.
+    ///
+    /// On a database that already applied the squashed migrations, running the baseline
+    /// records it in the migrations table instead, and the squashed versions are treated
+    /// as applied rather than missing.
+    ///
+    /// A database that applied only some of the squashed migrations must apply the rest
+    /// before the baseline can be run on it.
+    ///
+    /// Migrations with `-- no-transaction` cannot be squashed.
+    Squash {
+        #[clap(flatten)]
+        source: MigrationSourceOpt,
+
+        #[clap(flatten)]
+        config: ConfigOpt,
+
+        /// The version of the last migration to squash.
+        #[clap(long)]
+        version: i64,
+
+        /// The description of the baseline migration.
+        #[clap(long, default_value = "baseline")]
+        description: String,
+    },
+
+    /// Update the recorded checksums of applied migrations to match their files.
+    ///
+    /// --------------------------------
+    ///
+    /// Use this when the file of an applied migration was changed without changing what it
+    /// does, e.g. reformatted, and `sqlx migrate run` fails because of the different checksum.
+    /// The migrations are not run again.
+    Repair {
+        #[clap(flatten)]
+        source: MigrationSourceOpt,
+
+        #[clap(flatten)]
+        config: ConfigOpt,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+
+        /// Automatic confirmation. Without this option, you will be prompted before the
+        /// checksums are updated.
+        #[clap(short)]
+        yes: bool,
+    },
+
+    /// Mark the migrations up to a version as applied, without running them.
+    ///
+    /// --------------------------------
+    ///
+    /// Use this to start using migrations with an existing database, whose schema the
+    /// migrations up to the version already describe.
+    Baseline {
+        #[clap(flatten)]
+        source: MigrationSourceOpt,
+
+        #[clap(flatten)]
+        config: ConfigOpt,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+
+        /// The version of the last migration to mark as applied.
+        #[clap(long)]
+        version: i64,
+    },
+
+    /// Resolve a migration that failed partway through and was fixed by hand.
+    ///
+    /// --------------------------------
+    ///
+    /// By default, the migration is marked as applied, for when the rest of it was applied by
+    /// hand. With `--rolled-back`, it is marked as not applied, for when what it did apply was
+    /// undone by hand, so `sqlx migrate run` runs it again.
+    MarkDirtyResolved {
+        #[clap(flatten)]
+        config: ConfigOpt,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+
+        /// Mark the migration as not applied instead.
+        #[clap(long)]
+        rolled_back: bool,
+    },
+
+    /// Check the database schema against the schema its migrations produce.
+    ///
+    /// --------------------------------
+    ///
+    /// All migrations are applied to a scratch database, which is then compared with the
+    /// database at DATABASE_URL to find changes that were made outside of migrations, such as
+    /// hotfixes applied by hand: missing or extra tables, columns, constraints, indexes, views,
+    /// functions and enums, and columns with a different type, default or nullability.
+    ///
+    /// Unless given with `--scratch-database-url`, the scratch database is created next to the
+    /// database at DATABASE_URL, named after it with a `_sqlx_verify_<pid>` suffix (for SQLite,
+    /// in the temporary directory). It must not exist yet, and is dropped afterwards.
+    ///
+    /// Exits with 1 if any differences are found.
+    Verify {
+        #[clap(flatten)]
+        source: MigrationSourceOpt,
+
+        #[clap(flatten)]
+        config: ConfigOpt,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+
+        /// The URL of the scratch database to apply the migrations to.
+        ///
+        /// The database must not exist yet.
+        #[clap(long)]
+        scratch_database_url: Option<String>,
+    },
+
     /// Generate a `build.rs` to trigger recompilation when a new migration is added.
     ///
     /// Must be run in a Cargo project root.
@@ -351,11 +580,23 @@ impl MigrationSourceOpt {
     }
 
     pub async fn resolve(&self, config: &Config) -> Result<Migrator, MigrateError> {
-        Migrator::new(ResolveWith(
+        let mut migrator = Migrator::new(ResolveWith(
             self.resolve_path(config),
             config.migrate.to_resolve_config(),
         ))
-        .await
+        .await?;
+
+        migrator.set_strict_order(config.migrate.strict_order);
+
+        if let Some(vars) = &config.migrate.vars {
+            migrator.set_templating(true);
+
+            for (name, value) in vars {
+                migrator.set_var(name.to_string(), value.to_string());
+            }
+        }
+
+        Ok(migrator)
     }
 }
 
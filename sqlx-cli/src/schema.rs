@@ -0,0 +1,1008 @@
+//! Introspection of a live database into a canonical, diffable description of its schema.
+//!
+//! The description is built by querying the database catalogs directly rather than shelling out
+//! to `pg_dump`, `mysqldump` or `sqlite3`, so the output does not depend on which client tools
+//! (and which versions of them) are installed. Everything is sorted by name so that the same
+//! schema always renders to the same text.
+
+// Catalog rows are decoded into tuples since `sqlx-cli` does not enable `derive`.
+#![allow(clippy::type_complexity)]
+
+use anyhow::bail;
+use sqlx::AnyConnection;
+use sqlx::AssertSqlSafe;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+/// The header written at the top of a rendered schema.
+const HEADER: &str =
+    "-- This file is generated by `sqlx database dump-schema`. Do not edit it by hand.\n";
+
+/// A structural description of the objects in a database.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Schema {
+    pub tables: BTreeMap<String, Table>,
+    pub views: BTreeMap<String, View>,
+    /// Functions and procedures, keyed by name (including argument types where the database
+    /// supports overloading).
+    pub functions: BTreeMap<String, String>,
+    /// Enum types and their variants, in declaration order.
+    pub enums: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Table {
+    /// The columns of the table, in their physical order.
+    pub columns: Vec<Column>,
+    /// Constraint definitions, keyed by name, or by the definition itself for databases that do
+    /// not name constraints.
+    pub constraints: BTreeMap<String, String>,
+    /// Index definitions, keyed by name.
+    pub indexes: BTreeMap<String, String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Column {
+    pub name: String,
+    pub data_type: String,
+    pub not_null: bool,
+    pub default: Option<String>,
+    /// Anything else the database reports about the column, e.g. `GENERATED ALWAYS AS IDENTITY`
+    /// or `auto_increment`.
+    pub extra: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct View {
+    pub materialized: bool,
+    pub definition: String,
+}
+
+impl Schema {
+    /// Introspect the database `conn` is connected to.
+    ///
+    /// The migrations table, `migrations_table`, is left out since it is managed by SQLx.
+    pub async fn introspect(
+        conn: &mut AnyConnection,
+        migrations_table: &str,
+    ) -> anyhow::Result<Self> {
+        let mut schema = match conn.backend_name() {
+            "PostgreSQL" => introspect_postgres(conn).await?,
+            "MySQL" => introspect_mysql(conn).await?,
+            "SQLite" => introspect_sqlite(conn).await?,
+            backend => bail!("introspecting a {backend} database is not supported"),
+        };
+
+        schema
+            .tables
+            .retain(|name, _| !is_migrations_table(name, migrations_table));
+
+        Ok(schema)
+    }
+
+    /// List the differences between this, the expected schema, and `actual`.
+    ///
+    /// The order of columns is not compared, since databases generally cannot reorder columns
+    /// after the fact.
+    pub fn diff(&self, actual: &Schema) -> Vec<String> {
+        let mut diffs = Vec::new();
+
+        diff_objects(
+            &mut diffs,
+            "enum",
+            &self.enums,
+            &actual.enums,
+            |name, _, _| vec![format!("enum {name} has different values")],
+        );
+
+        diff_objects(
+            &mut diffs,
+            "table",
+            &self.tables,
+            &actual.tables,
+            |name, expected, actual| expected.diff(name, actual),
+        );
+
+        diff_objects(
+            &mut diffs,
+            "view",
+            &self.views,
+            &actual.views,
+            |name, _, _| vec![format!("view {name} has a different definition")],
+        );
+
+        diff_objects(
+            &mut diffs,
+            "function",
+            &self.functions,
+            &actual.functions,
+            |name, _, _| vec![format!("function {name} has a different definition")],
+        );
+
+        diffs
+    }
+}
+
+impl Table {
+    fn diff(&self, table_name: &str, actual: &Table) -> Vec<String> {
+        let mut diffs = Vec::new();
+
+        let columns = |table: &Table| -> BTreeMap<String, String> {
+            table
+                .columns
+                .iter()
+                .map(|column| (format!("{table_name}.{}", column.name), column.to_string()))
+                .collect()
+        };
+
+        diff_objects(
+            &mut diffs,
+            "column",
+            &columns(self),
+            &columns(actual),
+            |name, expected, actual| {
+                vec![format!(
+                    "column {name}: expected `{expected}`, found `{actual}`"
+                )]
+            },
+        );
+
+        let qualify = |objects: &BTreeMap<String, String>| -> BTreeMap<String, String> {
+            objects
+                .iter()
+                .map(|(name, definition)| (format!("{name} on {table_name}"), definition.clone()))
+                .collect()
+        };
+
+        for (kind, expected, actual) in [
+            ("constraint", &self.constraints, &actual.constraints),
+            ("index", &self.indexes, &actual.indexes),
+        ] {
+            diff_objects(
+                &mut diffs,
+                kind,
+                &qualify(expected),
+                &qualify(actual),
+                |name, expected, actual| {
+                    vec![format!(
+                        "{kind} {name}: expected `{expected}`, found `{actual}`"
+                    )]
+                },
+            );
+        }
+
+        diffs
+    }
+}
+
+/// Report objects missing from or extra in `actual`, and describe those that differ with
+/// `changed`.
+fn diff_objects<T: PartialEq>(
+    diffs: &mut Vec<String>,
+    kind: &str,
+    expected: &BTreeMap<String, T>,
+    actual: &BTreeMap<String, T>,
+    mut changed: impl FnMut(&str, &T, &T) -> Vec<String>,
+) {
+    for (name, expected_object) in expected {
+        match actual.get(name) {
+            None => diffs.push(format!("missing {kind} {name}")),
+            Some(actual_object) if actual_object != expected_object => {
+                diffs.extend(changed(name, expected_object, actual_object))
+            }
+            Some(_) => (),
+        }
+    }
+
+    for name in actual.keys() {
+        if !expected.contains_key(name) {
+            diffs.push(format!("extra {kind} {name}"));
+        }
+    }
+}
+
+/// `migrations_table` may or may not be schema-qualified.
+pub(crate) fn is_migrations_table(name: &str, migrations_table: &str) -> bool {
+    if migrations_table.contains('.') {
+        return name == migrations_table;
+    }
+
+    name.rsplit('.').next() == Some(migrations_table)
+}
+
+impl Display for Schema {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(HEADER)?;
+
+        for (name, variants) in &self.enums {
+            writeln!(f, "\nENUM {name}")?;
+
+            for variant in variants {
+                writeln!(f, "  VALUE {variant}")?;
+            }
+        }
+
+        for (name, table) in &self.tables {
+            writeln!(f, "\nTABLE {name}")?;
+
+            for column in &table.columns {
+                writeln!(f, "  COLUMN {column}")?;
+            }
+
+            for (name, definition) in &table.constraints {
+                // Unnamed constraints are keyed by their definition.
+                if name == definition {
+                    writeln!(f, "  CONSTRAINT {definition}")?;
+                } else {
+                    writeln!(f, "  CONSTRAINT {name} {definition}")?;
+                }
+            }
+
+            for (name, definition) in &table.indexes {
+                writeln!(f, "  INDEX {name} {definition}")?;
+            }
+        }
+
+        for (name, view) in &self.views {
+            let kind = if view.materialized {
+                "MATERIALIZED VIEW"
+            } else {
+                "VIEW"
+            };
+
+            writeln!(f, "\n{kind} {name}")?;
+            write_definition(f, &view.definition)?;
+        }
+
+        for (name, definition) in &self.functions {
+            writeln!(f, "\nFUNCTION {name}")?;
+            write_definition(f, definition)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Column {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.data_type)?;
+
+        if self.not_null {
+            f.write_str(" NOT NULL")?;
+        }
+
+        if let Some(default) = &self.default {
+            write!(f, " DEFAULT {default}")?;
+        }
+
+        if let Some(extra) = &self.extra {
+            write!(f, " {extra}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Write a (possibly multi-line) definition indented under its heading.
+fn write_definition(f: &mut Formatter<'_>, definition: &str) -> fmt::Result {
+    for line in definition.trim().lines() {
+        let line = line.trim_end();
+
+        if line.is_empty() {
+            writeln!(f)?;
+        } else {
+            writeln!(f, "    {line}")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Excludes the system schemas and objects owned by extensions.
+const PG_USER_OBJECTS: &str = "n.nspname NOT IN ('pg_catalog', 'information_schema') \
+    AND n.nspname NOT LIKE 'pg\\_%' \
+    AND NOT EXISTS ( \
+        SELECT 1 FROM pg_catalog.pg_depend dep WHERE dep.objid = {oid} AND dep.deptype = 'e' \
+    )";
+
+pub(crate) fn pg_user_objects(oid: &str) -> String {
+    PG_USER_OBJECTS.replace("{oid}", oid)
+}
+
+async fn introspect_postgres(conn: &mut AnyConnection) -> anyhow::Result<Schema> {
+    let mut schema = Schema::default();
+
+    // language=PostgreSQL
+    let tables: Vec<(String,)> = sqlx::query_as(AssertSqlSafe(format!(
+        "SELECT quote_ident(n.nspname) || '.' || quote_ident(c.relname) \
+         FROM pg_catalog.pg_class c \
+         JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace \
+         WHERE c.relkind IN ('r', 'p') AND {}",
+        pg_user_objects("c.oid")
+    )))
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (name,) in tables {
+        schema.tables.insert(name, Table::default());
+    }
+
+    // language=PostgreSQL
+    let columns: Vec<(String, String, String, bool, Option<String>, String, String)> =
+        sqlx::query_as(AssertSqlSafe(format!(
+            "SELECT quote_ident(n.nspname) || '.' || quote_ident(c.relname), \
+                 quote_ident(a.attname), \
+                 format_type(a.atttypid, a.atttypmod), \
+                 a.attnotnull, \
+                 pg_get_expr(d.adbin, d.adrelid), \
+                 a.attidentity::text, \
+                 a.attgenerated::text \
+             FROM pg_catalog.pg_attribute a \
+             JOIN pg_catalog.pg_class c ON c.oid = a.attrelid \
+             JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace \
+             LEFT JOIN pg_catalog.pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum \
+             WHERE a.attnum > 0 AND NOT a.attisdropped AND c.relkind IN ('r', 'p') AND {} \
+             ORDER BY 1, a.attnum",
+            pg_user_objects("c.oid")
+        )))
+        .fetch_all(&mut *conn)
+        .await?;
+
+    for (table, name, data_type, not_null, default, identity, generated) in columns {
+        let (default, extra) = match (identity.as_str(), generated.as_str()) {
+            ("a", _) => (None, Some("GENERATED ALWAYS AS IDENTITY".to_owned())),
+            ("d", _) => (None, Some("GENERATED BY DEFAULT AS IDENTITY".to_owned())),
+            (_, "s") => (
+                None,
+                default.map(|expr| format!("GENERATED ALWAYS AS ({expr}) STORED")),
+            ),
+            _ => (default, None),
+        };
+
+        if let Some(table) = schema.tables.get_mut(&table) {
+            table.columns.push(Column {
+                name,
+                data_type,
+                not_null,
+                default,
+                extra,
+            });
+        }
+    }
+
+    // `NOT NULL` constraints are listed in `pg_constraint` from Postgres 18 on, but are already
+    // covered by the columns.
+    // language=PostgreSQL
+    let constraints: Vec<(String, String, String)> = sqlx::query_as(AssertSqlSafe(format!(
+        "SELECT quote_ident(n.nspname) || '.' || quote_ident(c.relname), \
+             quote_ident(con.conname), \
+             pg_get_constraintdef(con.oid, true) \
+         FROM pg_catalog.pg_constraint con \
+         JOIN pg_catalog.pg_class c ON c.oid = con.conrelid \
+         JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace \
+         WHERE con.contype <> 'n' AND c.relkind IN ('r', 'p') AND {}",
+        pg_user_objects("c.oid")
+    )))
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (table, name, definition) in constraints {
+        if let Some(table) = schema.tables.get_mut(&table) {
+            table.constraints.insert(name, definition);
+        }
+    }
+
+    // language=PostgreSQL
+    let indexes: Vec<(String, String, String)> = sqlx::query_as(AssertSqlSafe(format!(
+        "SELECT quote_ident(n.nspname) || '.' || quote_ident(c.relname), \
+             quote_ident(i.relname), \
+             pg_get_indexdef(i.oid) \
+         FROM pg_catalog.pg_index x \
+         JOIN pg_catalog.pg_class i ON i.oid = x.indexrelid \
+         JOIN pg_catalog.pg_class c ON c.oid = x.indrelid \
+         JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace \
+         WHERE c.relkind IN ('r', 'p') AND {}",
+        pg_user_objects("c.oid")
+    )))
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (table, name, definition) in indexes {
+        if let Some(table) = schema.tables.get_mut(&table) {
+            table.indexes.insert(name, definition);
+        }
+    }
+
+    // language=PostgreSQL
+    let views: Vec<(String, bool, String)> = sqlx::query_as(AssertSqlSafe(format!(
+        "SELECT quote_ident(n.nspname) || '.' || quote_ident(c.relname), \
+             c.relkind = 'm', \
+             pg_get_viewdef(c.oid, true) \
+         FROM pg_catalog.pg_class c \
+         JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace \
+         WHERE c.relkind IN ('v', 'm') AND {}",
+        pg_user_objects("c.oid")
+    )))
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (name, materialized, definition) in views {
+        schema.views.insert(
+            name,
+            View {
+                materialized,
+                definition,
+            },
+        );
+    }
+
+    // language=PostgreSQL
+    let functions: Vec<(String, String)> = sqlx::query_as(AssertSqlSafe(format!(
+        "SELECT quote_ident(n.nspname) || '.' || quote_ident(p.proname) \
+                 || '(' || pg_get_function_identity_arguments(p.oid) || ')', \
+             pg_get_functiondef(p.oid) \
+         FROM pg_catalog.pg_proc p \
+         JOIN pg_catalog.pg_namespace n ON n.oid = p.pronamespace \
+         WHERE p.prokind IN ('f', 'p') AND {}",
+        pg_user_objects("p.oid")
+    )))
+    .fetch_all(&mut *conn)
+    .await?;
+
+    schema.functions.extend(functions);
+
+    // language=PostgreSQL
+    let enums: Vec<(String, String)> = sqlx::query_as(AssertSqlSafe(format!(
+        "SELECT quote_ident(n.nspname) || '.' || quote_ident(t.typname), \
+             quote_literal(e.enumlabel) \
+         FROM pg_catalog.pg_enum e \
+         JOIN pg_catalog.pg_type t ON t.oid = e.enumtypid \
+         JOIN pg_catalog.pg_namespace n ON n.oid = t.typnamespace \
+         WHERE {} \
+         ORDER BY 1, e.enumsortorder",
+        pg_user_objects("t.oid")
+    )))
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (name, variant) in enums {
+        schema.enums.entry(name).or_default().push(variant);
+    }
+
+    Ok(schema)
+}
+
+async fn introspect_mysql(conn: &mut AnyConnection) -> anyhow::Result<Schema> {
+    let mut schema = Schema::default();
+
+    // Columns of `information_schema` are cast to `CHAR` as some servers report them as binary.
+
+    // language=MySQL
+    let tables: Vec<(String,)> = sqlx::query_as(
+        "SELECT CAST(TABLE_NAME AS CHAR) \
+         FROM information_schema.TABLES \
+         WHERE TABLE_SCHEMA = DATABASE() AND TABLE_TYPE = 'BASE TABLE'",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (name,) in tables {
+        schema.tables.insert(name, Table::default());
+    }
+
+    // language=MySQL
+    let columns: Vec<(String, String, String, String, Option<String>, String)> = sqlx::query_as(
+        "SELECT CAST(TABLE_NAME AS CHAR), \
+                 CAST(COLUMN_NAME AS CHAR), \
+                 CAST(COLUMN_TYPE AS CHAR), \
+                 CAST(IS_NULLABLE AS CHAR), \
+                 CAST(COLUMN_DEFAULT AS CHAR), \
+                 CAST(EXTRA AS CHAR) \
+             FROM information_schema.COLUMNS \
+             WHERE TABLE_SCHEMA = DATABASE() \
+             ORDER BY TABLE_NAME, ORDINAL_POSITION",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (table, name, data_type, nullable, default, extra) in columns {
+        // Also lists the columns of views.
+        if let Some(table) = schema.tables.get_mut(&table) {
+            table.columns.push(Column {
+                name,
+                data_type,
+                not_null: nullable == "NO",
+                default,
+                extra: Some(extra).filter(|extra| !extra.is_empty()),
+            });
+        }
+    }
+
+    // language=MySQL
+    let constraints: Vec<(
+        String,
+        String,
+        String,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+    )> = sqlx::query_as(
+        "SELECT CAST(tc.TABLE_NAME AS CHAR), \
+             CAST(tc.CONSTRAINT_NAME AS CHAR), \
+             CAST(tc.CONSTRAINT_TYPE AS CHAR), \
+             CAST(k.COLUMN_NAME AS CHAR), \
+             CAST(k.REFERENCED_TABLE_NAME AS CHAR), \
+             CAST(k.REFERENCED_COLUMN_NAME AS CHAR), \
+             CAST(r.UPDATE_RULE AS CHAR), \
+             CAST(r.DELETE_RULE AS CHAR) \
+         FROM information_schema.TABLE_CONSTRAINTS tc \
+         LEFT JOIN information_schema.KEY_COLUMN_USAGE k \
+             ON k.CONSTRAINT_SCHEMA = tc.CONSTRAINT_SCHEMA \
+             AND k.TABLE_NAME = tc.TABLE_NAME \
+             AND k.CONSTRAINT_NAME = tc.CONSTRAINT_NAME \
+         LEFT JOIN information_schema.REFERENTIAL_CONSTRAINTS r \
+             ON r.CONSTRAINT_SCHEMA = tc.CONSTRAINT_SCHEMA \
+             AND r.TABLE_NAME = tc.TABLE_NAME \
+             AND r.CONSTRAINT_NAME = tc.CONSTRAINT_NAME \
+         WHERE tc.CONSTRAINT_SCHEMA = DATABASE() \
+         ORDER BY tc.TABLE_NAME, tc.CONSTRAINT_NAME, k.ORDINAL_POSITION",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    // (type, columns, referenced table, referenced columns, on update, on delete)
+    type MySqlConstraint = (
+        String,
+        Vec<String>,
+        Option<String>,
+        Vec<String>,
+        Option<String>,
+        Option<String>,
+    );
+
+    let mut grouped: BTreeMap<(String, String), MySqlConstraint> = BTreeMap::new();
+
+    for (table, name, kind, column, ref_table, ref_column, on_update, on_delete) in constraints {
+        let entry = grouped.entry((table, name)).or_insert_with(|| {
+            (
+                kind,
+                Vec::new(),
+                ref_table,
+                Vec::new(),
+                on_update,
+                on_delete,
+            )
+        });
+
+        entry.1.extend(column);
+        entry.3.extend(ref_column);
+    }
+
+    for ((table, name), (kind, columns, ref_table, ref_columns, on_update, on_delete)) in grouped {
+        let Some(table) = schema.tables.get_mut(&table) else {
+            continue;
+        };
+
+        let mut definition = kind;
+
+        if !columns.is_empty() {
+            write_list(&mut definition, &columns);
+        }
+
+        if let Some(ref_table) = ref_table {
+            definition.push_str(" REFERENCES ");
+            definition.push_str(&ref_table);
+            write_list(&mut definition, &ref_columns);
+        }
+
+        if let Some(on_update) = on_update {
+            definition.push_str(" ON UPDATE ");
+            definition.push_str(&on_update);
+        }
+
+        if let Some(on_delete) = on_delete {
+            definition.push_str(" ON DELETE ");
+            definition.push_str(&on_delete);
+        }
+
+        table.constraints.insert(name, definition);
+    }
+
+    // The primary key is already listed with the constraints.
+    // language=MySQL
+    let indexes: Vec<(String, String, i64, String, Option<String>, Option<i64>)> = sqlx::query_as(
+        "SELECT CAST(TABLE_NAME AS CHAR), \
+             CAST(INDEX_NAME AS CHAR), \
+             CAST(NON_UNIQUE AS SIGNED), \
+             CAST(INDEX_TYPE AS CHAR), \
+             CAST(COLUMN_NAME AS CHAR), \
+             CAST(SUB_PART AS SIGNED) \
+         FROM information_schema.STATISTICS \
+         WHERE TABLE_SCHEMA = DATABASE() AND INDEX_NAME <> 'PRIMARY' \
+         ORDER BY TABLE_NAME, INDEX_NAME, SEQ_IN_INDEX",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut grouped: BTreeMap<(String, String), (bool, String, Vec<String>)> = BTreeMap::new();
+
+    for (table, name, non_unique, index_type, column, sub_part) in indexes {
+        let entry = grouped
+            .entry((table, name))
+            .or_insert_with(|| (non_unique == 0, index_type, Vec::new()));
+
+        // Functional indexes have no column name.
+        let column = column.unwrap_or_else(|| "(expression)".to_owned());
+
+        entry.2.push(match sub_part {
+            Some(len) => format!("{column}({len})"),
+            None => column,
+        });
+    }
+
+    for ((table, name), (unique, index_type, columns)) in grouped {
+        let Some(table) = schema.tables.get_mut(&table) else {
+            continue;
+        };
+
+        let mut definition = if unique { "UNIQUE " } else { "" }.to_owned();
+        definition.push_str(&index_type);
+        write_list(&mut definition, &columns);
+
+        table.indexes.insert(name, definition);
+    }
+
+    // View definitions qualify every table with the database name, which would make the same
+    // schema render differently in differently named databases.
+    // language=MySQL
+    let database: String = sqlx::query_scalar("SELECT CAST(DATABASE() AS CHAR)")
+        .fetch_one(&mut *conn)
+        .await?;
+    let qualifier = format!("`{database}`.");
+
+    // language=MySQL
+    let views: Vec<(String, String)> = sqlx::query_as(
+        "SELECT CAST(TABLE_NAME AS CHAR), CAST(VIEW_DEFINITION AS CHAR) \
+         FROM information_schema.VIEWS \
+         WHERE TABLE_SCHEMA = DATABASE()",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (name, definition) in views {
+        schema.views.insert(
+            name,
+            View {
+                materialized: false,
+                definition: definition.replace(&qualifier, ""),
+            },
+        );
+    }
+
+    // language=MySQL
+    let functions: Vec<(String, String, Option<String>)> = sqlx::query_as(
+        "SELECT CAST(ROUTINE_NAME AS CHAR), \
+             CAST(ROUTINE_TYPE AS CHAR), \
+             CAST(ROUTINE_DEFINITION AS CHAR) \
+         FROM information_schema.ROUTINES \
+         WHERE ROUTINE_SCHEMA = DATABASE()",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (name, kind, definition) in functions {
+        let definition = format!("{kind} {}", definition.unwrap_or_default());
+        schema.functions.insert(name, definition);
+    }
+
+    Ok(schema)
+}
+
+async fn introspect_sqlite(conn: &mut AnyConnection) -> anyhow::Result<Schema> {
+    let mut schema = Schema::default();
+
+    // language=SQLite
+    let tables: Vec<(String,)> = sqlx::query_as(
+        r"SELECT name FROM sqlite_schema
+          WHERE type = 'table' AND name NOT LIKE 'sqlite\_%' ESCAPE '\'",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (name,) in tables {
+        schema.tables.insert(name, Table::default());
+    }
+
+    // language=SQLite
+    let columns: Vec<(String, String, String, i64, Option<String>, i64)> = sqlx::query_as(
+        r#"SELECT m.name, p.name, p.type, p."notnull", p.dflt_value, p.pk
+           FROM sqlite_schema m
+           JOIN pragma_table_info(m.name) p
+           WHERE m.type = 'table'
+           ORDER BY m.name, p.cid"#,
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut primary_keys: BTreeMap<String, Vec<(i64, String)>> = BTreeMap::new();
+
+    for (table_name, name, data_type, not_null, default, pk) in columns {
+        let Some(table) = schema.tables.get_mut(&table_name) else {
+            continue;
+        };
+
+        if pk > 0 {
+            primary_keys
+                .entry(table_name)
+                .or_default()
+                .push((pk, name.clone()));
+        }
+
+        table.columns.push(Column {
+            name,
+            data_type,
+            not_null: not_null != 0,
+            default,
+            extra: None,
+        });
+    }
+
+    // SQLite does not name constraints, so they are keyed by their kind and columns instead.
+    for (table, mut columns) in primary_keys {
+        columns.sort();
+
+        let columns: Vec<String> = columns.into_iter().map(|(_, name)| name).collect();
+        let mut definition = "PRIMARY KEY".to_owned();
+        write_list(&mut definition, &columns);
+
+        if let Some(table) = schema.tables.get_mut(&table) {
+            table.constraints.insert(definition.clone(), definition);
+        }
+    }
+
+    // language=SQLite
+    let foreign_keys: Vec<(String, i64, String, String, Option<String>, String, String)> =
+        sqlx::query_as(
+            r#"SELECT m.name, f.id, f."table", f."from", f."to", f.on_update, f.on_delete
+               FROM sqlite_schema m
+               JOIN pragma_foreign_key_list(m.name) f
+               WHERE m.type = 'table'
+               ORDER BY m.name, f.id, f.seq"#,
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+    // (referenced table, columns, referenced columns, on update, on delete)
+    let mut grouped: BTreeMap<(String, i64), (String, Vec<String>, Vec<String>, String, String)> =
+        BTreeMap::new();
+
+    for (table, id, ref_table, column, ref_column, on_update, on_delete) in foreign_keys {
+        let entry = grouped
+            .entry((table, id))
+            .or_insert_with(|| (ref_table, Vec::new(), Vec::new(), on_update, on_delete));
+
+        entry.1.push(column);
+        entry.2.extend(ref_column);
+    }
+
+    for ((table, _), (ref_table, columns, ref_columns, on_update, on_delete)) in grouped {
+        let Some(table) = schema.tables.get_mut(&table) else {
+            continue;
+        };
+
+        let mut definition = "FOREIGN KEY".to_owned();
+        write_list(&mut definition, &columns);
+        definition.push_str(" REFERENCES ");
+        definition.push_str(&ref_table);
+
+        // Empty if the key references the primary key.
+        if !ref_columns.is_empty() {
+            write_list(&mut definition, &ref_columns);
+        }
+
+        definition.push_str(&format!(" ON UPDATE {on_update} ON DELETE {on_delete}"));
+
+        table.constraints.insert(definition.clone(), definition);
+    }
+
+    // Indexes created for `UNIQUE` constraints have no SQL of their own.
+    // language=SQLite
+    let unique_constraints: Vec<(String, String, Option<String>)> = sqlx::query_as(
+        r#"SELECT m.name, il.name, ii.name
+           FROM sqlite_schema m
+           JOIN pragma_index_list(m.name) il
+           JOIN pragma_index_info(il.name) ii
+           WHERE m.type = 'table' AND il.origin = 'u'
+           ORDER BY m.name, il.name, ii.seqno"#,
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut grouped: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+
+    for (table, index, column) in unique_constraints {
+        grouped
+            .entry((table, index))
+            .or_default()
+            .push(column.unwrap_or_else(|| "(expression)".to_owned()));
+    }
+
+    for ((table, _), columns) in grouped {
+        let Some(table) = schema.tables.get_mut(&table) else {
+            continue;
+        };
+
+        let mut definition = "UNIQUE".to_owned();
+        write_list(&mut definition, &columns);
+
+        table.constraints.insert(definition.clone(), definition);
+    }
+
+    // language=SQLite
+    let indexes: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT tbl_name, name, sql FROM sqlite_schema WHERE type = 'index' AND sql IS NOT NULL",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for (table, name, definition) in indexes {
+        if let Some(table) = schema.tables.get_mut(&table) {
+            table.indexes.insert(name, definition);
+        }
+    }
+
+    // language=SQLite
+    let views: Vec<(String, String)> =
+        sqlx::query_as("SELECT name, sql FROM sqlite_schema WHERE type = 'view'")
+            .fetch_all(&mut *conn)
+            .await?;
+
+    for (name, definition) in views {
+        schema.views.insert(
+            name,
+            View {
+                materialized: false,
+                definition,
+            },
+        );
+    }
+
+    Ok(schema)
+}
+
+/// Append ` (a, b, c)`.
+fn write_list(out: &mut String, items: &[String]) {
+    out.push_str(" (");
+    out.push_str(&items.join(", "));
+    out.push(')');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_schema() {
+        let mut schema = Schema::default();
+
+        schema.tables.insert(
+            "public.users".into(),
+            Table {
+                columns: vec![
+                    Column {
+                        name: "id".into(),
+                        data_type: "bigint".into(),
+                        not_null: true,
+                        default: None,
+                        extra: Some("GENERATED ALWAYS AS IDENTITY".into()),
+                    },
+                    Column {
+                        name: "mood".into(),
+                        data_type: "public.mood".into(),
+                        not_null: false,
+                        default: Some("'happy'::public.mood".into()),
+                        extra: None,
+                    },
+                ],
+                constraints: [("users_pkey".into(), "PRIMARY KEY (id)".into())].into(),
+                indexes: BTreeMap::new(),
+            },
+        );
+        schema
+            .enums
+            .insert("public.mood".into(), vec!["'happy'".into(), "'sad'".into()]);
+        schema.views.insert(
+            "public.happy_users".into(),
+            View {
+                materialized: false,
+                definition: " SELECT id\n   FROM users\n  WHERE mood = 'happy'::mood;".into(),
+            },
+        );
+
+        assert_eq!(
+            schema.to_string(),
+            "-- This file is generated by `sqlx database dump-schema`. Do not edit it by hand.
+
+ENUM public.mood
+  VALUE 'happy'
+  VALUE 'sad'
+
+TABLE public.users
+  COLUMN id bigint NOT NULL GENERATED ALWAYS AS IDENTITY
+  COLUMN mood public.mood DEFAULT 'happy'::public.mood
+  CONSTRAINT users_pkey PRIMARY KEY (id)
+
+VIEW public.happy_users
+    SELECT id
+       FROM users
+      WHERE mood = 'happy'::mood;
+"
+        );
+    }
+
+    #[test]
+    fn diff_schemas() {
+        let column = |name: &str, data_type: &str| Column {
+            name: name.into(),
+            data_type: data_type.into(),
+            not_null: true,
+            default: None,
+            extra: None,
+        };
+
+        let mut expected = Schema::default();
+        expected.tables.insert(
+            "users".into(),
+            Table {
+                columns: vec![column("id", "bigint"), column("email", "text")],
+                constraints: [("users_pkey".into(), "PRIMARY KEY (id)".into())].into(),
+                indexes: [("users_email".into(), "CREATE INDEX users_email".into())].into(),
+            },
+        );
+        expected.tables.insert("posts".into(), Table::default());
+
+        let mut actual = Schema::default();
+        actual.tables.insert(
+            "users".into(),
+            Table {
+                columns: vec![column("email", "varchar"), column("id", "bigint")],
+                constraints: [("users_pkey".into(), "PRIMARY KEY (id)".into())].into(),
+                indexes: BTreeMap::new(),
+            },
+        );
+        actual.tables.insert("hotfix".into(), Table::default());
+
+        assert_eq!(
+            expected.diff(&actual),
+            [
+                "missing table posts",
+                "column users.email: expected `email text NOT NULL`, \
+                 found `email varchar NOT NULL`",
+                "missing index users_email on users",
+                "extra table hotfix",
+            ]
+        );
+
+        assert!(expected.diff(&expected).is_empty());
+    }
+
+    #[test]
+    fn migrations_table() {
+        assert!(is_migrations_table(
+            "public._sqlx_migrations",
+            "_sqlx_migrations"
+        ));
+        assert!(is_migrations_table("_sqlx_migrations", "_sqlx_migrations"));
+        assert!(is_migrations_table(
+            "foo._sqlx_migrations",
+            "foo._sqlx_migrations"
+        ));
+        assert!(!is_migrations_table(
+            "public._sqlx_migrations",
+            "foo._sqlx_migrations"
+        ));
+        assert!(!is_migrations_table("public.users", "_sqlx_migrations"));
+    }
+}
@@ -1,4 +1,4 @@
-use assert_cmd::{assert::Assert, Command};
+use assert_cmd::{assert::Assert, cargo_bin_cmd, Command};
 
 use sqlx::_unstable::config::Config;
 use sqlx::{migrate::Migrate, Connection, SqliteConnection};
@@ -6,6 +6,19 @@ use std::{
     env, fs,
     path::{Path, PathBuf},
 };
+use tempfile::TempDir;
+
+/// Copies the migrations in `tests/{migrations}` to a temporary directory, for tests that change them.
+pub fn copy_migrations(migrations: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+
+    for entry in fs::read_dir(Path::new("tests").join(migrations)).unwrap() {
+        let path = entry.unwrap().path();
+        fs::copy(&path, dir.path().join(path.file_name().unwrap())).unwrap();
+    }
+
+    dir
+}
 
 pub struct TestDatabase {
     file_path: PathBuf,
@@ -88,6 +101,22 @@ impl TestDatabase {
         command.assert()
     }
 
+    /// Runs a command that writes its `--output` from the database, such as `generate`,
+    /// or with `check`, only compares it.
+    pub fn run_output_command(&self, args: &[&str], output: &Path, check: bool) -> Assert {
+        let mut command = cargo_bin_cmd!("sqlx");
+        command
+            .args(args)
+            .args(["--database-url", &self.connection_string(), "--output"])
+            .arg(output);
+
+        if check {
+            command.arg("--check");
+        }
+
+        command.assert()
+    }
+
     pub async fn applied_migrations(&self) -> Vec<i64> {
         let mut conn = SqliteConnection::connect(&self.connection_string())
             .await
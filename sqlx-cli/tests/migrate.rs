@@ -1,6 +1,10 @@
 mod common;
 
-use common::TestDatabase;
+use assert_cmd::cargo_bin_cmd;
+use common::{copy_migrations, TestDatabase};
+use sqlx::{Connection, Executor, SqliteConnection};
+use std::fs;
+use tempfile::TempDir;
 
 #[tokio::test]
 async fn run_reversible_migrations() {
@@ -140,6 +144,77 @@ async fn revert_migrations() {
     }
 }
 
+#[tokio::test]
+async fn squash_migrations() {
+    let all_migrations: Vec<i64> = vec![
+        20230101000000,
+        20230201000000,
+        20230301000000,
+        20230401000000,
+        20230501000000,
+    ];
+
+    let migrations_dir = copy_migrations("migrations_reversible");
+
+    let mut db = TestDatabase::new("squash_existing", "migrations_reversible");
+    db.run_migration(false, None, false).success();
+
+    cargo_bin_cmd!("sqlx")
+        .args([
+            "migrate",
+            "squash",
+            "--version",
+            "20230301000000",
+            "--source",
+        ])
+        .arg(migrations_dir.path())
+        .assert()
+        .success();
+
+    let mut files: Vec<_> = fs::read_dir(migrations_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    files.sort();
+
+    assert_eq!(
+        files,
+        [
+            "20230301000000_baseline.sql",
+            "20230401000000_test4.down.sql",
+            "20230401000000_test4.up.sql",
+            "20230501000000_test5.down.sql",
+            "20230501000000_test5.up.sql",
+        ]
+    );
+
+    // The squashed migrations are already applied, so the baseline is only recorded.
+    db.set_migrations(migrations_dir.path().to_str().unwrap());
+    let info = db.migrate_info().success().get_output().stdout.clone();
+    assert!(String::from_utf8(info)
+        .unwrap()
+        .starts_with("20230301000000/installed (squashed on next run) baseline\n"));
+
+    db.run_migration(false, None, false).success();
+    assert_eq!(db.applied_migrations().await, all_migrations);
+
+    let info = db.migrate_info().success().get_output().stdout.clone();
+    assert!(String::from_utf8(info)
+        .unwrap()
+        .starts_with("20230301000000/installed baseline\n"));
+
+    // Reverting the migrations after the baseline still works.
+    db.run_migration(true, Some(20230301000000), false)
+        .success();
+    assert_eq!(db.applied_migrations().await, all_migrations[..3]);
+
+    // A new database runs the baseline instead.
+    let mut db = TestDatabase::new("squash_new", "migrations_reversible");
+    db.set_migrations(migrations_dir.path().to_str().unwrap());
+    db.run_migration(false, None, false).success();
+    assert_eq!(db.applied_migrations().await, all_migrations[2..]);
+}
+
 #[tokio::test]
 async fn ignored_chars() {
     let mut db = TestDatabase::new("ignored-chars", "ignored-chars/LF");
@@ -165,3 +240,348 @@ async fn ignored_chars() {
     db.migrate_info().success().stdout(expected_info);
     db.run_migration(false, None, false).success().stdout("");
 }
+
+#[tokio::test]
+async fn dump_schema() {
+    let db = TestDatabase::new("dump_schema", "migrations_schema");
+    db.run_migration(false, None, false).success();
+
+    let dir = TempDir::new().unwrap();
+    let output = dir.path().join("schema.txt");
+
+    let dump_schema = |check| db.run_output_command(&["database", "dump-schema"], &output, check);
+
+    dump_schema(false).success();
+
+    assert_eq!(
+        fs::read_to_string(&output).unwrap(),
+        "-- This file is generated by `sqlx database dump-schema`. Do not edit it by hand.
+
+TABLE posts
+  COLUMN id INTEGER
+  COLUMN user_id INTEGER NOT NULL
+  COLUMN title TEXT
+  CONSTRAINT FOREIGN KEY (user_id) REFERENCES users (id) ON UPDATE NO ACTION ON DELETE CASCADE
+  CONSTRAINT PRIMARY KEY (id)
+  INDEX posts_user_id CREATE INDEX posts_user_id ON posts (user_id)
+
+TABLE users
+  COLUMN id INTEGER
+  COLUMN email TEXT NOT NULL
+  COLUMN created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+  CONSTRAINT PRIMARY KEY (id)
+  CONSTRAINT UNIQUE (email)
+
+VIEW post_titles
+    CREATE VIEW post_titles AS SELECT title FROM posts
+"
+    );
+
+    dump_schema(true).success();
+
+    fs::write(&output, "-- out of date\n").unwrap();
+    dump_schema(true).failure();
+}
+
+#[tokio::test]
+async fn generate() {
+    let db = TestDatabase::new("generate", "migrations_schema");
+    db.run_migration(false, None, false).success();
+
+    let dir = TempDir::new().unwrap();
+    let output = dir.path().join("models");
+
+    let generate = |check| db.run_output_command(&["generate"], &output, check);
+
+    generate(false).success();
+
+    assert_eq!(
+        fs::read_to_string(output.join("mod.rs")).unwrap(),
+        "// This file is generated by `sqlx generate`. Do not edit it by hand.
+
+pub mod post_titles;
+pub mod posts;
+pub mod users;
+
+pub use post_titles::PostTitles;
+pub use posts::Posts;
+pub use users::Users;
+"
+    );
+
+    assert_eq!(
+        fs::read_to_string(output.join("users.rs")).unwrap(),
+        "// This file is generated by `sqlx generate`. Do not edit it by hand.
+
+/// The `users` table.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Users {
+    pub id: i64,
+    pub email: String,
+    pub created_at: String,
+}
+"
+    );
+
+    assert_eq!(
+        fs::read_to_string(output.join("posts.rs")).unwrap(),
+        "// This file is generated by `sqlx generate`. Do not edit it by hand.
+
+/// The `posts` table.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Posts {
+    pub id: i64,
+    pub user_id: i64,
+    pub title: Option<String>,
+}
+"
+    );
+
+    assert!(fs::read_to_string(output.join("post_titles.rs"))
+        .unwrap()
+        .contains("pub title: Option<String>,"));
+
+    generate(true).success();
+
+    fs::write(output.join("posts.rs"), "// out of date\n").unwrap();
+    generate(true).failure();
+
+    // Modules for tables that no longer exist are removed.
+    let stale = output.join("comments.rs");
+    fs::write(&stale, fs::read_to_string(output.join("users.rs")).unwrap()).unwrap();
+    generate(false).success();
+    assert!(!stale.exists());
+    generate(true).success();
+}
+
+#[tokio::test]
+async fn verify_schema() {
+    let db = TestDatabase::new("verify_schema", "migrations_schema");
+    db.run_migration(false, None, false).success();
+
+    let verify = || {
+        cargo_bin_cmd!("sqlx")
+            .args([
+                "migrate",
+                "verify",
+                "--database-url",
+                &db.connection_string(),
+                "--source",
+                "tests/migrations_schema",
+            ])
+            .assert()
+    };
+
+    verify().success();
+
+    // a hotfix applied by hand
+    let mut conn = SqliteConnection::connect(&db.connection_string())
+        .await
+        .unwrap();
+    conn.execute("CREATE INDEX users_created_at ON users (created_at)")
+        .await
+        .unwrap();
+    conn.close().await.unwrap();
+
+    let output = verify().failure().get_output().stdout.clone();
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .starts_with("difference: extra index users_created_at on users\n"));
+}
+
+#[tokio::test]
+async fn dry_run_sql() {
+    let db = TestDatabase::new("dry_run_sql", "migrations_reversible");
+    db.run_migration(false, Some(20230301000000), false)
+        .success();
+
+    let run = |args: &[&str]| {
+        cargo_bin_cmd!("sqlx")
+            .args([
+                "migrate",
+                "run",
+                "--database-url",
+                &db.connection_string(),
+                "--source",
+                "tests/migrations_reversible",
+                "--dry-run",
+                "--target-version",
+                "20230401000000",
+            ])
+            .args(args)
+            .assert()
+    };
+
+    run(&["--sql"]).success().stdout(
+        "-- 20230401000000/migrate test4
+BEGIN;
+CREATE TABLE test4(x INTEGER PRIMARY KEY);
+-- records the migration in _sqlx_migrations
+COMMIT;
+
+-- Skipped 20230501000000/migrate test5 (0ns)
+",
+    );
+
+    // Nothing was applied.
+    assert_eq!(
+        db.applied_migrations().await,
+        [20230101000000, 20230201000000, 20230301000000]
+    );
+
+    run(&["--sql", "--lock-plan"]).failure();
+}
+
+#[tokio::test]
+async fn baseline_migrations() {
+    let db = TestDatabase::new("baseline", "migrations_reversible");
+
+    cargo_bin_cmd!("sqlx")
+        .args([
+            "migrate",
+            "baseline",
+            "--version",
+            "20230201000000",
+            "--database-url",
+            &db.connection_string(),
+            "--source",
+            "tests/migrations_reversible",
+        ])
+        .assert()
+        .success();
+
+    assert_eq!(
+        db.applied_migrations().await,
+        [20230101000000, 20230201000000]
+    );
+
+    // The tables of the baselined migrations were never created, so this only succeeds if
+    // their migrations are skipped.
+    db.run_migration(false, None, false).success();
+    assert_eq!(db.applied_migrations().await.len(), 5);
+}
+
+#[tokio::test]
+async fn repair_checksums() {
+    let migrations_dir = copy_migrations("migrations_reversible");
+
+    let mut db = TestDatabase::new("repair", "migrations_reversible");
+    db.set_migrations(migrations_dir.path().to_str().unwrap());
+    db.run_migration(false, None, false).success();
+
+    // reformat an applied migration
+    fs::write(
+        migrations_dir.path().join("20230201000000_test2.up.sql"),
+        "CREATE TABLE test2 (x INTEGER PRIMARY KEY);\n",
+    )
+    .unwrap();
+
+    db.run_migration(false, None, false).failure();
+
+    cargo_bin_cmd!("sqlx")
+        .args([
+            "migrate",
+            "repair",
+            "-y",
+            "--database-url",
+            &db.connection_string(),
+            "--source",
+        ])
+        .arg(migrations_dir.path())
+        .assert()
+        .success();
+
+    db.run_migration(false, None, false).success().stdout("");
+}
+
+#[tokio::test]
+async fn mark_dirty_resolved() {
+    let db = TestDatabase::new("mark_dirty_resolved", "migrations_reversible");
+    db.run_migration(false, Some(20230301000000), false)
+        .success();
+
+    let mark_dirty = || async {
+        let mut conn = SqliteConnection::connect(&db.connection_string())
+            .await
+            .unwrap();
+        conn.execute("UPDATE _sqlx_migrations SET success = FALSE WHERE version = 20230301000000")
+            .await
+            .unwrap();
+        conn.close().await.unwrap();
+    };
+
+    let resolve = |args: &[&str]| {
+        cargo_bin_cmd!("sqlx")
+            .args([
+                "migrate",
+                "mark-dirty-resolved",
+                "--database-url",
+                &db.connection_string(),
+            ])
+            .args(args)
+            .assert()
+            .success();
+    };
+
+    mark_dirty().await;
+    db.run_migration(false, None, false).failure();
+
+    resolve(&[]);
+    assert_eq!(db.applied_migrations().await.len(), 3);
+    db.run_migration(false, Some(20230301000000), false)
+        .success();
+
+    mark_dirty().await;
+    resolve(&["--rolled-back"]);
+    assert_eq!(
+        db.applied_migrations().await,
+        [20230101000000, 20230201000000]
+    );
+}
+
+#[tokio::test]
+async fn out_of_order() {
+    let migrations_dir = TempDir::new().unwrap();
+
+    let copy_migrations = |pattern: &str| {
+        for entry in fs::read_dir("tests/migrations_reversible").unwrap() {
+            let path = entry.unwrap().path();
+            let file_name = path.file_name().unwrap();
+
+            if file_name.to_str().unwrap().contains(pattern) {
+                fs::copy(&path, migrations_dir.path().join(file_name)).unwrap();
+            }
+        }
+    };
+
+    // migration 2 is merged after migration 3 was deployed
+    copy_migrations("_test1.");
+    copy_migrations("_test3.");
+
+    let mut db = TestDatabase::new("out_of_order", "migrations_reversible");
+    db.set_migrations(migrations_dir.path().to_str().unwrap());
+    db.run_migration(false, None, false).success();
+
+    copy_migrations("_test2.");
+
+    let info = db.migrate_info().success().get_output().stdout.clone();
+    assert!(String::from_utf8(info)
+        .unwrap()
+        .contains("20230201000000/pending (out of order) test2"));
+
+    let config_path = migrations_dir.path().join("sqlx.toml");
+    fs::write(&config_path, "[migrate]\nstrict-order = true\n").unwrap();
+    db.config_path = Some(config_path);
+
+    db.run_migration(false, None, false).failure();
+
+    db.config_path = None;
+
+    db.run_migration(false, None, false).success();
+
+    db.migrate_info().success().stdout(
+        "20230101000000/installed test1\n\
+         20230201000000/installed (out of order) test2\n\
+         20230301000000/installed test3\n",
+    );
+}
@@ -3,6 +3,7 @@ use crate::column::Column;
 use crate::ext::ustr::UStr;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "offline", derive(serde::Serialize, serde::Deserialize))]
 pub struct AnyColumn {
     // NOTE: these fields are semver-exempt. See crate root docs for details.
     #[doc(hidden)]
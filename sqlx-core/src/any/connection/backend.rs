@@ -1,5 +1,8 @@
 use crate::any::{AnyArguments, AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo};
+use crate::common::QueryCanceller;
+use crate::connection::ResetMode;
 use crate::sql_str::SqlStr;
+use crate::transaction::TransactionOptions;
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
@@ -34,6 +37,9 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     /// `Error::InvalidSavePoint` is returned without running any statements.
     fn begin(&mut self, statement: Option<SqlStr>) -> BoxFuture<'_, crate::Result<()>>;
 
+    /// Render the statement that begins a new transaction with the given options.
+    fn begin_statement(&self, options: &TransactionOptions) -> SqlStr;
+
     fn commit(&mut self) -> BoxFuture<'_, crate::Result<()>>;
 
     fn rollback(&mut self) -> BoxFuture<'_, crate::Result<()>>;
@@ -71,6 +77,18 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
         Box::pin(async move { Ok(()) })
     }
 
+    /// Forward to [`Connection::reset()`].
+    ///
+    /// [`Connection::reset()`]: method@crate::connection::Connection::reset
+    fn reset(&mut self, _mode: ResetMode) -> BoxFuture<'_, crate::Result<()>> {
+        let name = self.name().to_string();
+        Box::pin(async move {
+            Err(crate::Error::Configuration(
+                format!("{name} driver does not support resetting connections").into(),
+            ))
+        })
+    }
+
     /// Forward to [`Connection::shrink_buffers()`].
     ///
     /// [`Connection::shrink_buffers()`]: method@crate::connection::Connection::shrink_buffers
@@ -93,6 +111,11 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
         ))
     }
 
+    /// Returns a handle that can cancel a query running on this connection.
+    ///
+    /// Used to enforce [`Execute::timeout()`][crate::executor::Execute::timeout].
+    fn query_canceller(&self) -> QueryCanceller;
+
     fn fetch_many(
         &mut self,
         query: SqlStr,
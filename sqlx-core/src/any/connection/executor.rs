@@ -24,7 +24,15 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
             Err(error) => return stream::once(future::ready(Err(error))).boxed(),
         };
         let persistent = query.persistent();
-        self.backend.fetch_many(query.sql(), persistent, arguments)
+        let canceller = query
+            .timeout()
+            .map(|timeout| (self.backend.query_canceller(), timeout));
+        let stream = self.backend.fetch_many(query.sql(), persistent, arguments);
+
+        match canceller {
+            Some((canceller, timeout)) => canceller.timeout_stream(stream, timeout),
+            None => stream,
+        }
     }
 
     fn fetch_optional<'e, 'q: 'e, E>(
@@ -40,8 +48,17 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
             Err(error) => return future::ready(Err(error)).boxed(),
         };
         let persistent = query.persistent();
-        self.backend
-            .fetch_optional(query.sql(), persistent, arguments)
+        let canceller = query
+            .timeout()
+            .map(|timeout| (self.backend.query_canceller(), timeout));
+        let future = self
+            .backend
+            .fetch_optional(query.sql(), persistent, arguments);
+
+        match canceller {
+            Some((canceller, timeout)) => canceller.timeout_future(future, timeout),
+            None => future,
+        }
     }
 
     fn prepare_with<'e>(
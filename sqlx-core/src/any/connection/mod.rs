@@ -2,13 +2,12 @@ use futures_core::future::BoxFuture;
 use std::future::Future;
 
 use crate::any::{Any, AnyConnectOptions};
-use crate::connection::{ConnectOptions, Connection};
+use crate::connection::{ConnectOptions, Connection, ResetMode};
 use crate::error::Error;
 
 use crate::config;
 use crate::database::Database;
-use crate::sql_str::SqlSafeStr;
-use crate::transaction::Transaction;
+use crate::transaction::{IntoBeginStatement, Transaction};
 pub use backend::AnyConnectionBackend;
 
 mod backend;
@@ -119,12 +118,13 @@ impl Connection for AnyConnection {
 
     fn begin_with(
         &mut self,
-        statement: impl SqlSafeStr,
+        statement: impl IntoBeginStatement<Self::Database>,
     ) -> impl Future<Output = Result<Transaction<'_, Self::Database>, Error>> + Send + '_
     where
         Self: Sized,
     {
-        Transaction::begin(self, Some(statement.into_sql_str()))
+        let statement = statement.into_begin_statement(self);
+        Transaction::begin(self, Some(statement))
     }
 
     fn cached_statements_size(&self) -> usize {
@@ -135,6 +135,10 @@ impl Connection for AnyConnection {
         self.backend.clear_cached_statements()
     }
 
+    fn reset(&mut self, mode: ResetMode) -> impl Future<Output = Result<(), Error>> + Send + '_ {
+        self.backend.reset(mode)
+    }
+
     fn shrink_buffers(&mut self) {
         self.backend.shrink_buffers()
     }
@@ -92,6 +92,47 @@ impl Migrate for AnyConnection {
         Box::pin(async { self.get_migrate()?.apply(table_name, migration).await })
     }
 
+    fn squash<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async { self.get_migrate()?.squash(table_name, migration).await })
+    }
+
+    fn repair<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async { self.get_migrate()?.repair(table_name, migration).await })
+    }
+
+    fn mark_applied<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async {
+            self.get_migrate()?
+                .mark_applied(table_name, migration)
+                .await
+        })
+    }
+
+    fn mark_dirty_resolved<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+        applied: bool,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            self.get_migrate()?
+                .mark_dirty_resolved(table_name, version, applied)
+                .await
+        })
+    }
+
     fn revert<'e>(
         &'e mut self,
         table_name: &'e str,
@@ -16,6 +16,7 @@ mod query_result;
 pub(crate) mod row;
 mod statement;
 mod transaction;
+mod type_checking;
 pub(crate) mod type_info;
 pub mod types;
 pub(crate) mod value;
@@ -4,7 +4,7 @@ use crate::any::{Any, AnyConnection};
 use crate::database::Database;
 use crate::error::Error;
 use crate::sql_str::SqlStr;
-use crate::transaction::TransactionManager;
+use crate::transaction::{TransactionManager, TransactionOptions};
 
 pub struct AnyTransactionManager;
 
@@ -18,6 +18,10 @@ impl TransactionManager for AnyTransactionManager {
         conn.backend.begin(statement)
     }
 
+    fn begin_statement(conn: &AnyConnection, options: &TransactionOptions) -> SqlStr {
+        conn.backend.begin_statement(options)
+    }
+
     fn commit(conn: &mut AnyConnection) -> impl Future<Output = Result<(), Error>> + Send + '_ {
         conn.backend.commit()
     }
@@ -0,0 +1,59 @@
+use crate::any::{Any, AnyTypeInfo, AnyTypeInfoKind, AnyValue};
+use crate::config::macros::PreferredCrates;
+use crate::type_checking::{Error, FmtValue, ParamChecking, TypeChecking};
+use crate::value::Value;
+
+// Written out by hand as `impl_type_checking!()` refers to `sqlx_core` by name.
+impl TypeChecking for Any {
+    const PARAM_CHECKING: ParamChecking = ParamChecking::Strong;
+
+    fn param_type_for_id(
+        info: &AnyTypeInfo,
+        _preferred_crates: &PreferredCrates,
+    ) -> Result<&'static str, Error> {
+        Ok(match info.kind {
+            AnyTypeInfoKind::Text => "&str",
+            AnyTypeInfoKind::Blob => "&[u8]",
+            kind => rust_type_for_kind(kind)?,
+        })
+    }
+
+    fn return_type_for_id(
+        info: &AnyTypeInfo,
+        _preferred_crates: &PreferredCrates,
+    ) -> Result<&'static str, Error> {
+        rust_type_for_kind(info.kind)
+    }
+
+    fn get_feature_gate(_info: &AnyTypeInfo) -> Option<&'static str> {
+        None
+    }
+
+    fn fmt_value_debug(value: &AnyValue) -> FmtValue<'_, Self> {
+        match value.type_info().kind {
+            AnyTypeInfoKind::Bool => FmtValue::debug::<bool>(value),
+            AnyTypeInfoKind::SmallInt => FmtValue::debug::<i16>(value),
+            AnyTypeInfoKind::Integer => FmtValue::debug::<i32>(value),
+            AnyTypeInfoKind::BigInt => FmtValue::debug::<i64>(value),
+            AnyTypeInfoKind::Real => FmtValue::debug::<f32>(value),
+            AnyTypeInfoKind::Double => FmtValue::debug::<f64>(value),
+            AnyTypeInfoKind::Text => FmtValue::debug::<String>(value),
+            AnyTypeInfoKind::Blob => FmtValue::debug::<Vec<u8>>(value),
+            AnyTypeInfoKind::Null => FmtValue::unknown(value),
+        }
+    }
+}
+
+fn rust_type_for_kind(kind: AnyTypeInfoKind) -> Result<&'static str, Error> {
+    Ok(match kind {
+        AnyTypeInfoKind::Bool => "bool",
+        AnyTypeInfoKind::SmallInt => "i16",
+        AnyTypeInfoKind::Integer => "i32",
+        AnyTypeInfoKind::BigInt => "i64",
+        AnyTypeInfoKind::Real => "f32",
+        AnyTypeInfoKind::Double => "f64",
+        AnyTypeInfoKind::Text => "String",
+        AnyTypeInfoKind::Blob => "Vec<u8>",
+        AnyTypeInfoKind::Null => return Err(Error::NoMappingFound),
+    })
+}
@@ -5,6 +5,7 @@ use crate::type_info::TypeInfo;
 use AnyTypeInfoKind::*;
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "offline", derive(serde::Serialize, serde::Deserialize))]
 pub struct AnyTypeInfo {
     #[doc(hidden)]
     pub kind: AnyTypeInfoKind,
@@ -17,6 +18,7 @@ impl AnyTypeInfo {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "offline", derive(serde::Serialize, serde::Deserialize))]
 pub enum AnyTypeInfoKind {
     Null,
     Bool,
@@ -1,5 +1,7 @@
+mod query_timeout;
 mod statement_cache;
 
+pub use query_timeout::QueryCanceller;
 pub use statement_cache::StatementCache;
 use std::fmt::{Debug, Formatter};
 use std::ops::{Deref, DerefMut};
@@ -0,0 +1,117 @@
+use std::future::{poll_fn, Future};
+use std::task::Poll;
+use std::time::Duration;
+
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+use futures_util::{FutureExt, StreamExt, TryStreamExt};
+
+use crate::error::Error;
+
+/// Cancels the query running on a connection, from outside of that connection.
+///
+/// Used by drivers to enforce [`Execute::timeout()`][crate::executor::Execute::timeout].
+pub struct QueryCanceller {
+    cancel: Box<dyn FnOnce() -> BoxFuture<'static, Result<(), Error>> + Send>,
+    is_cancellation: fn(&Error) -> bool,
+}
+
+impl QueryCanceller {
+    /// Create a new canceller.
+    ///
+    /// `cancel` is called once the timeout has elapsed, and should ask the database to stop the
+    /// query that is running. The query is expected to then fail with an error for which
+    /// `is_cancellation` returns `true`.
+    pub fn new(
+        cancel: impl FnOnce() -> BoxFuture<'static, Result<(), Error>> + Send + 'static,
+        is_cancellation: fn(&Error) -> bool,
+    ) -> Self {
+        QueryCanceller {
+            cancel: Box::new(cancel),
+            is_cancellation,
+        }
+    }
+
+    /// Cancel the query producing `stream` if it is still running after `timeout`.
+    ///
+    /// The stream is still read to completion after the query is cancelled, so that the
+    /// connection stays in a consistent state. If it then fails with the cancellation error,
+    /// [`Error::QueryTimedOut`] is returned instead.
+    pub fn timeout_stream<'a, T: Send + 'a>(
+        self,
+        stream: BoxStream<'a, Result<T, Error>>,
+        timeout: Duration,
+    ) -> BoxStream<'a, Result<T, Error>> {
+        let QueryCanceller {
+            cancel,
+            is_cancellation,
+        } = self;
+
+        Box::pin(try_stream! {
+            let mut stream = stream;
+            let mut sleep = Box::pin(crate::rt::sleep(timeout));
+            let mut cancel = Some(cancel);
+            let mut cancelling: Option<BoxFuture<'static, Result<(), Error>>> = None;
+            let mut timed_out = false;
+
+            let res = loop {
+                let next = poll_fn(|cx| {
+                    if !timed_out && sleep.as_mut().poll(cx).is_ready() {
+                        timed_out = true;
+                        cancelling = cancel.take().map(|cancel| cancel());
+                    }
+
+                    if let Some(Poll::Ready(res)) = cancelling.as_mut().map(|f| f.poll_unpin(cx)) {
+                        if let Err(error) = res {
+                            tracing::warn!(%error, "failed to cancel query after timeout");
+                        }
+
+                        cancelling = None;
+                    }
+
+                    stream.poll_next_unpin(cx)
+                })
+                .await;
+
+                match next {
+                    Some(Ok(v)) => r#yield!(v),
+                    Some(Err(e)) if timed_out && is_cancellation(&e) => {
+                        break Err(Error::QueryTimedOut)
+                    }
+                    Some(Err(e)) => break Err(e),
+                    None => break Ok(()),
+                }
+            };
+
+            // Don't let the cancellation outlive the query, or it could cancel the next one.
+            if let Some(cancelling) = cancelling {
+                if let Err(error) = cancelling.await {
+                    tracing::warn!(%error, "failed to cancel query after timeout");
+                }
+            }
+
+            res
+        })
+    }
+
+    /// Like [`timeout_stream()`][Self::timeout_stream], but for a future.
+    pub fn timeout_future<'a, T: Send + 'a>(
+        self,
+        future: BoxFuture<'a, Result<T, Error>>,
+        timeout: Duration,
+    ) -> BoxFuture<'a, Result<T, Error>> {
+        let mut stream = self.timeout_stream(future.into_stream().boxed(), timeout);
+
+        Box::pin(async move {
+            let ret = stream
+                .try_next()
+                .await?
+                .ok_or_else(|| Error::Protocol("query future did not produce a result".into()))?;
+
+            // Drive the stream to the end so the cancellation, if any, completes.
+            while stream.try_next().await?.is_some() {}
+
+            Ok(ret)
+        })
+    }
+}
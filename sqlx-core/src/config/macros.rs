@@ -225,6 +225,144 @@ pub struct Config {
     /// '"My Column"' = "crate::types::MyType"
     /// ```
     pub table_overrides: BTreeMap<TableName, BTreeMap<ColumnName, RustType>>,
+
+    /// Check queries for SQLite against an in-memory database with the crate's migrations applied.
+    ///
+    /// When enabled, the macros do not use `DATABASE_URL` or the offline query data in `.sqlx`
+    /// for SQLite. Instead, the migrations in [`migrate.migrations-dir`] are applied to a new
+    /// in-memory database when the crate is compiled, and queries are checked against that.
+    /// No database file or `cargo sqlx prepare` step is needed, and the checked schema cannot go
+    /// stale.
+    ///
+    /// Migrations are resolved as for `sqlx::migrate!()`, and like it, the macros include the
+    /// migration files so that queries are checked again when one of them changes. Cargo does not
+    /// know to recompile when a migration is added, unless told to, e.g. with
+    /// `cargo:rerun-if-changed=migrations` in a build script.
+    ///
+    /// Requires one of the runtime features of SQLx to be enabled.
+    ///
+    /// [`migrate.migrations-dir`]: crate::config::migrate::Config::migrations_dir
+    ///
+    /// #### `sqlx.toml`
+    /// ```toml
+    /// [macros]
+    /// sqlite-from-migrations = true
+    /// ```
+    pub sqlite_from_migrations: bool,
+
+    /// Specify additional derives for the record types generated by `query!()`.
+    ///
+    /// Each entry is the path to a derive macro, as it would be written in `#[derive(...)]`
+    /// in the crate invoking the macros. `Debug` is always derived and must not be listed.
+    ///
+    /// Derives for a single query can also be added with an attribute in the macro invocation,
+    /// e.g. `query!(#[derive(serde::Serialize)] "SELECT ...")`.
+    ///
+    /// #### `sqlx.toml`
+    /// ```toml
+    /// [macros]
+    /// # Allow returning generated records directly from a web handler
+    /// record-derives = ["serde::Serialize"]
+    /// ```
+    pub record_derives: Vec<String>,
+
+    /// Specify overrides for the inferred nullability of table columns, keyed by table name.
+    ///
+    /// The nullability SQLx infers for a column is not always right, e.g. for columns of views,
+    /// which databases generally report as nullable. An override here applies to any output
+    /// column that the database reports as originating from the given table and column,
+    /// in the same way as a `!` or `?` suffix on the column name in the query itself.
+    ///
+    /// A suffix on the column name in the query takes precedence over these overrides.
+    ///
+    /// Table and column names follow the same rules as for
+    /// [`table_overrides`][Self::table_overrides].
+    ///
+    /// Whether the origin of a column is known depends on the database driver. Postgres reports
+    /// the columns of a view as originating from the view, while SQLite reports the column
+    /// of the underlying table, if there is one.
+    ///
+    /// Example
+    /// -------
+    ///
+    /// #### `sqlx.toml`
+    /// ```toml
+    /// [macros.nullability-overrides.'active_users']
+    /// # Columns of a view are always reported as nullable, but this one never is:
+    /// 'id' = "non-null"
+    ///
+    /// [macros.nullability-overrides.'my_schema.my_table']
+    /// 'my_column' = "nullable"
+    /// ```
+    pub nullability_overrides: BTreeMap<TableName, BTreeMap<ColumnName, Nullability>>,
+
+    /// Specify overrides for the inferred nullability of columns computed by SQL functions.
+    ///
+    /// An override applies to an output column that does not originate from a table and is named
+    /// for a call to the given function: either exactly the function name (as in Postgres)
+    /// or the text of the call expression (as in MySQL and SQLite). Function names are matched
+    /// case-insensitively. If the call is given an alias in the query, the override does not apply.
+    ///
+    /// A suffix on the column name in the query takes precedence over these overrides.
+    ///
+    /// Example
+    /// -------
+    ///
+    /// #### `sqlx.toml`
+    /// ```toml
+    /// [macros.function-nullability]
+    /// # `SELECT coalesce_user(...) FROM ...` never returns `NULL`:
+    /// 'coalesce_user' = "non-null"
+    /// ```
+    pub function_nullability: BTreeMap<FunctionName, Nullability>,
+
+    /// Check queries for the `Any` driver against several databases.
+    ///
+    /// If set, the macros expand for [`Any`][crate::any::Any] instead of a specific database.
+    /// Each query is checked against every backend listed here, and must be valid for all of them:
+    /// they must agree on the number of parameters, and on the names and types of the columns,
+    /// as mapped to [`AnyTypeInfoKind`][crate::any::AnyTypeInfoKind]. Integer types of different
+    /// widths are reconciled, as the `Any` driver converts between them.
+    ///
+    /// Each key is a backend (`postgres`, `mysql` or `sqlite`), whose Cargo feature must be
+    /// enabled. Each value is the name of the environment variable with the URL of a database
+    /// for that backend, which is read in the same way as `DATABASE_URL`.
+    /// If a variable is not set, or `SQLX_OFFLINE=true`, the data saved for that backend by
+    /// `cargo sqlx prepare` is used instead. If [`sqlite_from_migrations`][Self::sqlite_from_migrations]
+    /// is set, the `sqlite` backend is checked against the migrations instead.
+    ///
+    /// The query is sent to the database as written, so bind parameters must use a syntax that
+    /// every backend accepts, e.g. `$1` for both Postgres and SQLite.
+    ///
+    /// Requires the `any` feature of SQLx.
+    ///
+    /// Example
+    /// -------
+    ///
+    /// #### `sqlx.toml`
+    /// ```toml
+    /// [macros.any-backends]
+    /// postgres = "POSTGRES_DATABASE_URL"
+    /// sqlite = "SQLITE_DATABASE_URL"
+    /// ```
+    pub any_backends: BTreeMap<Box<str>, Box<str>>,
+}
+
+/// The nullability to use for a column in an override.
+///
+/// See [`macros.nullability-overrides`][Config::nullability_overrides] for usages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum Nullability {
+    /// The column is never `NULL`, and is not wrapped in `Option<_>`.
+    NonNull,
+
+    /// The column may be `NULL`, and is wrapped in `Option<_>`.
+    Nullable,
 }
 
 #[derive(Debug, Default)]
@@ -361,6 +499,11 @@ pub type TableName = Box<str>;
 /// See [`macros.table-overrides`][Config::table_overrides] for usages.
 pub type ColumnName = Box<str>;
 
+/// A SQL function name, without a schema, as output columns are named for the unqualified call.
+///
+/// See [`macros.function-nullability`][Config::function_nullability] for usages.
+pub type FunctionName = Box<str>;
+
 /// A Rust type name or path.
 ///
 /// Should be a global path (not relative).
@@ -381,6 +524,31 @@ impl Config {
             .and_then(|by_column| by_column.get(column))
             .map(|s| &**s)
     }
+
+    /// Get the nullability override for a given column and table name
+    /// (optionally schema-qualified).
+    pub fn column_nullability(&self, table: &str, column: &str) -> Option<Nullability> {
+        self.nullability_overrides
+            .get(table)
+            .and_then(|by_column| by_column.get(column))
+            .copied()
+    }
+
+    /// Get the nullability override for an output column named `column_name`,
+    /// if it is named for a call to a function with an override.
+    pub fn function_nullability(&self, column_name: &str) -> Option<Nullability> {
+        self.function_nullability
+            .iter()
+            .find(|(function, _)| {
+                let Some(rest) = column_name.get(function.len()..) else {
+                    return false;
+                };
+
+                column_name[..function.len()].eq_ignore_ascii_case(function)
+                    && (rest.is_empty() || rest.trim_start().starts_with('('))
+            })
+            .map(|(_, nullability)| *nullability)
+    }
 }
 
 impl DateTimeCrate {
@@ -1,4 +1,4 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 /// Configuration for migrations when executed using `sqlx::migrate!()` or through `sqlx-cli`.
 ///
@@ -114,8 +114,52 @@ pub struct Config {
     // Likely lower overhead for small sets than `HashSet`.
     pub ignored_chars: BTreeSet<char>,
 
+    /// Return an error for pending migrations that are older than the latest applied migration,
+    /// instead of applying them.
+    ///
+    /// Such migrations are applied by default, as happens when migrations are added on separate
+    /// branches, which may be merged and deployed in a different order than their versions.
+    /// The order the migrations were applied in is recorded in the migrations table either way,
+    /// and `sqlx migrate info` shows those applied out of order.
+    ///
+    /// ### Example
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate]
+    /// strict-order = true
+    /// ```
+    pub strict_order: bool,
+
     /// Specify default options for new migrations created with `sqlx migrate add`.
     pub defaults: MigrationDefaults,
+
+    /// Configure the rules checked by `sqlx migrate lint`.
+    pub lint: LintConfig,
+
+    /// Replace `${name}` placeholders in migrations, for values that differ between environments.
+    ///
+    /// Each placeholder is replaced with the value given here or, if there is none, the value of
+    /// the environment variable `SQLX_VAR_name`; a placeholder with neither is an error. `name` may contain
+    /// letters, digits and underscores. Use `$${` for a literal `${`.
+    ///
+    /// Placeholders are only replaced if this is set, even if it's empty. The values are read
+    /// when the migrations run, both by `sqlx migrate run` and by `sqlx::migrate!()`.
+    ///
+    /// Checksums are taken of migrations before the placeholders are replaced,
+    /// so databases migrated with different values don't see the migrations as modified.
+    ///
+    /// ### Example
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate.vars]
+    /// app_role = "app"
+    /// ```
+    ///
+    /// `migrations/20240101000000_grants.sql`:
+    /// ```sql
+    /// GRANT SELECT ON users TO ${app_role};
+    /// ```
+    pub vars: Option<BTreeMap<Box<str>, Box<str>>>,
 }
 
 #[derive(Debug, Default)]
@@ -146,6 +190,115 @@ pub struct MigrationDefaults {
     pub migration_versioning: DefaultVersioning,
 }
 
+#[derive(Debug, Default)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(default, rename_all = "kebab-case", deny_unknown_fields)
+)]
+pub struct LintConfig {
+    /// Rules that `sqlx migrate lint` should not check in any migration.
+    ///
+    /// ### Example: Allow Dropping Columns
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate.lint]
+    /// allow = ["drop-column"]
+    /// ```
+    pub allow: BTreeSet<LintRule>,
+
+    /// Rules that `sqlx migrate lint` should not check in specific migrations, by version.
+    ///
+    /// Use this to acknowledge a warning once it's been reviewed,
+    /// e.g. after the application stopped using a column in a prior deploy.
+    ///
+    /// ### Example
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate.lint.allow-migrations]
+    /// 20240101000000 = ["drop-column"]
+    /// ```
+    #[cfg_attr(
+        feature = "sqlx-toml",
+        serde(deserialize_with = "deserialize_allow_migrations")
+    )]
+    pub allow_migrations: BTreeMap<i64, BTreeSet<LintRule>>,
+}
+
+// TOML keys are always strings.
+#[cfg(feature = "sqlx-toml")]
+fn deserialize_allow_migrations<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<i64, BTreeSet<LintRule>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    BTreeMap::<String, BTreeSet<LintRule>>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(version, rules)| {
+            let version = version.parse().map_err(|_| {
+                serde::de::Error::custom(format!("expected a migration version, got {version:?}"))
+            })?;
+
+            Ok((version, rules))
+        })
+        .collect()
+}
+
+/// A pattern in migrations that `sqlx migrate lint` warns about.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum LintRule {
+    /// (Postgres) `CREATE INDEX` without `CONCURRENTLY`, which blocks writes to the table
+    /// while the index is built.
+    CreateIndexNotConcurrently,
+
+    /// (Postgres) `CONCURRENTLY` in a migration that runs in a transaction, or alongside
+    /// other statements, which Postgres rejects.
+    ConcurrentlyInTransaction,
+
+    /// `ALTER TABLE ... ADD COLUMN ... NOT NULL` without a `DEFAULT`, which fails
+    /// if the table has rows, or breaks inserts from instances of the application not yet updated.
+    AddColumnNotNull,
+
+    /// Changing the type of a column, which may rewrite the whole table under an exclusive lock.
+    AlterColumnType,
+
+    /// `ALTER TABLE ... DROP COLUMN`, which breaks instances of the application
+    /// still using the column unless it was removed from them in a prior deploy.
+    DropColumn,
+}
+
+impl LintRule {
+    /// The name of the rule, as used in `sqlx.toml`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CreateIndexNotConcurrently => "create-index-not-concurrently",
+            Self::ConcurrentlyInTransaction => "concurrently-in-transaction",
+            Self::AddColumnNotNull => "add-column-not-null",
+            Self::AlterColumnType => "alter-column-type",
+            Self::DropColumn => "drop-column",
+        }
+    }
+}
+
+impl LintConfig {
+    /// Returns `true` if `rule` should not be checked in the migration with the given version.
+    pub fn is_allowed(&self, rule: LintRule, version: i64) -> bool {
+        self.allow.contains(&rule)
+            || self
+                .allow_migrations
+                .get(&version)
+                .is_some_and(|rules| rules.contains(&rule))
+    }
+}
+
 /// The default type of migration that `sqlx migrate add` should create by default.
 #[derive(Debug, Default, PartialEq, Eq)]
 #[cfg_attr(
@@ -1,5 +1,5 @@
 use crate::config::{self, Config};
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 #[test]
 fn reference_parses_as_config() {
@@ -48,6 +48,9 @@ fn assert_drivers_config(config: &config::drivers::Config) {
 fn assert_macros_config(config: &config::macros::Config) {
     use config::macros::*;
 
+    assert!(config.sqlite_from_migrations);
+    assert_eq!(config.record_derives, ["serde::Serialize", "Clone"]);
+
     assert_eq!(config.preferred_crates.date_time, DateTimeCrate::Chrono);
     assert_eq!(config.preferred_crates.numeric, NumericCrate::RustDecimal);
 
@@ -99,6 +102,40 @@ fn assert_macros_config(config: &config::macros::Config) {
         config.column_override(r#""My Schema"."My Table""#, r#""My Column""#),
         Some("crate::types::MyType"),
     );
+
+    // Nullability overrides
+    assert_eq!(
+        config.column_nullability("active_users", "id"),
+        Some(Nullability::NonNull),
+    );
+
+    assert_eq!(
+        config.column_nullability("my_schema.my_table", "my_column"),
+        Some(Nullability::Nullable),
+    );
+
+    assert_eq!(config.column_nullability("active_users", "name"), None);
+
+    assert_eq!(
+        config.function_nullability("coalesce_user"),
+        Some(Nullability::NonNull),
+    );
+
+    assert_eq!(
+        config.function_nullability("COALESCE_USER(name, 'anonymous')"),
+        Some(Nullability::NonNull),
+    );
+
+    assert_eq!(config.function_nullability("coalesce_users"), None);
+
+    assert_eq!(
+        config.any_backends.get("postgres").map(|var| &**var),
+        Some("POSTGRES_DATABASE_URL"),
+    );
+    assert_eq!(
+        config.any_backends.get("sqlite").map(|var| &**var),
+        Some("SQLITE_DATABASE_URL"),
+    );
 }
 
 fn assert_migrate_config(config: &config::migrate::Config) {
@@ -111,6 +148,8 @@ fn assert_migrate_config(config: &config::migrate::Config) {
 
     assert_eq!(config.ignored_chars, ignored_chars);
 
+    assert!(config.strict_order);
+
     assert_eq!(
         config.defaults.migration_type,
         DefaultMigrationType::Reversible
@@ -119,4 +158,17 @@ fn assert_migrate_config(config: &config::migrate::Config) {
         config.defaults.migration_versioning,
         DefaultVersioning::Sequential
     );
+    assert!(config
+        .lint
+        .is_allowed(LintRule::CreateIndexNotConcurrently, 1));
+    assert!(config.lint.is_allowed(LintRule::DropColumn, 20240101000000));
+    assert!(!config.lint.is_allowed(LintRule::DropColumn, 1));
+    assert!(!config
+        .lint
+        .is_allowed(LintRule::AddColumnNotNull, 20240101000000));
+
+    assert_eq!(
+        config.vars,
+        Some(BTreeMap::from([("app_role".into(), "app".into())]))
+    );
 }
@@ -1,9 +1,9 @@
 use crate::database::{Database, HasStatementCache};
 use crate::error::Error;
+use crate::retry::RetryPolicy;
 
 use crate::config;
-use crate::sql_str::SqlSafeStr;
-use crate::transaction::{Transaction, TransactionManager};
+use crate::transaction::{IntoBeginStatement, Transaction, TransactionManager};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
 use std::fmt::Debug;
@@ -52,20 +52,23 @@ pub trait Connection: Send {
         &mut self,
     ) -> impl Future<Output = Result<Transaction<'_, Self::Database>, Error>> + Send + '_;
 
-    /// Begin a new transaction with a custom statement.
+    /// Begin a new transaction with a custom statement or [`TransactionOptions`].
     ///
     /// Returns a [`Transaction`] for controlling and tracking the new transaction.
     ///
     /// Returns an error if the connection is already in a transaction or if
     /// `statement` does not put the connection into a transaction.
+    ///
+    /// [`TransactionOptions`]: crate::transaction::TransactionOptions
     fn begin_with(
         &mut self,
-        statement: impl SqlSafeStr,
+        statement: impl IntoBeginStatement<Self::Database>,
     ) -> impl Future<Output = Result<Transaction<'_, Self::Database>, Error>> + Send + '_
     where
         Self: Sized,
     {
-        Transaction::begin(self, Some(statement.into_sql_str()))
+        let statement = statement.into_begin_statement(self);
+        Transaction::begin(self, Some(statement))
     }
 
     /// Returns `true` if the connection is currently in a transaction.
@@ -128,6 +131,84 @@ pub trait Connection: Send {
         }
     }
 
+    /// Execute the function inside a transaction, retrying it according to `policy`
+    /// if it fails with a transient error.
+    ///
+    /// Each attempt runs in a new transaction. If the function returns an error (or committing
+    /// the transaction fails), the transaction is rolled back and, if
+    /// [`policy.should_retry()`][RetryPolicy::should_retry] allows it, the function is called
+    /// again after a delay. Otherwise, the error is returned.
+    ///
+    /// Because the function may be called more than once, it must be `Fn` rather than `FnOnce`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use sqlx::postgres::PgConnection;
+    /// use sqlx::retry::RetryPolicy;
+    /// use sqlx::Connection;
+    ///
+    /// # pub async fn _f(conn: &mut PgConnection) -> sqlx::Result<i64> {
+    /// conn.transaction_with_retry(&RetryPolicy::new(), |txn| Box::pin(async move {
+    ///     sqlx::query("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE")
+    ///         .execute(&mut **txn)
+    ///         .await?;
+    ///
+    ///     sqlx::query_scalar("UPDATE counters SET value = value + 1 RETURNING value")
+    ///         .fetch_one(&mut **txn)
+    ///         .await
+    /// })).await
+    /// # }
+    /// ```
+    fn transaction_with_retry<'a, F, R, E>(
+        &'a mut self,
+        policy: &'a RetryPolicy<E>,
+        callback: F,
+    ) -> impl Future<Output = Result<R, E>> + Send + 'a
+    where
+        for<'c> F: Fn(&'c mut Transaction<'_, Self::Database>) -> BoxFuture<'c, Result<R, E>>
+            + 'a
+            + Send
+            + Sync,
+        Self: Sized,
+        R: Send,
+        E: From<Error> + Send,
+    {
+        async move {
+            let mut attempt = 1;
+
+            loop {
+                let res: Result<R, E> = async {
+                    let mut transaction = self.begin().await?;
+
+                    match callback(&mut transaction).await {
+                        Ok(ret) => {
+                            transaction.commit().await?;
+
+                            Ok(ret)
+                        }
+                        Err(err) => {
+                            transaction.rollback().await?;
+
+                            Err(err)
+                        }
+                    }
+                }
+                .await;
+
+                match res {
+                    Err(error) if policy.should_retry(attempt, &error) => {
+                        tracing::debug!(attempt, "retrying transaction after transient error");
+
+                        crate::rt::sleep(policy.backoff_for(attempt)).await;
+                        attempt += 1;
+                    }
+                    res => return res,
+                }
+            }
+        }
+    }
+
     /// The number of statements currently cached in the connection.
     fn cached_statements_size(&self) -> usize
     where
@@ -145,6 +226,30 @@ pub trait Connection: Send {
         async move { Ok(()) }
     }
 
+    /// Discard any session state accumulated on this connection,
+    /// so that it can be handed to unrelated code as if it were freshly opened.
+    ///
+    /// This covers things like session variables, temporary tables, advisory locks and
+    /// `LISTEN` channels; what exactly is reset depends on the database and the given [`ResetMode`].
+    ///
+    /// Any statements cached by the connection that no longer exist on the server afterwards
+    /// are removed from the statement cache.
+    ///
+    /// This is used to implement
+    /// [`PoolOptions::reset_on_release()`][crate::pool::PoolOptions::reset_on_release].
+    ///
+    /// The default implementation returns an error, for drivers which can't reset connections;
+    /// with `reset_on_release()`, the pool then closes connections instead of reusing them.
+    fn reset(&mut self, mode: ResetMode) -> impl Future<Output = Result<(), Error>> + Send + '_ {
+        let _ = mode;
+
+        async move {
+            Err(Error::Configuration(
+                "this driver does not support resetting connections".into(),
+            ))
+        }
+    }
+
     /// Restore any buffers in the connection to their default capacity, if possible.
     ///
     /// Sending a large query or receiving a resultset with many columns can cause the connection
@@ -190,6 +295,32 @@ pub trait Connection: Send {
     }
 }
 
+/// How thoroughly to reset a connection's session state in [`Connection::reset()`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ResetMode {
+    /// Reset all session state, including server-side prepared statements.
+    ///
+    /// This clears the connection's statement cache, so statements will need to be prepared
+    /// again on next use.
+    ///
+    /// * Postgres: `DISCARD ALL`
+    /// * MySQL: `COM_RESET_CONNECTION`, then re-applies the session settings that were
+    ///   set when the connection was opened (`sql_mode`, `time_zone` and `SET NAMES`).
+    /// * SQLite: drops all tables, views and triggers in the `temp` schema
+    ///   and clears the statement cache.
+    All,
+
+    /// Reset session state, but keep prepared statements where the database allows it.
+    ///
+    /// * Postgres: the equivalent of `DISCARD ALL` except for `DEALLOCATE ALL` and `DISCARD PLANS`:
+    ///   `CLOSE ALL; SET SESSION AUTHORIZATION DEFAULT; RESET ALL; UNLISTEN *;
+    ///   SELECT pg_advisory_unlock_all(); DISCARD TEMP; DISCARD SEQUENCES;`
+    /// * MySQL: same as [`ResetMode::All`], as `COM_RESET_CONNECTION` always deallocates
+    ///   prepared statements and there is no lighter-weight equivalent.
+    /// * SQLite: drops all tables, views and triggers in the `temp` schema.
+    Session,
+}
+
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct LogSettings {
@@ -110,6 +110,14 @@ pub enum Error {
     #[error("attempted to acquire a connection on a closed pool")]
     PoolClosed,
 
+    /// A query did not complete within the timeout set with [`Query::timeout`].
+    ///
+    /// The query was cancelled, and the connection can still be used.
+    ///
+    /// [`Query::timeout`]: crate::query::Query::timeout
+    #[error("query timed out")]
+    QueryTimedOut,
+
     /// A background worker has crashed.
     #[error("attempted to communicate with a crashed background worker")]
     WorkerCrashed,
@@ -148,6 +156,16 @@ impl Error {
         }
     }
 
+    /// Returns `true` if this is a database error which is
+    /// [transient][DatabaseError::is_transient], meaning the transaction that caused it
+    /// may succeed if retried.
+    ///
+    /// This is the default classifier used by [`RetryPolicy`][crate::retry::RetryPolicy].
+    pub fn is_retryable(&self) -> bool {
+        self.as_database_error()
+            .is_some_and(|error| error.is_transient())
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn protocol(err: impl Display) -> Self {
@@ -270,6 +288,18 @@ pub trait DatabaseError: 'static + Send + Sync + StdError {
     fn is_check_violation(&self) -> bool {
         matches!(self.kind(), ErrorKind::CheckViolation)
     }
+
+    /// Returns whether the error is caused by a transient condition, such as a serialization
+    /// failure or a deadlock, which may not occur again if the transaction is retried.
+    ///
+    /// * Postgres: `serialization_failure` (`40001`) and `deadlock_detected` (`40P01`).
+    /// * MySQL: `ER_LOCK_DEADLOCK` (1213) and `ER_LOCK_WAIT_TIMEOUT` (1205).
+    /// * SQLite: `SQLITE_BUSY` and `SQLITE_LOCKED`, including their extended result codes.
+    ///
+    /// See [`RetryPolicy`][crate::retry::RetryPolicy] for a way to act on this.
+    fn is_transient(&self) -> bool {
+        false
+    }
 }
 
 impl dyn DatabaseError {
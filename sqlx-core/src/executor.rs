@@ -6,6 +6,7 @@ use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::{FutureExt, StreamExt, TryFutureExt, TryStreamExt};
+use std::time::Duration;
 use std::{fmt::Debug, future};
 
 /// A type that contains or can provide a database
@@ -211,6 +212,13 @@ pub trait Execute<'q, DB: Database>: Send + Sized {
 
     /// Returns `true` if the statement should be cached.
     fn persistent(&self) -> bool;
+
+    /// Returns the maximum time the query may run for, if any.
+    ///
+    /// See [`Query::timeout()`][crate::query::Query::timeout].
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
 }
 
 impl<DB: Database, T> Execute<'_, DB> for T
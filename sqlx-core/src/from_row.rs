@@ -310,6 +310,97 @@ pub trait FromRow<'r, R: Row>: Sized {
     fn from_row(row: &'r R) -> Result<Self, Error>;
 }
 
+/// The columns read by an implementation of [`FromRow`] generated by `#[derive(FromRow)]`.
+///
+/// `query_as!()` uses this to check a query against the struct at compile time.
+/// Not public API.
+///
+/// `P` only makes the bounds on `#[sqlx(flatten)]` fields non-trivial, so that the derive still
+/// compiles when a flattened type implements `FromRow` by hand.
+#[doc(hidden)]
+pub trait FromRowColumns<P = ()> {
+    const COLUMNS: &'static [FromRowColumn];
+}
+
+#[doc(hidden)]
+pub enum FromRowColumn {
+    /// A column read by name. It may be missing from the row if `optional`.
+    Named {
+        name: &'static str,
+        optional: bool,
+        /// The error if the column is required but missing.
+        missing: &'static str,
+    },
+    /// The columns of a `#[sqlx(flatten)]` field.
+    Flatten {
+        columns: &'static [FromRowColumn],
+        optional: bool,
+    },
+}
+
+/// The type that the column with the hashed name `NAME` is decoded as by
+/// `#[derive(FromRow)]`. Not public API.
+#[doc(hidden)]
+pub trait FromRowColumnType<const NAME: u64> {
+    type Type;
+}
+
+/// Fails compile-time evaluation if a required column in `columns` is not in `returned`.
+#[doc(hidden)]
+pub const fn check_columns(columns: &[FromRowColumn], returned: &[&str]) {
+    let mut i = 0;
+
+    while i < columns.len() {
+        match &columns[i] {
+            FromRowColumn::Named {
+                name,
+                optional: false,
+                missing,
+            } => {
+                let mut found = false;
+                let mut j = 0;
+
+                while j < returned.len() {
+                    found |= str_eq(name, returned[j]);
+                    j += 1;
+                }
+
+                if !found {
+                    panic!("{}", *missing);
+                }
+            }
+            FromRowColumn::Flatten {
+                columns,
+                optional: false,
+            } => check_columns(columns, returned),
+            _ => (),
+        }
+
+        i += 1;
+    }
+}
+
+// `str::eq()` is not `const`
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+
+        i += 1;
+    }
+
+    true
+}
+
 impl<'r, R> FromRow<'r, R> for ()
 where
     R: Row,
@@ -72,9 +72,11 @@ pub mod net;
 pub mod query_as;
 pub mod query_builder;
 pub mod query_scalar;
+pub mod query_script;
 pub mod sql_str;
 
 pub mod raw_sql;
+pub mod retry;
 pub mod row;
 pub mod rt;
 pub mod sync;
@@ -9,6 +9,9 @@ pub enum MigrateError {
     #[error("while executing migration {1}: {0}")]
     ExecuteMigration(#[source] Error, i64),
 
+    #[error("while executing migration {1}: {0}")]
+    ExecuteCode(#[source] BoxDynError, i64),
+
     #[error("while resolving migrations: {0}")]
     Source(#[source] BoxDynError),
 
@@ -18,6 +21,26 @@ pub enum MigrateError {
     #[error("migration {0} was previously applied but has been modified")]
     VersionMismatch(i64),
 
+    #[error(
+        "migration {0} squashes migrations that were only partially applied; \
+         apply the remaining ones before running it"
+    )]
+    PartiallySquashed(i64),
+
+    #[error(
+        "migration {1} uses the variable `{0}`, which is not set in `[migrate.vars]` or as `SQLX_VAR_{0}`"
+    )]
+    UndefinedVar(String, i64),
+
+    #[error(
+        "migration {0} is older than the latest applied migration {1}; \
+         disable strict ordering to apply it"
+    )]
+    OutOfOrder(i64, i64),
+
+    #[error("migration {0} is already present in the migration source")]
+    DuplicateVersion(i64),
+
     #[error("migration {0} is not present in the migration source")]
     VersionNotPresent(i64),
 
@@ -42,4 +65,7 @@ pub enum MigrateError {
 
     #[error("database driver does not support creation of schemas at migrate time: {0}")]
     CreateSchemasNotSupported(String),
+
+    #[error("database driver does not support {0}")]
+    OperationNotSupported(&'static str),
 }
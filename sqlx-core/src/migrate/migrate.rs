@@ -70,6 +70,55 @@ pub trait Migrate {
         migration: &'e Migration,
     ) -> BoxFuture<'e, Result<Duration, MigrateError>>;
 
+    // record that the migrations replaced by a baseline from `sqlx migrate squash` were applied,
+    // by updating the row of the last one to match the baseline without running it
+    fn squash<'e>(
+        &'e mut self,
+        _table_name: &'e str,
+        _migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async { Err(MigrateError::OperationNotSupported("squashing migrations")) })
+    }
+
+    // update the recorded description and checksum of an applied migration to match `migration`,
+    // e.g. after its file was reformatted; used by `sqlx migrate repair`
+    fn repair<'e>(
+        &'e mut self,
+        _table_name: &'e str,
+        _migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async { Err(MigrateError::OperationNotSupported("repairing migrations")) })
+    }
+
+    // record `migration` as applied without running it,
+    // e.g. for a database that predates its migrations; used by `sqlx migrate baseline`
+    fn mark_applied<'e>(
+        &'e mut self,
+        _table_name: &'e str,
+        _migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async {
+            Err(MigrateError::OperationNotSupported(
+                "marking migrations as applied",
+            ))
+        })
+    }
+
+    // resolve a migration left partially applied (see `dirty_version`) after it was fixed by hand:
+    // if `applied`, it is marked as successfully applied, otherwise its row is removed so it runs again
+    fn mark_dirty_resolved<'e>(
+        &'e mut self,
+        _table_name: &'e str,
+        _version: i64,
+        _applied: bool,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async {
+            Err(MigrateError::OperationNotSupported(
+                "resolving dirty migrations",
+            ))
+        })
+    }
+
     // run a revert SQL from migration in a DDL transaction
     // deletes the row in [_migrations] table with specified migration version on completion (success or failure)
     // returns the time taking to run the migration SQL
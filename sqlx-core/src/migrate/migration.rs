@@ -1,9 +1,11 @@
 use sha2::{Digest, Sha384};
 use std::borrow::Cow;
 
+use crate::connection::Connection;
+use crate::executor::Executor;
 use crate::sql_str::SqlStr;
 
-use super::MigrationType;
+use super::{MigrateError, MigrationType, RunMigration};
 
 #[derive(Debug, Clone)]
 pub struct Migration {
@@ -13,6 +15,16 @@ pub struct Migration {
     pub sql: SqlStr,
     pub checksum: Cow<'static, [u8]>,
     pub no_tx: bool,
+    /// The function to run instead of `sql`, for a migration written in Rust.
+    ///
+    /// Not public API; use [`Migration::rust()`] to create a migration written in Rust.
+    #[doc(hidden)]
+    pub code: Option<&'static dyn RunMigration>,
+    /// For a baseline migration generated by `sqlx migrate squash`, the version of the first
+    /// migration it replaces.
+    ///
+    /// Set by a `-- squashed-from: <VERSION>` comment on the first line of the migration.
+    pub squashed_from: Option<i64>,
 }
 
 impl Migration {
@@ -50,6 +62,51 @@ impl Migration {
             sql,
             checksum,
             no_tx,
+            code: None,
+            squashed_from: None,
+        }
+    }
+
+    /// A migration written in Rust, which runs `code` in a transaction.
+    ///
+    /// The checksum is taken of `checksum_version` rather than the code itself, so changing
+    /// `checksum_version` marks the migration as modified if it was already applied.
+    pub fn rust(
+        version: i64,
+        description: Cow<'static, str>,
+        checksum_version: &str,
+        code: &'static dyn RunMigration,
+    ) -> Self {
+        Migration {
+            code: Some(code),
+            ..Self::with_checksum(
+                version,
+                description,
+                MigrationType::Simple,
+                SqlStr::from_static(""),
+                checksum(checksum_version).into(),
+                false,
+            )
+        }
+    }
+
+    /// Run the SQL or code of this migration on `conn`.
+    #[doc(hidden)]
+    pub async fn execute<C>(&self, conn: &mut C) -> Result<(), MigrateError>
+    where
+        C: Connection + 'static,
+        for<'c> &'c mut C: Executor<'c, Database = C::Database>,
+    {
+        match self.code {
+            Some(code) => code
+                .run(conn)
+                .await
+                .map_err(|e| MigrateError::ExecuteCode(e, self.version)),
+            None => conn
+                .execute(self.sql.clone())
+                .await
+                .map(|_| ())
+                .map_err(|e| MigrateError::ExecuteMigration(e, self.version)),
         }
     }
 }
@@ -58,6 +115,19 @@ impl Migration {
 pub struct AppliedMigration {
     pub version: i64,
     pub checksum: Cow<'static, [u8]>,
+    /// The first version replaced by this migration, if it is a baseline of squashed migrations.
+    pub squashed_from: Option<i64>,
+    /// The position of this migration in the order the migrations were applied in,
+    /// or `None` if it was applied before the order was recorded.
+    pub installed_rank: Option<i64>,
+}
+
+impl AppliedMigration {
+    /// Returns `true` if `version` is one of the migrations squashed into this one.
+    pub fn squashes(&self, version: i64) -> bool {
+        self.squashed_from
+            .is_some_and(|from| from <= version && version < self.version)
+    }
 }
 
 pub fn checksum(sql: &str) -> Vec<u8> {
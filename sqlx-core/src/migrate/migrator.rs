@@ -1,5 +1,8 @@
 use crate::acquire::Acquire;
-use crate::migrate::{AppliedMigration, Migrate, MigrateError, Migration, MigrationSource};
+use crate::migrate::{
+    template, AppliedMigration, Migrate, MigrateError, Migration, MigrationSource,
+};
+use crate::sql_str::{AssertSqlSafe, SqlSafeStr};
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
@@ -24,10 +27,16 @@ pub struct Migrator {
     #[doc(hidden)]
     pub no_tx: bool,
     #[doc(hidden)]
+    pub strict_order: bool,
+    #[doc(hidden)]
     pub table_name: Cow<'static, str>,
 
     #[doc(hidden)]
     pub create_schemas: Cow<'static, [Cow<'static, str>]>,
+
+    /// The values of `${name}` placeholders, if they should be replaced.
+    #[doc(hidden)]
+    pub vars: Option<Cow<'static, [(Cow<'static, str>, Cow<'static, str>)]>>,
 }
 
 impl Migrator {
@@ -37,8 +46,10 @@ impl Migrator {
         ignore_missing: false,
         no_tx: false,
         locking: true,
+        strict_order: false,
         table_name: Cow::Borrowed("_sqlx_migrations"),
         create_schemas: Cow::Borrowed(&[]),
+        vars: None,
     };
 
     /// Creates a new instance with the given source.
@@ -95,6 +106,26 @@ impl Migrator {
         }
     }
 
+    /// Add a migration, such as one written in Rust with [`Migration::rust()`],
+    /// keeping the migrations ordered by version.
+    ///
+    /// Returns an error if there is already a migration with the same version
+    /// (in the same direction, for reversible migrations).
+    pub fn add_migration(&mut self, migration: Migration) -> Result<&mut Self, MigrateError> {
+        let is_down = migration.migration_type.is_down_migration();
+
+        if self.migrations.iter().any(|m| {
+            m.version == migration.version && m.migration_type.is_down_migration() == is_down
+        }) {
+            return Err(MigrateError::DuplicateVersion(migration.version));
+        }
+
+        let migrations = self.migrations.to_mut();
+        let index = migrations.partition_point(|m| m.version <= migration.version);
+        migrations.insert(index, migration);
+        Ok(self)
+    }
+
     /// Override the name of the table used to track executed migrations.
     ///
     /// May be schema-qualified and/or contain quotes. Defaults to `_sqlx_migrations`.
@@ -146,6 +177,73 @@ impl Migrator {
         self
     }
 
+    /// Specify whether pending migrations older than the latest applied migration are an error.
+    /// Defaults to `false`, in which case they are applied.
+    ///
+    /// Older migrations are pending when migrations are added on separate branches, which may be
+    /// merged and deployed in a different order than their versions. The order the migrations
+    /// were applied in is recorded in the migrations table, and shown by `sqlx migrate info`.
+    pub fn set_strict_order(&mut self, strict_order: bool) -> &mut Self {
+        self.strict_order = strict_order;
+        self
+    }
+
+    /// Specify whether `${name}` placeholders in migrations should be replaced with the value set
+    /// with [`Self::set_var()`], or otherwise the value of the environment variable `SQLX_VAR_name`.
+    /// Defaults to `false`, unless `[migrate.vars]` is set in `sqlx.toml`.
+    ///
+    /// `name` may contain letters, digits and underscores. Use `$${` for a literal `${`.
+    ///
+    /// Checksums are taken of migrations before the placeholders are replaced, so the same
+    /// migrations can be applied with different values to different databases.
+    pub fn set_templating(&mut self, templating: bool) -> &mut Self {
+        if !templating {
+            self.vars = None;
+        } else if self.vars.is_none() {
+            self.vars = Some(Cow::Borrowed(&[]));
+        }
+
+        self
+    }
+
+    /// Set the value of `${name}` placeholders in migrations, enabling [templating][Self::set_templating()].
+    ///
+    /// Takes precedence over the environment variable `SQLX_VAR_name`.
+    pub fn set_var(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        value: impl Into<Cow<'static, str>>,
+    ) -> &mut Self {
+        let name = name.into();
+        let vars = self.vars.get_or_insert(Cow::Borrowed(&[])).to_mut();
+
+        vars.retain(|(existing, _)| *existing != name);
+        vars.push((name, value.into()));
+
+        self
+    }
+
+    /// Returns `migration` with its placeholders replaced, if templating is enabled.
+    #[doc(hidden)]
+    pub fn expand<'m>(&self, migration: &'m Migration) -> Result<Cow<'m, Migration>, MigrateError> {
+        let Some(vars) = &self.vars else {
+            return Ok(Cow::Borrowed(migration));
+        };
+
+        let sql = template::expand_vars(migration.sql.as_str(), |name| {
+            vars.iter()
+                .find(|(var, _)| var == name)
+                .map(|(_, value)| value.to_string())
+                .or_else(|| std::env::var(format!("SQLX_VAR_{name}")).ok())
+        })
+        .map_err(|name| MigrateError::UndefinedVar(name, migration.version))?;
+
+        Ok(Cow::Owned(Migration {
+            sql: AssertSqlSafe(sql).into_sql_str(),
+            ..migration.clone()
+        }))
+    }
+
     /// Get an iterator over all known migrations.
     pub fn iter(&self) -> slice::Iter<'_, Migration> {
         self.migrations.iter()
@@ -216,8 +314,21 @@ impl Migrator {
             return Err(MigrateError::Dirty(version));
         }
 
-        let applied_migrations = conn.list_applied_migrations(&self.table_name).await?;
+        let mut applied_migrations = conn.list_applied_migrations(&self.table_name).await?;
+
+        // databases that applied the migrations squashed into a baseline don't run it again
+        let squashes = self.pending_squashes(&applied_migrations)?;
+
+        if !squashes.is_empty() {
+            for migration in squashes {
+                conn.squash(&self.table_name, migration).await?;
+            }
+
+            applied_migrations = conn.list_applied_migrations(&self.table_name).await?;
+        }
+
         validate_applied_migrations(&applied_migrations, self)?;
+        self.validate_order(&applied_migrations, target)?;
 
         let applied_migrations: HashMap<_, _> = applied_migrations
             .into_iter()
@@ -241,7 +352,8 @@ impl Migrator {
                     }
                 }
                 None => {
-                    conn.apply(&self.table_name, migration).await?;
+                    let migration = self.expand(migration)?;
+                    conn.apply(&self.table_name, &migration).await?;
                 }
             }
         }
@@ -255,6 +367,72 @@ impl Migrator {
         Ok(())
     }
 
+    /// Returns the baseline migrations from `sqlx migrate squash` that replace migrations
+    /// which were applied to the database, but have not been recorded with [`Migrate::squash()`].
+    ///
+    /// Returns an error if only some of the migrations replaced by a baseline were applied.
+    #[doc(hidden)]
+    pub fn pending_squashes(
+        &self,
+        applied_migrations: &[AppliedMigration],
+    ) -> Result<Vec<&Migration>, MigrateError> {
+        let mut squashes = Vec::new();
+
+        for migration in self.iter() {
+            let Some(squashed_from) = migration.squashed_from else {
+                continue;
+            };
+
+            match applied_migrations
+                .iter()
+                .find(|m| m.version == migration.version)
+            {
+                Some(applied) if applied.squashed_from.is_none() => squashes.push(migration),
+                Some(_) => (),
+                None => {
+                    if applied_migrations
+                        .iter()
+                        .any(|m| squashed_from <= m.version && m.version < migration.version)
+                    {
+                        return Err(MigrateError::PartiallySquashed(migration.version));
+                    }
+                }
+            }
+        }
+
+        Ok(squashes)
+    }
+
+    /// Returns an error if a pending migration, up to `target`, is older than the latest applied
+    /// migration, if [strict ordering][Self::set_strict_order()] is enabled.
+    #[doc(hidden)]
+    pub fn validate_order(
+        &self,
+        applied_migrations: &[AppliedMigration],
+        target: Option<i64>,
+    ) -> Result<(), MigrateError> {
+        if !self.strict_order {
+            return Ok(());
+        }
+
+        let Some(latest) = applied_migrations.iter().map(|m| m.version).max() else {
+            return Ok(());
+        };
+
+        let out_of_order = self
+            .iter()
+            .filter(|m| !m.migration_type.is_down_migration())
+            .filter(|m| target.is_none_or(|target| m.version <= target))
+            .find(|m| {
+                m.version < latest && !applied_migrations.iter().any(|a| a.version == m.version)
+            });
+
+        match out_of_order {
+            Some(migration) => Err(MigrateError::OutOfOrder(migration.version, latest)),
+            None => Ok(()),
+        }
+    }
+
     /// Run down migrations against the database until a specific version.
     ///
     /// # Examples
@@ -308,7 +486,8 @@ impl Migrator {
             .filter(|m| applied_migrations.contains_key(&m.version))
             .filter(|m| m.version > target)
         {
-            conn.revert(&self.table_name, migration).await?;
+            let migration = self.expand(migration)?;
+            conn.revert(&self.table_name, &migration).await?;
         }
 
         // unlock the migrator to allow other migrators to run
@@ -332,7 +511,11 @@ fn validate_applied_migrations(
     let migrations: HashSet<_> = migrator.iter().map(|m| m.version).collect();
 
     for applied_migration in applied_migrations {
-        if !migrations.contains(&applied_migration.version) {
+        if !migrations.contains(&applied_migration.version)
+            && !applied_migrations
+                .iter()
+                .any(|m| m.squashes(applied_migration.version))
+        {
             return Err(MigrateError::VersionMissing(applied_migration.version));
         }
     }
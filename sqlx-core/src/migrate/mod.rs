@@ -4,13 +4,16 @@ mod migrate;
 mod migration;
 mod migration_type;
 mod migrator;
+mod rust_migration;
 mod source;
+mod template;
 
 pub use error::MigrateError;
 pub use migrate::{Migrate, MigrateDatabase};
 pub use migration::{AppliedMigration, Migration};
 pub use migration_type::MigrationType;
 pub use migrator::Migrator;
+pub use rust_migration::{MigrationFn, RunMigration, RustMigration};
 pub use source::{MigrationSource, ResolveConfig, ResolveWith};
 
 #[doc(hidden)]
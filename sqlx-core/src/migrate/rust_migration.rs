@@ -0,0 +1,79 @@
+use std::any::Any;
+use std::fmt::{self, Debug};
+
+use futures_core::future::BoxFuture;
+
+use crate::connection::Connection;
+use crate::database::Database;
+use crate::error::BoxDynError;
+use crate::transaction::Transaction;
+
+/// The signature of a migration written in Rust for the database `DB`.
+pub type MigrationFn<DB> =
+    for<'c, 't> fn(&'c mut Transaction<'t, DB>) -> BoxFuture<'c, Result<(), BoxDynError>>;
+
+/// A migration written in Rust, run in a transaction on a connection to `DB`.
+///
+/// Usually declared with `migrate!()`, but may also be added to a [`Migrator`][super::Migrator]
+/// with [`Migration::rust()`][super::Migration::rust]:
+///
+/// ```rust,no_run
+/// use sqlx::migrate::{Migration, Migrator, RustMigration};
+/// use sqlx::{Postgres, Transaction};
+///
+/// async fn backfill_slugs(tx: &mut Transaction<'_, Postgres>) -> Result<(), sqlx::error::BoxDynError> {
+///     sqlx::query("UPDATE posts SET slug = lower(replace(title, ' ', '-'))")
+///         .execute(&mut **tx)
+///         .await?;
+///
+///     Ok(())
+/// }
+///
+/// static BACKFILL_SLUGS: RustMigration<Postgres> = RustMigration(|tx| Box::pin(backfill_slugs(tx)));
+///
+/// # async fn example() -> Result<(), sqlx::migrate::MigrateError> {
+/// let mut migrator = Migrator::new(std::path::Path::new("./migrations")).await?;
+/// migrator.add_migration(Migration::rust(3, "backfill slugs".into(), "v1", &BACKFILL_SLUGS))?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// With the `Any` driver, the migration must be written for the database the
+/// `AnyConnection` is connected to, e.g. `RustMigration<Postgres>`.
+pub struct RustMigration<DB: Database>(pub MigrationFn<DB>);
+
+/// A migration function with its database type erased, so that it can be held by a
+/// [`Migration`][super::Migration].
+///
+/// Implemented by [`RustMigration`].
+pub trait RunMigration: Debug + Send + Sync + 'static {
+    /// Run the migration on `conn` in a transaction (or a savepoint, if `conn` is already in one).
+    ///
+    /// Returns an error if `conn` is not a connection to the database the migration is for.
+    fn run<'c>(&'c self, conn: &'c mut (dyn Any + Send)) -> BoxFuture<'c, Result<(), BoxDynError>>;
+}
+
+impl<DB: Database> RunMigration for RustMigration<DB> {
+    fn run<'c>(&'c self, conn: &'c mut (dyn Any + Send)) -> BoxFuture<'c, Result<(), BoxDynError>> {
+        Box::pin(async move {
+            let conn = conn.downcast_mut::<DB::Connection>().ok_or_else(|| {
+                format!(
+                    "migration is written for {}, but is being run on a different database",
+                    DB::NAME
+                )
+            })?;
+
+            let mut tx = conn.begin().await?;
+            (self.0)(&mut tx).await?;
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+}
+
+impl<DB: Database> Debug for RustMigration<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RustMigration").field(&DB::NAME).finish()
+    }
+}
@@ -233,19 +233,34 @@ pub fn resolve_blocking_with_config(
         // opt-out of migration transaction
         let no_tx = sql.starts_with("-- no-transaction");
 
+        // baseline generated by `sqlx migrate squash`
+        let squashed_from = sql
+            .lines()
+            .next()
+            .and_then(|line| line.strip_prefix("-- squashed-from:"))
+            .map(|from| {
+                from.trim().parse::<i64>().map_err(|_e| ResolveError {
+                    message: format!(
+                        "error parsing migration {file_name:?}; expected integer version after `-- squashed-from:`"
+                    ),
+                    source: None,
+                })
+            })
+            .transpose()?;
+
         let checksum = checksum_with(&sql, &config.ignored_chars);
 
-        migrations.push((
-            Migration::with_checksum(
-                version,
-                Cow::Owned(description),
-                migration_type,
-                AssertSqlSafe(sql).into_sql_str(),
-                checksum.into(),
-                no_tx,
-            ),
-            entry_path,
-        ));
+        let mut migration = Migration::with_checksum(
+            version,
+            Cow::Owned(description),
+            migration_type,
+            AssertSqlSafe(sql).into_sql_str(),
+            checksum.into(),
+            no_tx,
+        );
+        migration.squashed_from = squashed_from;
+
+        migrations.push((migration, entry_path));
     }
 
     // Ensure that we are sorted by version in ascending order.
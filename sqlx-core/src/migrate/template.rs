@@ -0,0 +1,54 @@
+/// Replace each `${name}` placeholder in `sql` with `var(name)`.
+///
+/// `name` must be an identifier (letters, digits and underscores, not starting with a digit);
+/// anything else after `${` is left as-is. `$${` is replaced with a literal `${`.
+///
+/// Returns the name of the first placeholder for which `var` returns `None`.
+pub(crate) fn expand_vars(
+    sql: &str,
+    mut var: impl FnMut(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut expanded = String::with_capacity(sql.len());
+    let mut rest = sql;
+
+    while let Some(start) = rest.find("${") {
+        // `$${` escapes the placeholder
+        if rest[..start].ends_with('$') {
+            expanded.push_str(&rest[..start - 1]);
+            expanded.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+
+        expanded.push_str(&rest[..start]);
+
+        let after = &rest[start + 2..];
+
+        let name = after
+            .split_once('}')
+            .map(|(name, _)| name)
+            .filter(|name| is_identifier(name));
+
+        let Some(name) = name else {
+            expanded.push_str("${");
+            rest = after;
+            continue;
+        };
+
+        expanded.push_str(&var(name).ok_or_else(|| name.to_owned())?);
+        rest = &after[name.len() + 1..];
+    }
+
+    expanded.push_str(rest);
+
+    Ok(expanded)
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
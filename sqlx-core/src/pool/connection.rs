@@ -204,7 +204,7 @@ impl<DB: Database> Drop for PoolConnection<DB> {
         }
 
         // We still need to spawn a task to maintain `min_connections`.
-        if self.live.is_some() || self.pool.options.min_connections > 0 {
+        if self.live.is_some() || self.pool.min_connections() > 0 {
             crate::rt::spawn(self.return_to_pool());
         }
     }
@@ -286,6 +286,13 @@ impl<DB: Database> Floating<DB, Live<DB>> {
             return false;
         }
 
+        // If `max_connections` was lowered while the connection was checked out,
+        // close it to bring the pool back within the limit.
+        if self.guard.pool.size() > self.guard.pool.max_connections() {
+            self.close().await;
+            return false;
+        }
+
         if let Some(test) = &self.guard.pool.options.after_release {
             let meta = self.metadata();
             match (test)(&mut self.inner.raw, meta).await {
@@ -311,7 +318,14 @@ impl<DB: Database> Floating<DB, Live<DB>> {
         // returned to the pool; also of course, if it was dropped due to an error
         // this is simply a band-aid as SQLx-next connections should be able
         // to recover from cancellations
-        if let Err(error) = self.raw.ping().await {
+        //
+        // Resetting the connection requires a round-trip anyway, so it doubles as the test.
+        let res = match self.guard.pool.options.reset_on_release {
+            Some(mode) => self.raw.reset(mode).await,
+            None => self.raw.ping().await,
+        };
+
+        if let Err(error) = res {
             tracing::warn!(
                 %error,
                 "error occurred while testing the connection on-release",
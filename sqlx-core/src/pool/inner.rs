@@ -4,7 +4,7 @@ use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
 use crate::pool::{deadline_as_timeout, CloseEvent, Pool, PoolOptions};
-use crossbeam_queue::ArrayQueue;
+use crossbeam_queue::SegQueue;
 
 use crate::sync::{AsyncSemaphore, AsyncSemaphoreReleaser};
 
@@ -12,7 +12,7 @@ use std::cmp;
 use std::future::{self, Future};
 use std::pin::pin;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 use std::task::Poll;
 
 use crate::logger::private_level_filter_to_trace_level;
@@ -24,9 +24,17 @@ use tracing::Level;
 
 pub(crate) struct PoolInner<DB: Database> {
     pub(super) connect_options: RwLock<Arc<<DB::Connection as Connection>::Options>>,
-    pub(super) idle_conns: ArrayQueue<Idle<DB>>,
+    pub(super) idle_conns: SegQueue<Idle<DB>>,
     pub(super) semaphore: AsyncSemaphore,
     pub(super) size: AtomicU32,
+    max_connections: AtomicU32,
+    min_connections: AtomicU32,
+    /// Permits that still need to be removed from `semaphore` after `max_connections` was lowered.
+    ///
+    /// Paid down as connections are returned or closed.
+    permit_debt: AtomicU32,
+    /// Serializes changes to `max_connections` and `permit_debt` against each other and `close()`.
+    resize_lock: Mutex<()>,
     pub(super) num_idle: AtomicUsize,
     is_closed: AtomicBool,
     pub(super) on_closed: event_listener::Event,
@@ -40,22 +48,24 @@ impl<DB: Database> PoolInner<DB> {
         options: PoolOptions<DB>,
         connect_options: <DB::Connection as Connection>::Options,
     ) -> Arc<Self> {
-        let capacity = options.max_connections as usize;
-
         let semaphore_capacity = if let Some(parent) = &options.parent_pool {
             assert!(options.max_connections <= parent.options().max_connections);
             assert_eq!(options.fair, parent.options().fair);
             // The child pool must steal permits from the parent
             0
         } else {
-            capacity
+            options.max_connections as usize
         };
 
         let pool = Self {
             connect_options: RwLock::new(Arc::new(connect_options)),
-            idle_conns: ArrayQueue::new(capacity),
+            idle_conns: SegQueue::new(),
             semaphore: AsyncSemaphore::new(options.fair, semaphore_capacity),
             size: AtomicU32::new(0),
+            max_connections: AtomicU32::new(options.max_connections),
+            min_connections: AtomicU32::new(options.min_connections),
+            permit_debt: AtomicU32::new(0),
+            resize_lock: Mutex::new(()),
             num_idle: AtomicUsize::new(0),
             is_closed: AtomicBool::new(false),
             on_closed: event_listener::Event::new(),
@@ -75,6 +85,131 @@ impl<DB: Database> PoolInner<DB> {
         self.size.load(Ordering::Acquire)
     }
 
+    pub(super) fn max_connections(&self) -> u32 {
+        self.max_connections.load(Ordering::Acquire)
+    }
+
+    pub(super) fn min_connections(&self) -> u32 {
+        self.min_connections.load(Ordering::Acquire)
+    }
+
+    /// Change `max_connections`, adding or removing semaphore permits as necessary.
+    ///
+    /// Permits that are currently held by checked-out connections can't be removed right away;
+    /// they are recorded in `permit_debt` and paid down as those connections are returned.
+    ///
+    /// Idle connections in excess of the new maximum are removed from the pool right away;
+    /// the returned future closes them gracefully, and dropping it closes them hard.
+    pub(super) fn set_max_connections(self: &Arc<Self>, max: u32) -> impl Future<Output = ()> + '_ {
+        // A child pool can't have more connections than its parent.
+        let max = self
+            .parent()
+            .map_or(max, |parent| cmp::min(max, parent.max_connections()));
+
+        let surplus = {
+            let _lock = self
+                .resize_lock
+                .lock()
+                .expect("resize-lock holder panicked");
+
+            let prev = self.max_connections.swap(max, Ordering::AcqRel);
+
+            // Child pools don't own any permits; they steal them from the parent as needed,
+            // so the new limit is enforced purely by `try_increment_size()`.
+            if self.parent().is_none() && !self.is_closed() {
+                if max > prev {
+                    let mut grow = max - prev;
+
+                    // Forgive any outstanding debt before adding new permits.
+                    if let Ok(debt) =
+                        self.permit_debt
+                            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |debt| {
+                                Some(debt.saturating_sub(grow))
+                            })
+                    {
+                        grow -= cmp::min(debt, grow);
+                    }
+
+                    self.semaphore.release(grow as usize);
+                } else if prev > max {
+                    let mut shrink = prev - max;
+
+                    // Take whatever permits are available right now...
+                    while shrink > 0 {
+                        let Some(permit) = self.semaphore.try_acquire(1) else {
+                            break;
+                        };
+
+                        permit.disarm();
+                        shrink -= 1;
+                    }
+
+                    // ...and collect the rest as checked-out connections are returned.
+                    self.permit_debt.fetch_add(shrink, Ordering::AcqRel);
+                }
+            }
+
+            self.take_surplus_idle()
+        };
+
+        async move {
+            for idle in surplus {
+                let _ = idle.live.raw.close().await;
+            }
+        }
+    }
+
+    /// Remove idle connections from the pool while its size exceeds `max_connections`.
+    ///
+    /// Idle connections don't hold a semaphore permit, so none is released for them.
+    /// Any remaining surplus connections are checked out, and will be closed when they're returned.
+    fn take_surplus_idle(&self) -> Vec<Idle<DB>> {
+        let mut surplus = Vec::new();
+
+        while self.size() > self.max_connections() {
+            let Some(idle) = self.idle_conns.pop() else {
+                break;
+            };
+
+            self.num_idle.fetch_sub(1, Ordering::AcqRel);
+            self.size.fetch_sub(1, Ordering::AcqRel);
+
+            surplus.push(idle);
+        }
+
+        surplus
+    }
+
+    pub(super) fn set_min_connections(self: &Arc<Self>, min: u32) -> impl Future<Output = ()> + '_ {
+        self.min_connections.store(min, Ordering::Release);
+
+        self.min_connections_maintenance(None)
+    }
+
+    /// Consume a permit that would otherwise be released to `semaphore`
+    /// if we're still shrinking from a previous call to `set_max_connections()`.
+    ///
+    /// Returns `true` if the permit should be forgotten.
+    fn try_pay_permit_debt(&self) -> bool {
+        // Fast path which avoids a read-modify-write in the common case.
+        if self.permit_debt.load(Ordering::Acquire) == 0 {
+            return false;
+        }
+
+        self.permit_debt
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |debt| {
+                debt.checked_sub(1)
+            })
+            .is_ok()
+    }
+
+    /// Release a permit held by a connection, unless it's owed to `permit_debt`.
+    fn release_connection_permit(&self) {
+        if !self.try_pay_permit_debt() {
+            self.semaphore.release(1);
+        }
+    }
+
     pub(super) fn num_idle(&self) -> usize {
         // We don't use `self.idle_conns.len()` as it waits for the internal
         // head and tail pointers to stop changing for a moment before calculating the length,
@@ -96,17 +231,23 @@ impl<DB: Database> PoolInner<DB> {
     pub(super) fn close(self: &Arc<Self>) -> impl Future<Output = ()> + '_ {
         self.mark_closed();
 
-        async move {
-            // For child pools, we need to acquire permits we actually have rather than
-            // max_connections
-            let permits_to_acquire = if self.options.parent_pool.is_some() {
-                // Child pools start with 0 permits, so we acquire based on current size
-                self.size()
-            } else {
-                // Parent pools can acquire all max_connections permits
-                self.options.max_connections
-            };
+        // For child pools, we need to acquire permits we actually have rather than
+        // max_connections
+        let permits_to_acquire = if self.options.parent_pool.is_some() {
+            // Child pools start with 0 permits, so we acquire based on current size
+            self.size()
+        } else {
+            let _lock = self
+                .resize_lock
+                .lock()
+                .expect("resize-lock holder panicked");
+
+            // Any permits still owed from shrinking the pool will now be released normally,
+            // so we need to wait for those as well.
+            self.max_connections() + self.permit_debt.swap(0, Ordering::AcqRel)
+        };
 
+        async move {
             let _permits = self.semaphore.acquire(permits_to_acquire).await;
 
             while let Some(idle) = self.idle_conns.pop() {
@@ -133,7 +274,7 @@ impl<DB: Database> PoolInner<DB> {
             .parent()
             // If we're already at the max size, we shouldn't try to steal from the parent.
             // This is just going to cause unnecessary churn in `acquire()`.
-            .filter(|_| self.size() < self.options.max_connections);
+            .filter(|_| self.size() < self.max_connections());
 
         let mut acquire_self = pin!(self.semaphore.acquire(1).fuse());
         let mut close_event = pin!(self.close_event());
@@ -206,9 +347,7 @@ impl<DB: Database> PoolInner<DB> {
 
         let Floating { inner: idle, guard } = floating.into_idle();
 
-        if self.idle_conns.push(idle).is_err() {
-            panic!("BUG: connection queue overflow in release()");
-        }
+        self.idle_conns.push(idle);
 
         // NOTE: we need to make sure we drop the permit *after* we push to the idle queue
         // don't decrease the size
@@ -232,7 +371,7 @@ impl<DB: Database> PoolInner<DB> {
                 }
 
                 size.checked_add(1)
-                    .filter(|size| size <= &self.options.max_connections)
+                    .filter(|size| size <= &self.max_connections())
             });
 
         match result {
@@ -272,19 +411,25 @@ impl<DB: Database> PoolInner<DB> {
                             // we get the `DecrementSizeGuard` back to open a new one
                             Err(guard) => guard,
                         },
-                        Err(permit) => if let Ok(guard) = self.try_increment_size(permit) {
+                        Err(permit) => match self.try_increment_size(permit) {
                             // we can open a new connection
-                            guard
-                        } else {
-                            // This can happen for a child pool that's at its connection limit,
-                            // or if the pool was closed between `acquire_permit()` and
-                            // `try_increment_size()`.
-                            tracing::debug!("woke but was unable to acquire idle connection or open new one; retrying");
-                            // If so, we're likely in the current-thread runtime if it's Tokio,
-                            // and so we should yield to let any spawned return_to_pool() tasks
-                            // execute.
-                            crate::rt::yield_now().await;
-                            continue;
+                            Ok(guard) => guard,
+                            // The pool is being shrunk; keep the permit so we don't spin on it.
+                            Err(permit) if self.try_pay_permit_debt() => {
+                                permit.disarm();
+                                continue;
+                            }
+                            Err(_permit) => {
+                                // This can happen for a child pool that's at its connection limit,
+                                // or if the pool was closed between `acquire_permit()` and
+                                // `try_increment_size()`.
+                                tracing::debug!("woke but was unable to acquire idle connection or open new one; retrying");
+                                // If so, we're likely in the current-thread runtime if it's Tokio,
+                                // and so we should yield to let any spawned return_to_pool() tasks
+                                // execute.
+                                crate::rt::yield_now().await;
+                                continue;
+                            }
                         }
                     };
 
@@ -398,7 +543,7 @@ impl<DB: Database> PoolInner<DB> {
 
     /// Try to maintain `min_connections`, returning any errors (including `PoolTimedOut`).
     pub async fn try_min_connections(self: &Arc<Self>, deadline: Instant) -> Result<(), Error> {
-        while self.size() < self.options.min_connections {
+        while self.size() < self.min_connections() {
             // Don't wait for a semaphore permit.
             //
             // If no extra permits are available then we shouldn't be trying to spin up
@@ -515,7 +660,7 @@ fn spawn_maintenance_tasks<DB: Database>(pool: &Arc<PoolInner<DB>>) {
         (Some(a), Some(b)) => cmp::min(a, b),
 
         (None, None) => {
-            if pool.options.min_connections > 0 {
+            if pool.min_connections() > 0 {
                 crate::rt::spawn(async move {
                     if let Some(pool) = pool_weak.upgrade() {
                         pool.min_connections_maintenance(None).await;
@@ -547,7 +692,10 @@ fn spawn_maintenance_tasks<DB: Database>(pool: &Arc<PoolInner<DB>>) {
                     // the queue in the meantime - that's fine, there is no harm in checking more
                     for _ in 0..pool.num_idle() {
                         if let Some(conn) = pool.try_acquire() {
-                            if is_beyond_idle_timeout(&conn, &pool.options)
+                            if pool.size() > pool.max_connections() {
+                                // The pool was shrunk while this connection was checked out.
+                                let _ = conn.close().await;
+                            } else if is_beyond_idle_timeout(&conn, &pool.options)
                                 || is_beyond_max_lifetime(&conn, &pool.options)
                             {
                                 let _ = conn.close().await;
@@ -602,7 +750,7 @@ impl<DB: Database> DecrementSizeGuard<DB> {
     ///
     /// If the permit was stolen from the pool's parent, it will be returned to the child's semaphore.
     fn release_permit(self) {
-        self.pool.semaphore.release(1);
+        self.pool.release_connection_permit();
         self.cancel();
     }
 
@@ -617,7 +765,7 @@ impl<DB: Database> Drop for DecrementSizeGuard<DB> {
             self.pool.size.fetch_sub(1, Ordering::AcqRel);
 
             // and here we release the permit we got on construction
-            self.pool.semaphore.release(1);
+            self.pool.release_connection_permit();
         }
     }
 }
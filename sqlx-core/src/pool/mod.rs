@@ -68,14 +68,14 @@ use futures_util::FutureExt;
 use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
-use crate::sql_str::SqlSafeStr;
-use crate::transaction::Transaction;
+use crate::transaction::{IntoBeginStatement, Transaction};
 
 pub use self::connection::PoolConnection;
 use self::inner::PoolInner;
 #[doc(hidden)]
 pub use self::maybe::MaybePoolConnection;
 pub use self::options::{PoolConnectionMetadata, PoolOptions};
+pub use crate::connection::ResetMode;
 
 #[macro_use]
 mod executor;
@@ -388,30 +388,34 @@ impl<DB: Database> Pool<DB> {
     }
 
     /// Retrieves a connection and immediately begins a new transaction using `statement`.
+    ///
+    /// `statement` may be a custom `BEGIN` statement or [`TransactionOptions`].
+    ///
+    /// [`TransactionOptions`]: crate::transaction::TransactionOptions
     pub async fn begin_with(
         &self,
-        statement: impl SqlSafeStr,
+        statement: impl IntoBeginStatement<DB>,
     ) -> Result<Transaction<'static, DB>, Error> {
-        Transaction::begin(
-            MaybePoolConnection::PoolConnection(self.acquire().await?),
-            Some(statement.into_sql_str()),
-        )
-        .await
+        let conn = self.acquire().await?;
+        let statement = statement.into_begin_statement(&conn);
+
+        Transaction::begin(MaybePoolConnection::PoolConnection(conn), Some(statement)).await
     }
 
     /// Attempts to retrieve a connection and, if successful, immediately begins a new
     /// transaction using `statement`.
     pub async fn try_begin_with(
         &self,
-        statement: impl SqlSafeStr,
+        statement: impl IntoBeginStatement<DB>,
     ) -> Result<Option<Transaction<'static, DB>>, Error> {
         match self.try_acquire() {
-            Some(conn) => Transaction::begin(
-                MaybePoolConnection::PoolConnection(conn),
-                Some(statement.into_sql_str()),
-            )
-            .await
-            .map(Some),
+            Some(conn) => {
+                let statement = statement.into_begin_statement(&conn);
+
+                Transaction::begin(MaybePoolConnection::PoolConnection(conn), Some(statement))
+                    .await
+                    .map(Some)
+            }
 
             None => Ok(None),
         }
@@ -563,6 +567,59 @@ impl<DB: Database> Pool<DB> {
         *guard = Arc::new(connect_options);
     }
 
+    /// Returns the maximum number of connections the pool is currently allowed to maintain.
+    ///
+    /// This starts as [`PoolOptions::max_connections`] but may be changed with
+    /// [`.set_max_connections()`][Self::set_max_connections].
+    pub fn max_connections(&self) -> u32 {
+        self.0.max_connections()
+    }
+
+    /// Returns the minimum number of connections the pool currently tries to maintain.
+    ///
+    /// This starts as [`PoolOptions::min_connections`] but may be changed with
+    /// [`.set_min_connections()`][Self::set_min_connections].
+    pub fn min_connections(&self) -> u32 {
+        self.0.min_connections()
+    }
+
+    /// Change the maximum number of connections the pool may maintain.
+    ///
+    /// Raising the limit takes effect immediately, waking any tasks waiting in
+    /// [`.acquire()`][Self::acquire].
+    ///
+    /// Lowering the limit also takes effect immediately for new connections. Idle connections in
+    /// excess of the new limit are removed from the pool right away, and checked-out connections
+    /// are closed when they are next returned to the pool. Until then, [`.size()`][Self::size]
+    /// may temporarily exceed the new limit.
+    ///
+    /// The returned `Future` closes the removed idle connections gracefully. Waiting on it is
+    /// optional; if it is dropped instead, they are closed without notifying the server.
+    ///
+    /// On a pool created with [`PoolOptions::parent`], the limit is clamped to not exceed
+    /// the parent's [`.max_connections()`][Self::max_connections].
+    ///
+    /// [`.options()`][Self::options] continues to report the value the pool was created with.
+    /// Use [`.max_connections()`][Self::max_connections] to get the current value.
+    pub fn set_max_connections(&self, max: u32) -> impl Future<Output = ()> + '_ {
+        self.0.set_max_connections(max)
+    }
+
+    /// Change the minimum number of connections the pool tries to maintain.
+    ///
+    /// The returned `Future` opens connections until the new minimum is met, and may be
+    /// `.await`ed to ensure that it is. Otherwise, the minimum is maintained in the background
+    /// as connections are closed and returned, same as [`PoolOptions::min_connections`].
+    ///
+    /// Lowering the minimum does not close any connections, but allows idle connections to be
+    /// reaped by [`PoolOptions::idle_timeout`] and [`PoolOptions::max_lifetime`].
+    ///
+    /// Like [`PoolOptions::min_connections`], this is clamped internally to not exceed
+    /// [`.max_connections()`][Self::max_connections].
+    pub fn set_min_connections(&self, min: u32) -> impl Future<Output = ()> + '_ {
+        self.0.set_min_connections(min)
+    }
+
     /// Get the options for this pool
     pub fn options(&self) -> &PoolOptions<DB> {
         &self.0.options
@@ -1,4 +1,4 @@
-use crate::connection::Connection;
+use crate::connection::{Connection, ResetMode};
 use crate::database::Database;
 use crate::error::Error;
 use crate::pool::inner::PoolInner;
@@ -83,6 +83,7 @@ pub struct PoolOptions<DB: Database> {
     pub(crate) max_lifetime: Option<Duration>,
     pub(crate) idle_timeout: Option<Duration>,
     pub(crate) fair: bool,
+    pub(crate) reset_on_release: Option<ResetMode>,
 
     pub(crate) parent_pool: Option<Pool<DB>>,
 }
@@ -106,6 +107,7 @@ impl<DB: Database> Clone for PoolOptions<DB> {
             max_lifetime: self.max_lifetime,
             idle_timeout: self.idle_timeout,
             fair: self.fair,
+            reset_on_release: self.reset_on_release,
             parent_pool: self.parent_pool.clone(),
         }
     }
@@ -161,6 +163,7 @@ impl<DB: Database> PoolOptions<DB> {
             idle_timeout: Some(Duration::from_secs(10 * 60)),
             max_lifetime: Some(Duration::from_secs(30 * 60)),
             fair: true,
+            reset_on_release: None,
             parent_pool: None,
         }
     }
@@ -500,6 +503,31 @@ impl<DB: Database> PoolOptions<DB> {
         self
     }
 
+    /// Reset the session state of connections before they are returned to the pool,
+    /// so that session variables, temporary tables, advisory locks and the like
+    /// don't leak to the next task that acquires the connection.
+    ///
+    /// See [`ResetMode`] for what is reset for each database.
+    ///
+    /// The reset is performed after [`after_release`][Self::after_release], if set,
+    /// and takes the place of the liveness check normally done on release.
+    /// If it fails, the connection is closed instead of being returned to the pool.
+    ///
+    /// Note that this also resets anything configured in [`after_connect`][Self::after_connect]
+    /// with session-level statements like `SET`. Prefer setting such parameters in the
+    /// connect options for your database, where possible.
+    ///
+    /// Defaults to `None` (no reset).
+    pub fn reset_on_release(mut self, mode: impl Into<Option<ResetMode>>) -> Self {
+        self.reset_on_release = mode.into();
+        self
+    }
+
+    /// Get the mode used to reset connections on release, if any.
+    pub fn get_reset_on_release(&self) -> Option<ResetMode> {
+        self.reset_on_release
+    }
+
     /// Set the parent `Pool` from which the new pool will inherit its semaphore.
     ///
     /// This is currently an internal-only API.
@@ -591,6 +619,7 @@ impl<DB: Database> Debug for PoolOptions<DB> {
             .field("max_lifetime", &self.max_lifetime)
             .field("idle_timeout", &self.idle_timeout)
             .field("test_before_acquire", &self.test_before_acquire)
+            .field("reset_on_release", &self.reset_on_release)
             .finish()
     }
 }
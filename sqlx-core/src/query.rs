@@ -1,3 +1,4 @@
+use std::time::Duration;
 use std::{future, marker::PhantomData};
 
 use either::Either;
@@ -20,6 +21,7 @@ pub struct Query<'q, DB: Database, A> {
     pub(crate) arguments: Option<Result<A, BoxDynError>>,
     pub(crate) database: PhantomData<DB>,
     pub(crate) persistent: bool,
+    pub(crate) timeout: Option<Duration>,
 }
 
 /// A single SQL query that will map its results to an owned Rust type.
@@ -35,7 +37,7 @@ pub struct Query<'q, DB: Database, A> {
 /// `query!()` et al.
 #[must_use = "query must be executed to affect database"]
 pub struct Map<'q, DB: Database, F, A> {
-    inner: Query<'q, DB, A>,
+    pub(crate) inner: Query<'q, DB, A>,
     mapper: F,
 }
 
@@ -71,6 +73,11 @@ where
     fn persistent(&self) -> bool {
         self.persistent
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
 }
 
 impl<DB: Database> Query<'_, DB, <DB as Database>::Arguments> {
@@ -121,6 +128,30 @@ impl<DB: Database> Query<'_, DB, <DB as Database>::Arguments> {
     }
 }
 
+impl<DB: Database, A> Query<'_, DB, A> {
+    /// Cancel the query if it has not completed after `timeout`, and return
+    /// [`Error::QueryTimedOut`].
+    ///
+    /// The time is measured from when the query starts executing until all of its results have
+    /// been received, so it includes time spent waiting on a slow consumer of [`fetch()`].
+    /// It does not include the time taken to acquire a connection from a pool.
+    ///
+    /// Each driver cancels the query in the most appropriate way:
+    ///
+    /// * Postgres sends a cancel request over a new connection to the server.
+    /// * MySQL runs `KILL QUERY` over a new connection to the server.
+    /// * SQLite calls `sqlite3_interrupt()`.
+    ///
+    /// The connection waits for the query to be cancelled and remains usable afterwards.
+    /// If the query completes before it can be cancelled, its results are returned as normal.
+    ///
+    /// [`fetch()`]: Self::fetch
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
 impl<DB, A> Query<'_, DB, A>
 where
     DB: Database + HasStatementCache,
@@ -322,6 +353,21 @@ where
     fn persistent(&self) -> bool {
         self.inner.arguments.is_some()
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout
+    }
+}
+
+impl<DB: Database, F, A> Map<'_, DB, F, A> {
+    /// Cancel the query if it has not completed after `timeout`.
+    ///
+    /// See [`Query::timeout()`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.timeout(timeout);
+        self
+    }
 }
 
 impl<'q, DB, F, O, A> Map<'q, DB, F, A>
@@ -509,6 +555,7 @@ where
         arguments: Some(Ok(Default::default())),
         statement: Either::Right(statement),
         persistent: true,
+        timeout: None,
     }
 }
 
@@ -523,6 +570,7 @@ where
         arguments: Some(Ok(arguments)),
         statement: Either::Right(statement),
         persistent: true,
+        timeout: None,
     }
 }
 
@@ -659,6 +707,7 @@ where
         arguments: Some(Ok(Default::default())),
         statement: Either::Left(sql.into_sql_str()),
         persistent: true,
+        timeout: None,
     }
 }
 
@@ -687,5 +736,6 @@ where
         arguments: Some(arguments),
         statement: Either::Left(sql.into_sql_str()),
         persistent: true,
+        timeout: None,
     }
 }
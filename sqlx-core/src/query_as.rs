@@ -1,4 +1,5 @@
 use std::marker::PhantomData;
+use std::time::Duration;
 
 use either::Either;
 use futures_core::stream::BoxStream;
@@ -46,6 +47,11 @@ where
     fn persistent(&self) -> bool {
         self.inner.persistent()
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        Execute::timeout(&self.inner)
+    }
 }
 
 impl<'q, DB: Database, O> QueryAs<'q, DB, O, <DB as Database>::Arguments> {
@@ -58,6 +64,16 @@ impl<'q, DB: Database, O> QueryAs<'q, DB, O, <DB as Database>::Arguments> {
     }
 }
 
+impl<DB: Database, O, A> QueryAs<'_, DB, O, A> {
+    /// Cancel the query if it has not completed after `timeout`.
+    ///
+    /// See [`Query::timeout()`](crate::query::Query::timeout).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.timeout(timeout);
+        self
+    }
+}
+
 impl<DB, O, A> QueryAs<'_, DB, O, A>
 where
     DB: Database + HasStatementCache,
@@ -1,6 +1,7 @@
 use either::Either;
 use futures_core::stream::BoxStream;
 use futures_util::{StreamExt, TryFutureExt, TryStreamExt};
+use std::time::Duration;
 
 use crate::arguments::IntoArguments;
 use crate::database::{Database, HasStatementCache};
@@ -43,6 +44,11 @@ where
     fn persistent(&self) -> bool {
         Execute::persistent(&self.inner)
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        Execute::timeout(&self.inner)
+    }
 }
 
 impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as Database>::Arguments> {
@@ -55,6 +61,16 @@ impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as Database>::Arguments> {
     }
 }
 
+impl<DB: Database, O, A> QueryScalar<'_, DB, O, A> {
+    /// Cancel the query if it has not completed after `timeout`.
+    ///
+    /// See [`Query::timeout()`](crate::query::Query::timeout).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.timeout(timeout);
+        self
+    }
+}
+
 impl<DB, O, A> QueryScalar<'_, DB, O, A>
 where
     DB: Database + HasStatementCache,
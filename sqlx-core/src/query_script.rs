@@ -0,0 +1,161 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::acquire::Acquire;
+use crate::arguments::IntoArguments;
+use crate::database::Database;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::query::{Map, Query};
+use crate::transaction::Transaction;
+
+/// The statements of a script checked by `query_file_script!()`, run in order on one connection.
+///
+/// Only the result of the last statement is returned; the statements before it are executed
+/// and their results discarded.
+#[must_use = "query must be executed to affect database"]
+pub struct Script<'q, DB: Database, F, A> {
+    setup: Vec<Query<'q, DB, A>>,
+    query: Map<'q, DB, F, A>,
+    transaction: bool,
+}
+
+/// Run the `setup` statements, and then `query`. Used by `query_file_script!()`.
+#[doc(hidden)]
+pub fn query_script<'q, DB, F, A>(
+    setup: Vec<Query<'q, DB, A>>,
+    query: Map<'q, DB, F, A>,
+) -> Script<'q, DB, F, A>
+where
+    DB: Database,
+{
+    Script {
+        setup,
+        query,
+        transaction: false,
+    }
+}
+
+impl<'q, DB, F, O, A> Script<'q, DB, F, A>
+where
+    DB: Database,
+    F: FnMut(DB::Row) -> Result<O, Error> + Send,
+    O: Send + Unpin,
+    A: 'q + Send + IntoArguments<DB>,
+{
+    /// Run the script in a transaction, which is committed if every statement succeeds
+    /// and rolled back otherwise.
+    pub fn in_transaction(mut self) -> Self {
+        self.transaction = true;
+        self
+    }
+
+    /// Run the script and return the number of rows affected by the last statement.
+    pub async fn execute<'c, C>(self, conn: C) -> Result<DB::QueryResult, Error>
+    where
+        C: Acquire<'c, Database = DB>,
+        for<'e> &'e mut DB::Connection: Executor<'e, Database = DB>,
+    {
+        let (mut conn, query) = self.run_setup(conn).await?;
+        let result = query.inner.execute(&mut *conn).await?;
+        conn.finish().await?;
+
+        Ok(result)
+    }
+
+    /// Run the script and return all the rows of the last statement.
+    pub async fn fetch_all<'c, C>(self, conn: C) -> Result<Vec<O>, Error>
+    where
+        C: Acquire<'c, Database = DB>,
+        for<'e> &'e mut DB::Connection: Executor<'e, Database = DB>,
+    {
+        let (mut conn, query) = self.run_setup(conn).await?;
+        let rows = query.fetch_all(&mut *conn).await?;
+        conn.finish().await?;
+
+        Ok(rows)
+    }
+
+    /// Run the script and return the first row of the last statement,
+    /// or [`Error::RowNotFound`] if there is none.
+    pub async fn fetch_one<'c, C>(self, conn: C) -> Result<O, Error>
+    where
+        C: Acquire<'c, Database = DB>,
+        for<'e> &'e mut DB::Connection: Executor<'e, Database = DB>,
+    {
+        let (mut conn, query) = self.run_setup(conn).await?;
+        let row = query.fetch_one(&mut *conn).await?;
+        conn.finish().await?;
+
+        Ok(row)
+    }
+
+    /// Run the script and return the first row of the last statement, if any.
+    pub async fn fetch_optional<'c, C>(self, conn: C) -> Result<Option<O>, Error>
+    where
+        C: Acquire<'c, Database = DB>,
+        for<'e> &'e mut DB::Connection: Executor<'e, Database = DB>,
+    {
+        let (mut conn, query) = self.run_setup(conn).await?;
+        let row = query.fetch_optional(&mut *conn).await?;
+        conn.finish().await?;
+
+        Ok(row)
+    }
+
+    /// Acquire a connection (or begin a transaction) and execute the statements before the last.
+    async fn run_setup<'c, C>(
+        self,
+        conn: C,
+    ) -> Result<(ScriptConnection<'c, C>, Map<'q, DB, F, A>), Error>
+    where
+        C: Acquire<'c, Database = DB>,
+        for<'e> &'e mut DB::Connection: Executor<'e, Database = DB>,
+    {
+        let mut conn = if self.transaction {
+            ScriptConnection::Transaction(conn.begin().await?)
+        } else {
+            ScriptConnection::Connection(conn.acquire().await?)
+        };
+
+        for statement in self.setup {
+            statement.execute(&mut *conn).await?;
+        }
+
+        Ok((conn, self.query))
+    }
+}
+
+enum ScriptConnection<'c, C: Acquire<'c>> {
+    Connection(C::Connection),
+    Transaction(Transaction<'c, C::Database>),
+}
+
+impl<'c, C: Acquire<'c>> ScriptConnection<'c, C> {
+    /// Commit the transaction, if the script is run in one.
+    async fn finish(self) -> Result<(), Error> {
+        match self {
+            ScriptConnection::Connection(_) => Ok(()),
+            ScriptConnection::Transaction(tx) => tx.commit().await,
+        }
+    }
+}
+
+impl<'c, C: Acquire<'c>> Deref for ScriptConnection<'c, C> {
+    type Target = <C::Database as Database>::Connection;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            ScriptConnection::Connection(conn) => conn,
+            ScriptConnection::Transaction(tx) => tx,
+        }
+    }
+}
+
+impl<'c, C: Acquire<'c>> DerefMut for ScriptConnection<'c, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            ScriptConnection::Connection(conn) => conn,
+            ScriptConnection::Transaction(tx) => tx,
+        }
+    }
+}
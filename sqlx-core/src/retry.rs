@@ -0,0 +1,204 @@
+//! Retrying transactions that fail due to transient errors.
+//!
+//! At stricter isolation levels (e.g. `SERIALIZABLE` in Postgres), or under heavy contention,
+//! the database may abort a transaction with an error that does not indicate a problem
+//! with the transaction itself, but rather a conflict with a concurrent one.
+//! The correct response to such an error is to run the whole transaction again.
+//!
+//! [`RetryPolicy`] describes how many times to try and how long to wait between attempts,
+//! and which errors are worth retrying. By default, it retries errors for which
+//! [`Error::is_retryable()`] returns `true`.
+//!
+//! Use it with [`Connection::transaction_with_retry()`], or wrap any operation
+//! (like one using [`Pool::begin()`]) with [`RetryPolicy::run()`].
+//!
+//! [`Connection::transaction_with_retry()`]: crate::connection::Connection::transaction_with_retry
+//! [`Pool::begin()`]: crate::pool::Pool::begin
+use std::cmp;
+use std::collections::hash_map::RandomState;
+use std::fmt::{self, Debug, Formatter};
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::Error;
+
+/// Describes when and how to retry an operation that failed with a transient error.
+///
+/// The delay between attempts starts at the initial backoff and doubles after each attempt,
+/// up to the maximum backoff. A random jitter of up to half the delay is applied so that
+/// transactions which conflicted with each other don't retry in lockstep.
+///
+/// # Example: Retrying a Transaction from a Pool
+/// ```rust,no_run
+/// # async fn example(pool: &sqlx::PgPool) -> sqlx::Result<()> {
+/// use sqlx::retry::RetryPolicy;
+/// use sqlx::{IsolationLevel, TransactionOptions};
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new()
+///     .max_attempts(5)
+///     .backoff(Duration::from_millis(5), Duration::from_millis(500));
+///
+/// let balance: i64 = policy
+///     .run(|| async move {
+///         let mut tx = pool
+///             .begin_with(TransactionOptions::new().isolation(IsolationLevel::Serializable))
+///             .await?;
+///
+///         let balance: i64 = sqlx::query_scalar(
+///             "UPDATE accounts SET balance = balance - 10 WHERE id = 1 RETURNING balance",
+///         )
+///         .fetch_one(&mut *tx)
+///         .await?;
+///
+///         tx.commit().await?;
+///
+///         Ok(balance)
+///     })
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct RetryPolicy<E = Error> {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    classifier: Arc<dyn Fn(&E) -> bool + Send + Sync>,
+}
+
+impl RetryPolicy<Error> {
+    /// Create a policy that retries errors for which [`Error::is_retryable()`] returns `true`.
+    ///
+    /// See the source of this method for the current default values.
+    pub fn new() -> Self {
+        Self::with_classifier(Error::is_retryable)
+    }
+}
+
+impl Default for RetryPolicy<Error> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> RetryPolicy<E> {
+    /// Create a policy that retries errors for which `classifier` returns `true`.
+    ///
+    /// This allows retrying operations that return an error type other than [`Error`],
+    /// or retrying errors that aren't [transient][crate::error::DatabaseError::is_transient].
+    pub fn with_classifier(classifier: impl Fn(&E) -> bool + Send + Sync + 'static) -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+            classifier: Arc::new(classifier),
+        }
+    }
+
+    /// Set the maximum number of times the operation will be run, including the first attempt.
+    ///
+    /// A value of `1` disables retrying. `0` is treated the same as `1`.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = cmp::max(max_attempts, 1);
+        self
+    }
+
+    /// Get the maximum number of times the operation will be run.
+    pub fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Set the delay before the first retry, and the maximum delay between any two attempts.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = cmp::max(max, initial);
+        self
+    }
+
+    /// Get the delay before the first retry.
+    pub fn get_initial_backoff(&self) -> Duration {
+        self.initial_backoff
+    }
+
+    /// Get the maximum delay between any two attempts.
+    pub fn get_max_backoff(&self) -> Duration {
+        self.max_backoff
+    }
+
+    /// Returns `true` if an operation that failed with `error` on its `attempt`-th run
+    /// (starting from `1`) should be run again.
+    pub fn should_retry(&self, attempt: u32, error: &E) -> bool {
+        attempt < self.max_attempts && (self.classifier)(error)
+    }
+
+    /// Returns the delay before running the operation again after its `attempt`-th run
+    /// (starting from `1`), including jitter.
+    pub fn backoff_for(&self, attempt: u32) -> Duration {
+        let backoff = cmp::min(
+            self.initial_backoff
+                .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1))),
+            self.max_backoff,
+        );
+
+        // Equal jitter: wait between half and all of `backoff`.
+        //
+        // `RandomState` is randomly seeded, which is good enough for this purpose
+        // and saves us a dependency.
+        let half = backoff / 2;
+        let random = RandomState::new().build_hasher().finish();
+        let jitter = half.mul_f64((random as f64) / (u64::MAX as f64));
+
+        half + jitter
+    }
+
+    /// Run `operation` until it succeeds, fails with an error that should not be retried,
+    /// or the maximum number of attempts is reached.
+    ///
+    /// Returns the result of the last attempt.
+    ///
+    /// The operation should start from scratch each time; for example, it should begin
+    /// a new transaction rather than continuing one that failed.
+    pub async fn run<F, Fut, T>(&self, mut operation: F) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut attempt = 1;
+
+        loop {
+            match operation().await {
+                Err(error) if self.should_retry(attempt, &error) => {
+                    tracing::debug!(attempt, "retrying operation after transient error");
+
+                    crate::rt::sleep(self.backoff_for(attempt)).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+// Manually implement `Clone` to avoid requiring `E: Clone`.
+impl<E> Clone for RetryPolicy<E> {
+    fn clone(&self) -> Self {
+        RetryPolicy {
+            max_attempts: self.max_attempts,
+            initial_backoff: self.initial_backoff,
+            max_backoff: self.max_backoff,
+            classifier: self.classifier.clone(),
+        }
+    }
+}
+
+impl<E> Debug for RetryPolicy<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .finish_non_exhaustive()
+    }
+}
@@ -27,6 +27,37 @@ pub trait TransactionManager {
         statement: Option<SqlStr>,
     ) -> impl Future<Output = Result<(), Error>> + Send + '_;
 
+    /// Render the statement that begins a new transaction with the given options.
+    ///
+    /// The result is passed to [`begin()`][Self::begin] when [`TransactionOptions`] are given to
+    /// `begin_with()`. Options the database does not support should be ignored.
+    ///
+    /// The default implementation renders the standard SQL `START TRANSACTION` with the isolation
+    /// level and access mode, if either is set, so that a database which doesn't support them
+    /// returns an error rather than ignoring them. `deferrable` is ignored.
+    fn begin_statement(
+        conn: &<Self::Database as Database>::Connection,
+        options: &TransactionOptions,
+    ) -> SqlStr {
+        let _ = conn;
+
+        let mut modes = Vec::new();
+
+        if let Some(isolation) = options.get_isolation() {
+            modes.push(format!("ISOLATION LEVEL {}", isolation.as_sql()));
+        }
+
+        if options.get_read_only() {
+            modes.push("READ ONLY".into());
+        }
+
+        if modes.is_empty() {
+            return begin_ansi_transaction_sql(0);
+        }
+
+        AssertSqlSafe(format!("START TRANSACTION {}", modes.join(", "))).into_sql_str()
+    }
+
     /// Commit the active transaction or release the most recent savepoint.
     fn commit(
         conn: &mut <Self::Database as Database>::Connection,
@@ -279,6 +310,140 @@ where
     }
 }
 
+/// The isolation level of a transaction.
+///
+/// See [`TransactionOptions::isolation()`] for how each level is applied by the different
+/// databases.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    /// The name of this isolation level as it appears in SQL, e.g. `REPEATABLE READ`.
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
+/// Options for beginning a new transaction, in a database-agnostic form.
+///
+/// Pass to [`Connection::begin_with()`] or [`Pool::begin_with()`] in place of a `BEGIN` statement;
+/// each database renders these options to its own syntax. The default options begin
+/// a transaction with the database's default settings, like [`Connection::begin()`].
+///
+/// ```rust,no_run
+/// # async fn example(pool: &sqlx::PgPool) -> sqlx::Result<()> {
+/// use sqlx::{IsolationLevel, TransactionOptions};
+///
+/// let mut tx = pool
+///     .begin_with(
+///         TransactionOptions::new()
+///             .isolation(IsolationLevel::Serializable)
+///             .read_only(true),
+///     )
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Connection::begin_with()`]: crate::connection::Connection::begin_with()
+/// [`Connection::begin()`]: crate::connection::Connection::begin()
+/// [`Pool::begin_with()`]: crate::pool::Pool::begin_with()
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TransactionOptions {
+    isolation: Option<IsolationLevel>,
+    read_only: bool,
+    deferrable: bool,
+}
+
+impl TransactionOptions {
+    /// Default options: the database's default isolation level and a read-write transaction.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the isolation level of the transaction.
+    ///
+    /// ### Postgres and MySQL
+    /// The isolation level is set for the new transaction only.
+    ///
+    /// Postgres treats `ReadUncommitted` as `ReadCommitted`.
+    ///
+    /// ### SQLite
+    /// SQLite transactions are always serializable, so the isolation level instead selects
+    /// when the transaction takes its locks:
+    ///
+    /// * `Serializable`: `BEGIN EXCLUSIVE`
+    /// * `RepeatableRead`: `BEGIN IMMEDIATE`
+    /// * `ReadCommitted` and `ReadUncommitted`: `BEGIN DEFERRED`
+    pub fn isolation(mut self, isolation: impl Into<Option<IsolationLevel>>) -> Self {
+        self.isolation = isolation.into();
+        self
+    }
+
+    /// Get the isolation level of the transaction, if set.
+    pub fn get_isolation(&self) -> Option<IsolationLevel> {
+        self.isolation
+    }
+
+    /// If `true`, begin a read-only transaction.
+    ///
+    /// SQLite has no read-only transactions; a read-only transaction always uses
+    /// `BEGIN DEFERRED` so that it does not take a write lock.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Returns `true` if the transaction will be read-only.
+    pub fn get_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// If `true`, begin a deferrable transaction.
+    ///
+    /// Only supported by Postgres, where it only has an effect for `SERIALIZABLE READ ONLY`
+    /// transactions. Ignored by other databases.
+    pub fn deferrable(mut self, deferrable: bool) -> Self {
+        self.deferrable = deferrable;
+        self
+    }
+
+    /// Returns `true` if the transaction will be deferrable.
+    pub fn get_deferrable(&self) -> bool {
+        self.deferrable
+    }
+}
+
+/// A statement or set of options that can be used to begin a transaction.
+///
+/// Implemented for [`TransactionOptions`] and all [`SqlSafeStr`] types.
+pub trait IntoBeginStatement<DB: Database> {
+    /// Convert `self` to the statement beginning a transaction on `conn`.
+    fn into_begin_statement(self, conn: &DB::Connection) -> SqlStr;
+}
+
+impl<DB: Database, T: SqlSafeStr> IntoBeginStatement<DB> for T {
+    fn into_begin_statement(self, _conn: &DB::Connection) -> SqlStr {
+        self.into_sql_str()
+    }
+}
+
+impl<DB: Database> IntoBeginStatement<DB> for TransactionOptions {
+    fn into_begin_statement(self, conn: &DB::Connection) -> SqlStr {
+        DB::TransactionManager::begin_statement(conn, &self)
+    }
+}
+
 pub fn begin_ansi_transaction_sql(depth: usize) -> SqlStr {
     if depth == 0 {
         "BEGIN".into_sql_str()
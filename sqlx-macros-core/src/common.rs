@@ -30,3 +30,14 @@ pub(crate) fn resolve_path(path: impl AsRef<Path>, err_span: Span) -> syn::Resul
 
     Ok(out_path)
 }
+
+/// Hashes a column name to a `u64`, which unlike a string can be a const generic argument.
+///
+/// Used to name the columns of a `#[derive(FromRow)]` struct in the types it generates.
+#[cfg(any(feature = "derive", feature = "macros"))]
+pub(crate) fn column_name_hash(name: &str) -> u64 {
+    // 64-bit FNV-1a, which is stable across compiler versions unlike `DefaultHasher`
+    name.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,122 @@
+#[cfg(any(feature = "postgres", feature = "mysql", feature = "_sqlite"))]
+use sqlx_core::error::DatabaseError;
+
+/// What the database told us about where an error in a query is, and why.
+#[derive(Debug, Default)]
+pub struct ErrorDetails {
+    /// The byte offset into the query of the token that caused the error.
+    pub offset: Option<usize>,
+
+    /// Further information about the error, e.g. `hint: ...`.
+    pub notes: Vec<String>,
+}
+
+/// Postgres reports the position of the error in characters, along with detail and hint fields.
+#[cfg(feature = "postgres")]
+pub fn postgres(err: &dyn DatabaseError, query: &str) -> ErrorDetails {
+    use sqlx_postgres::{PgDatabaseError, PgErrorPosition};
+
+    let Some(err) = err.try_downcast_ref::<PgDatabaseError>() else {
+        return ErrorDetails::default();
+    };
+
+    let mut notes = Vec::new();
+
+    let offset = match err.position() {
+        // 1-based
+        Some(PgErrorPosition::Original(position)) => query
+            .char_indices()
+            .nth(position.saturating_sub(1))
+            .map(|(offset, _)| offset),
+        Some(PgErrorPosition::Internal { position, query }) => {
+            notes.push(format!(
+                "in a query generated by the database, at character {position}: {query}"
+            ));
+            None
+        }
+        None => None,
+    };
+
+    notes.extend(err.detail().map(|detail| format!("detail: {detail}")));
+    notes.extend(err.hint().map(|hint| format!("hint: {hint}")));
+
+    ErrorDetails { offset, notes }
+}
+
+/// MySQL only quotes the rest of the query from the offending token,
+/// e.g. `... near 'FORM accounts' at line 1`.
+#[cfg(feature = "mysql")]
+pub fn mysql(err: &dyn DatabaseError, query: &str) -> ErrorDetails {
+    let message = err.message();
+
+    let offset = message.rfind(" near '").and_then(|start| {
+        let rest = &message[start + " near '".len()..];
+        let (near, line) = rest.rsplit_once("' at line ")?;
+        let line = line.trim_end().parse::<usize>().ok()?;
+
+        // MySQL truncates the quoted text, so only look for the first line of it.
+        let near = near.lines().next().filter(|near| !near.is_empty())?;
+
+        let line_start = match line.checked_sub(1)? {
+            0 => 0,
+            n => query.match_indices('\n').nth(n - 1)?.0 + 1,
+        };
+
+        query[line_start..]
+            .find(near)
+            .map(|offset| line_start + offset)
+    });
+
+    ErrorDetails {
+        offset,
+        notes: Vec::new(),
+    }
+}
+
+/// SQLite reports the byte offset of the offending token since 3.38.0, which
+/// [`SqliteError::offset()`][sqlx_sqlite::SqliteError::offset] returns with the bundled SQLite.
+///
+/// Otherwise, the token is looked up in the query by its name in the message,
+/// e.g. `near "FORM": syntax error` or `no such column: nme`.
+#[cfg(feature = "_sqlite")]
+pub fn sqlite(err: &dyn DatabaseError, query: &str) -> ErrorDetails {
+    if let Some(offset) = err
+        .try_downcast_ref::<sqlx_sqlite::SqliteError>()
+        .and_then(sqlx_sqlite::SqliteError::offset)
+    {
+        return ErrorDetails {
+            offset: Some(offset),
+            notes: Vec::new(),
+        };
+    }
+
+    let message = err.message();
+
+    let token = if let Some(rest) = message.strip_prefix("near \"") {
+        rest.split_once("\": ").map(|(token, _)| token)
+    } else {
+        ["no such column: ", "no such table: ", "no such function: "]
+            .iter()
+            .find_map(|prefix| message.strip_prefix(prefix))
+            // the table is qualified with its schema, e.g. `main.accounts`
+            .map(|name| name.rsplit('.').next().unwrap_or(name))
+    };
+
+    ErrorDetails {
+        offset: token.and_then(|token| find_token(query, token)),
+        notes: Vec::new(),
+    }
+}
+
+/// Finds `token` in `query` where it isn't part of a longer word.
+#[cfg(feature = "_sqlite")]
+fn find_token(query: &str, token: &str) -> Option<usize> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    query.match_indices(token).map(|(i, _)| i).find(|&i| {
+        let before = query[..i].chars().next_back();
+        let after = query[i + token.len()..].chars().next();
+
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
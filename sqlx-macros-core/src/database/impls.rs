@@ -2,12 +2,54 @@ macro_rules! impl_database_ext {
     (
         $database:path,
         row: $row:path,
+        params: $params:ident,
+        named-params: [$($prefix:literal),*],
+        capture-named-params: $capture:literal,
+        empty-list: $empty_list:literal,
         $(describe-blocking: $describe:path,)?
+        $(describe-from-migrations: $describe_from_migrations:path,)?
+        error-details: $error_details:path,
     ) => {
         impl $crate::database::DatabaseExt for $database {
             const DATABASE_PATH: &'static str = stringify!($database);
             const ROW_PATH: &'static str = stringify!($row);
+            const PARAM_STYLE: $crate::database::ParamStyle = $crate::database::ParamStyle::$params;
+            const NAMED_PARAM_PREFIXES: &'static [u8] = &[$($prefix),*];
+            const CAPTURE_NAMED_PARAMS: bool = $capture;
+            const EMPTY_LIST: &'static str = $empty_list;
             impl_describe_blocking!($database, $($describe)?);
+
+            #[cfg(feature = "any")]
+            fn describe_into_any(
+                describe: sqlx_core::describe::Describe<Self>,
+            ) -> sqlx_core::Result<sqlx_core::describe::Describe<sqlx_core::any::Any>> {
+                describe.try_into_any()
+            }
+
+            fn error_details(
+                err: &dyn sqlx_core::error::DatabaseError,
+                query: &str,
+            ) -> $crate::database::ErrorDetails {
+                $error_details(err, query)
+            }
+
+            fn describe_script_blocking(
+                statements: &[&str],
+                database_url: &str,
+                driver_config: &sqlx_core::config::drivers::Config,
+            ) -> sqlx_core::Result<Vec<sqlx_core::describe::Describe<Self>>> {
+                $crate::database::describe_script::<Self>(statements, database_url, driver_config)
+            }
+
+            $(
+                fn describe_from_migrations(
+                    query: &str,
+                    migrations_dir: &std::path::Path,
+                    config: &sqlx_core::config::Config,
+                ) -> sqlx_core::Result<sqlx_core::describe::Describe<Self>> {
+                    $describe_from_migrations(query, migrations_dir, config)
+                }
+            )?
         }
     }
 }
@@ -50,24 +92,73 @@ mod sqlx {
     pub use sqlx_sqlite as sqlite;
 }
 
+// Queries for `Any` are described by the backends in `macros.any-backends` instead.
+#[cfg(feature = "any")]
+impl crate::database::DatabaseExt for sqlx_core::any::Any {
+    const DATABASE_PATH: &'static str = "sqlx::any::Any";
+    const ROW_PATH: &'static str = "sqlx::any::AnyRow";
+    // The query is sent to each backend as written.
+    const PARAM_STYLE: crate::database::ParamStyle = crate::database::ParamStyle::Numbered;
+    const NAMED_PARAM_PREFIXES: &'static [u8] = &[];
+    const CAPTURE_NAMED_PARAMS: bool = false;
+    // List parameters are not supported.
+    const EMPTY_LIST: &'static str = "";
+
+    fn describe_blocking(
+        _query: &str,
+        _database_url: &str,
+        _driver_config: &sqlx_core::config::drivers::Config,
+    ) -> sqlx_core::Result<sqlx_core::describe::Describe<Self>> {
+        Err(sqlx_core::Error::Configuration(
+            "queries for the Any driver are described by the backends in `macros.any-backends`"
+                .into(),
+        ))
+    }
+
+    fn describe_into_any(
+        describe: sqlx_core::describe::Describe<Self>,
+    ) -> sqlx_core::Result<sqlx_core::describe::Describe<Self>> {
+        Ok(describe)
+    }
+}
+
 // NOTE: type mappings have been moved to `src/type_checking.rs` in their respective driver crates.
 #[cfg(feature = "mysql")]
 impl_database_ext! {
     sqlx::mysql::MySql,
     row: sqlx::mysql::MySqlRow,
+    params: Positional,
+    // `@name` is a user-defined variable in MySQL.
+    named-params: [b':'],
+    capture-named-params: true,
+    empty-list: "SELECT NULL FROM DUAL WHERE FALSE",
+    error-details: crate::database::error_details::mysql,
 }
 
 #[cfg(feature = "postgres")]
 impl_database_ext! {
     sqlx::postgres::Postgres,
     row: sqlx::postgres::PgRow,
+    params: Numbered,
+    named-params: [b':', b'@'],
+    // `@` is an operator, and `:` separates the bounds of array slices, e.g. `arr[:hi]`.
+    capture-named-params: false,
+    // List parameters are not supported; bind an array instead.
+    empty-list: "",
+    error-details: crate::database::error_details::postgres,
 }
 
 #[cfg(feature = "_sqlite")]
 impl_database_ext! {
     sqlx::sqlite::Sqlite,
     row: sqlx::sqlite::SqliteRow,
+    params: Positional,
+    named-params: [b':', b'@'],
+    capture-named-params: true,
+    empty-list: "SELECT NULL WHERE 1 = 0",
     // Since proc-macros don't benefit from async, we can make a describe call directly
     // which also ensures that the database is closed afterwards, regardless of errors.
     describe-blocking: sqlx_sqlite::describe_blocking,
+    describe-from-migrations: crate::database::describe_sqlite_from_migrations,
+    error-details: crate::database::error_details::sqlite,
 }
@@ -2,21 +2,41 @@ use sqlx_core::config;
 use sqlx_core::connection::Connection;
 use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
+use sqlx_core::error::DatabaseError;
 use sqlx_core::executor::Executor;
 use sqlx_core::sql_str::AssertSqlSafe;
 use sqlx_core::sql_str::SqlSafeStr;
 use sqlx_core::type_checking::TypeChecking;
+use sqlx_core::Either;
 use std::collections::hash_map;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::{LazyLock, Mutex};
 
+mod error_details;
 #[cfg(any(feature = "postgres", feature = "mysql", feature = "_sqlite"))]
 mod impls;
 
+pub use error_details::ErrorDetails;
+
 pub trait DatabaseExt: Database + TypeChecking {
     const DATABASE_PATH: &'static str;
     const ROW_PATH: &'static str;
 
+    /// How bind parameters are written in SQL for this database.
+    const PARAM_STYLE: ParamStyle;
+
+    /// The characters that may introduce a named parameter (e.g. `:name`) in macro input.
+    const NAMED_PARAM_PREFIXES: &'static [u8];
+
+    /// Whether named parameters are bound to variables in scope even if no argument is passed
+    /// by name; `false` where the prefixes are also SQL syntax, which would then be rewritten.
+    const CAPTURE_NAMED_PARAMS: bool;
+
+    /// The SQL that an empty list parameter (`?...`) expands to: a subquery without rows,
+    /// so that `x IN (?...)` matches no rows and `x NOT IN (?...)` matches all of them.
+    const EMPTY_LIST: &'static str;
+
     fn db_path() -> syn::Path {
         syn::parse_str(Self::DATABASE_PATH).unwrap()
     }
@@ -30,6 +50,53 @@ pub trait DatabaseExt: Database + TypeChecking {
         database_url: &str,
         driver_config: &config::drivers::Config,
     ) -> sqlx_core::Result<Describe<Self>>;
+
+    /// Convert the description of a query to that of the same query for the `Any` driver.
+    #[cfg(feature = "any")]
+    fn describe_into_any(
+        describe: Describe<Self>,
+    ) -> sqlx_core::Result<Describe<sqlx_core::any::Any>>;
+
+    /// Get the position of `err` in `query`, and any further information about it,
+    /// for an error returned when describing `query`.
+    fn error_details(_err: &dyn DatabaseError, _query: &str) -> ErrorDetails {
+        ErrorDetails::default()
+    }
+
+    /// Describe each of `statements` in order on one connection; see [`describe_script()`].
+    fn describe_script_blocking(
+        _statements: &[&str],
+        _database_url: &str,
+        _driver_config: &config::drivers::Config,
+    ) -> sqlx_core::Result<Vec<Describe<Self>>> {
+        Err(sqlx_core::Error::Configuration(
+            format!("checking query scripts is not supported for {}", Self::NAME).into(),
+        ))
+    }
+
+    /// Describe `query` against a new database with the migrations in `migrations_dir` applied.
+    fn describe_from_migrations(
+        _query: &str,
+        _migrations_dir: &Path,
+        _config: &config::Config,
+    ) -> sqlx_core::Result<Describe<Self>> {
+        Err(sqlx_core::Error::Configuration(
+            format!(
+                "checking queries against migrations is not supported for {}",
+                Self::NAME
+            )
+            .into(),
+        ))
+    }
+}
+
+/// The native syntax for bind parameters in a database.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParamStyle {
+    /// `$1`, `$2`, ...; the same parameter may be referenced more than once.
+    Numbered,
+    /// `?`, bound in order of appearance.
+    Positional,
 }
 
 #[allow(dead_code)]
@@ -104,3 +171,150 @@ impl<DB: DatabaseExt> CachingDescribeBlocking<DB> {
         })
     }
 }
+
+/// Describes each of `statements` in order on a new connection, in a transaction which is
+/// rolled back afterwards.
+///
+/// Each statement without parameters, except the last, is also executed after it is described
+/// so that the statements after it can see its effects, e.g. a temporary table it creates.
+#[allow(dead_code)]
+pub fn describe_script<DB: DatabaseExt>(
+    statements: &[&str],
+    database_url: &str,
+    _driver_config: &config::drivers::Config,
+) -> sqlx_core::Result<Vec<Describe<DB>>>
+where
+    for<'a> &'a mut DB::Connection: Executor<'a, Database = DB>,
+{
+    crate::block_on(async {
+        let mut conn = DB::Connection::connect(database_url).await?;
+        let mut tx = conn.begin().await?;
+
+        let mut described = Vec::with_capacity(statements.len());
+
+        for (i, statement) in statements.iter().enumerate() {
+            let describe = tx
+                .describe(AssertSqlSafe(statement.to_string()).into_sql_str())
+                .await?;
+
+            let has_params = match describe.parameters() {
+                Some(Either::Left(params)) => !params.is_empty(),
+                Some(Either::Right(num)) => num > 0,
+                None => false,
+            };
+
+            if i + 1 < statements.len() && !has_params {
+                tx.execute(AssertSqlSafe(statement.to_string()).into_sql_str())
+                    .await?;
+            }
+
+            described.push(describe);
+        }
+
+        tx.rollback().await?;
+        conn.close().await?;
+
+        Ok(described)
+    })
+}
+
+/// Describes a query against an in-memory SQLite database with the migrations in
+/// `migrations_dir` applied.
+///
+/// The database is kept for subsequent queries, and rebuilt if the migrations change.
+#[cfg(feature = "_sqlite")]
+pub fn describe_sqlite_from_migrations(
+    query: &str,
+    migrations_dir: &Path,
+    config: &config::Config,
+) -> sqlx_core::Result<Describe<sqlx_sqlite::Sqlite>> {
+    use sqlx_core::connection::ConnectOptions;
+    use sqlx_core::migrate::{MigrateError, Migrator};
+    use sqlx_sqlite::{SqliteConnectOptions, SqliteConnection};
+    use std::path::PathBuf;
+
+    type Fingerprint = Vec<(i64, Vec<u8>)>;
+
+    static DATABASES: LazyLock<Mutex<HashMap<PathBuf, (Fingerprint, SqliteConnection)>>> =
+        LazyLock::new(|| Mutex::new(HashMap::new()));
+
+    let migrations = sqlx_core::migrate::resolve_blocking_with_config(
+        migrations_dir,
+        &config.migrate.to_resolve_config(),
+    )
+    .map_err(|e| MigrateError::Source(e.into()))?
+    .into_iter()
+    .map(|(migration, _path)| migration)
+    .collect::<Vec<_>>();
+
+    let fingerprint: Fingerprint = migrations
+        .iter()
+        .map(|migration| (migration.version, migration.checksum.to_vec()))
+        .collect();
+
+    let mut databases = DATABASES.lock().expect("previous panic in describe call");
+
+    crate::block_on(async {
+        let conn = match databases.entry(migrations_dir.to_path_buf()) {
+            hash_map::Entry::Occupied(hit) if hit.get().0 == fingerprint => &mut hit.into_mut().1,
+            entry => {
+                let mut conn = SqliteConnectOptions::new()
+                    .in_memory(true)
+                    .__unstable_apply_driver_config(&config.drivers)?
+                    .connect()
+                    .await?;
+
+                let mut migrator = Migrator::with_migrations(migrations);
+
+                // the same as `migrate!()` and `sqlx migrate run`
+                if let Some(vars) = &config.migrate.vars {
+                    migrator.set_templating(true);
+
+                    for (name, value) in vars {
+                        migrator.set_var(name.to_string(), value.to_string());
+                    }
+                }
+
+                migrator.run(&mut conn).await?;
+
+                let database = (fingerprint, conn);
+
+                match entry {
+                    hash_map::Entry::Occupied(mut stale) => {
+                        stale.insert(database);
+                        &mut stale.into_mut().1
+                    }
+                    hash_map::Entry::Vacant(miss) => &mut miss.insert(database).1,
+                }
+            }
+        };
+
+        conn.describe(AssertSqlSafe(query.to_string()).into_sql_str())
+            .await
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx_core::column::Column;
+
+    #[cfg(feature = "_sqlite")]
+    #[test]
+    fn sqlite_from_migrations_with_vars() {
+        let migrations_dir =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/sqlite/migrations_template");
+
+        let mut config = config::Config::default();
+        config.migrate.vars = Some([("sqlx_test_name".into(), "test".into())].into());
+
+        let describe = describe_sqlite_from_migrations(
+            "SELECT name FROM migrations_template_test",
+            &migrations_dir,
+            &config,
+        )
+        .unwrap();
+
+        assert_eq!(describe.columns()[0].name(), "name");
+    }
+}
@@ -1,12 +1,18 @@
-use proc_macro2::{Span, TokenStream};
+use std::collections::HashSet;
+
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
+use syn::ext::IdentExt;
 use syn::{
     parse_quote, punctuated::Punctuated, token::Comma, Data, DataStruct, DeriveInput, Expr, Field,
     Fields, FieldsNamed, FieldsUnnamed, Lifetime, Stmt,
 };
 
 use super::{
-    attributes::{parse_child_attributes, parse_container_attributes, JsonAttribute},
+    attributes::{
+        parse_child_attributes, parse_container_attributes, JsonAttribute, SqlxChildAttributes,
+        SqlxContainerAttributes,
+    },
     rename_all,
 };
 
@@ -87,15 +93,7 @@ fn expand_derive_from_row_struct(
                 ));
             }
 
-            let id_s = if let Some(s) = attributes.rename {
-                s
-            } else {
-                let s = id.to_string().trim_start_matches("r#").to_owned();
-                match container_attributes.rename_all {
-                    Some(pattern) => rename_all(&s, pattern),
-                    None => s
-                }
-            };
+            let id_s = column_name(id, &attributes, &container_attributes);
 
             let expr: Expr = match (attributes.flatten, attributes.try_from, attributes.json) {
                 // <No attributes>
@@ -226,6 +224,8 @@ fn expand_derive_from_row_struct(
 
     let names = fields.iter().map(|field| &field.ident);
 
+    let columns = expand_from_row_columns(input, &container_attributes, fields)?;
+
     Ok(quote!(
         #[automatically_derived]
         impl #impl_generics ::sqlx::FromRow<#lifetime, R> for #ident #ty_generics #where_clause {
@@ -239,9 +239,115 @@ fn expand_derive_from_row_struct(
                 })
             }
         }
+
+        #columns
     ))
 }
 
+/// Describes the columns read by the generated `FromRow` impl, for `query_as!()` to check.
+fn expand_from_row_columns(
+    input: &DeriveInput,
+    container_attributes: &SqlxContainerAttributes,
+    fields: &Punctuated<Field, Comma>,
+) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut columns_generics = input.generics.clone();
+    columns_generics.params.push(parse_quote!(__P));
+
+    let columns_predicates = &mut columns_generics.make_where_clause().predicates;
+
+    let mut columns = Vec::new();
+    let mut column_types = Vec::new();
+    let mut seen = HashSet::new();
+
+    for field in fields {
+        let Some(field_ident) = &field.ident else {
+            continue;
+        };
+
+        let attributes = parse_child_attributes(&field.attrs)?;
+
+        if attributes.skip {
+            continue;
+        }
+
+        let optional = attributes.default || container_attributes.default;
+        let ty = attributes.try_from.as_ref().unwrap_or(&field.ty);
+
+        if attributes.flatten {
+            columns_predicates.push(parse_quote!(#ty: ::sqlx::__from_row::FromRowColumns<__P>));
+
+            columns.push(quote! {
+                ::sqlx::__from_row::FromRowColumn::Flatten {
+                    columns: <#ty as ::sqlx::__from_row::FromRowColumns<__P>>::COLUMNS,
+                    optional: #optional,
+                }
+            });
+
+            continue;
+        }
+
+        let name = column_name(field_ident, &attributes, container_attributes);
+
+        let missing = format!(
+            "column `{name}` for field `{ident}::{}` is not returned by the query",
+            field_ident.unraw()
+        );
+
+        columns.push(quote! {
+            ::sqlx::__from_row::FromRowColumn::Named {
+                name: #name,
+                optional: #optional,
+                missing: #missing,
+            }
+        });
+
+        // JSON columns are checked against `Json<T>`, which the macros never infer
+        if attributes.json.is_none() && seen.insert(name.clone()) {
+            let hash = crate::common::column_name_hash(&name);
+
+            column_types.push(quote! {
+                #[automatically_derived]
+                impl #impl_generics ::sqlx::__from_row::FromRowColumnType<#hash> for #ident #ty_generics #where_clause {
+                    type Type = #ty;
+                }
+            });
+        }
+    }
+
+    let (columns_impl_generics, _, columns_where_clause) = columns_generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #columns_impl_generics ::sqlx::__from_row::FromRowColumns<__P> for #ident #ty_generics #columns_where_clause {
+            const COLUMNS: &'static [::sqlx::__from_row::FromRowColumn] = &[#(#columns),*];
+        }
+
+        #(#column_types)*
+    })
+}
+
+/// The name of the column that is read for a field.
+fn column_name(
+    field_ident: &Ident,
+    attributes: &SqlxChildAttributes,
+    container_attributes: &SqlxContainerAttributes,
+) -> String {
+    if let Some(s) = &attributes.rename {
+        return s.clone();
+    }
+
+    let s = field_ident.unraw().to_string();
+
+    match container_attributes.rename_all {
+        Some(pattern) => rename_all(&s, pattern),
+        None => s,
+    }
+}
+
 fn expand_derive_from_row_struct_unnamed(
     input: &DeriveInput,
     fields: &Punctuated<Field, Comma>,
@@ -280,6 +386,11 @@ fn expand_derive_from_row_struct_unnamed(
 
     let (impl_generics, _, where_clause) = generics.split_for_impl();
 
+    let mut columns_generics = input.generics.clone();
+    columns_generics.params.push(parse_quote!(__P));
+
+    let (columns_impl_generics, _, columns_where_clause) = columns_generics.split_for_impl();
+
     let gets = fields
         .iter()
         .enumerate()
@@ -294,5 +405,11 @@ fn expand_derive_from_row_struct_unnamed(
                 ))
             }
         }
+
+        // columns are read by index, so there are no names to check
+        #[automatically_derived]
+        impl #columns_impl_generics ::sqlx::__from_row::FromRowColumns<__P> for #ident #ty_generics #columns_where_clause {
+            const COLUMNS: &'static [::sqlx::__from_row::FromRowColumn] = &[];
+        }
     ))
 }
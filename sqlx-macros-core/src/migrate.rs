@@ -5,9 +5,12 @@ use std::path::{Path, PathBuf};
 
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens, TokenStreamExt};
+use sha2::{Digest, Sha384};
 use sqlx_core::config::Config;
 use sqlx_core::migrate::{Migration, MigrationType};
-use syn::LitStr;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{LitInt, LitStr, Token};
 
 pub const DEFAULT_PATH: &str = "./migrations";
 
@@ -39,11 +42,17 @@ impl ToTokens for QuoteMigration {
             migration_type,
             checksum,
             no_tx,
+            squashed_from,
             ..
         } = &self.migration;
 
         let migration_type = QuoteMigrationType(*migration_type);
 
+        let squashed_from = match squashed_from {
+            Some(from) => quote! { ::std::option::Option::Some(#from) },
+            None => quote! { ::std::option::Option::None },
+        };
+
         let sql = self
             .path
             .canonicalize()
@@ -76,6 +85,123 @@ impl ToTokens for QuoteMigration {
                 checksum: ::std::borrow::Cow::Borrowed(&[
                     #(#checksum),*
                 ]),
+                code: ::std::option::Option::None,
+                squashed_from: #squashed_from,
+            }
+        };
+
+        tokens.append_all(ts);
+    }
+}
+
+/// The input to `migrate!()`:
+///
+/// ```text
+/// migrate!("./migrations", rust = [(version, "description", "checksum version", path::to_fn), ...])
+/// ```
+///
+/// where both parts are optional.
+pub struct MigrateInput {
+    pub path: Option<LitStr>,
+    pub rust_migrations: Vec<RustMigrationInput>,
+}
+
+/// A migration written in Rust, declared in `migrate!()`.
+pub struct RustMigrationInput {
+    version: LitInt,
+    description: LitStr,
+    checksum_version: LitStr,
+    function: syn::Path,
+}
+
+impl Parse for MigrateInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse::<Option<LitStr>>()?;
+
+        if path.is_some() && !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+
+        let mut rust_migrations = Vec::new();
+
+        if !input.is_empty() {
+            let key = input.parse::<syn::Ident>()?;
+
+            if key != "rust" {
+                return Err(syn::Error::new_spanned(key, "expected `rust = [...]`"));
+            }
+
+            input.parse::<Token![=]>()?;
+
+            let content;
+            syn::bracketed!(content in input);
+
+            rust_migrations =
+                Punctuated::<RustMigrationInput, Token![,]>::parse_terminated(&content)?
+                    .into_iter()
+                    .collect();
+
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        Ok(MigrateInput {
+            path,
+            rust_migrations,
+        })
+    }
+}
+
+impl Parse for RustMigrationInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        syn::parenthesized!(content in input);
+
+        let version = content.parse()?;
+        content.parse::<Token![,]>()?;
+        let description = content.parse()?;
+        content.parse::<Token![,]>()?;
+        let checksum_version = content.parse()?;
+        content.parse::<Token![,]>()?;
+        let function = content.parse()?;
+        content.parse::<Option<Token![,]>>()?;
+
+        Ok(RustMigrationInput {
+            version,
+            description,
+            checksum_version,
+            function,
+        })
+    }
+}
+
+struct QuoteRustMigration<'a> {
+    version: i64,
+    migration: &'a RustMigrationInput,
+}
+
+impl ToTokens for QuoteRustMigration<'_> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let version = self.version;
+        let description = &self.migration.description;
+        let function = &self.migration.function;
+
+        // Same as `Migration::rust()`.
+        let checksum = Sha384::digest(self.migration.checksum_version.value()).to_vec();
+
+        let ts = quote! {
+            ::sqlx::migrate::Migration {
+                version: #version,
+                description: ::std::borrow::Cow::Borrowed(#description),
+                migration_type: ::sqlx::migrate::MigrationType::Simple,
+                sql: ::sqlx::SqlStr::from_static(""),
+                no_tx: false,
+                checksum: ::std::borrow::Cow::Borrowed(&[
+                    #(#checksum),*
+                ]),
+                code: ::std::option::Option::Some(&::sqlx::migrate::RustMigration(
+                    |tx| ::std::boxed::Box::pin(#function(tx))
+                )),
+                squashed_from: ::std::option::Option::None,
             }
         };
 
@@ -91,18 +217,22 @@ pub fn default_path(config: &Config) -> &str {
         .unwrap_or(DEFAULT_PATH)
 }
 
-pub fn expand(path_arg: Option<LitStr>) -> crate::Result<TokenStream> {
+pub fn expand(input: MigrateInput) -> crate::Result<TokenStream> {
     let config = Config::try_from_crate_or_default()?;
 
-    let path = match path_arg {
+    let path = match input.path {
         Some(path_arg) => crate::common::resolve_path(path_arg.value(), path_arg.span())?,
         None => { crate::common::resolve_path(default_path(&config), Span::call_site()) }?,
     };
 
-    expand_with_path(&config, &path)
+    expand_with_path(&config, &path, &input.rust_migrations)
 }
 
-pub fn expand_with_path(config: &Config, path: &Path) -> crate::Result<TokenStream> {
+pub fn expand_with_path(
+    config: &Config,
+    path: &Path,
+    rust_migrations: &[RustMigrationInput],
+) -> crate::Result<TokenStream> {
     let path = path.canonicalize().map_err(|e| {
         format!(
             "error canonicalizing migration directory {}: {e}",
@@ -113,16 +243,67 @@ pub fn expand_with_path(config: &Config, path: &Path) -> crate::Result<TokenStre
     let resolve_config = config.migrate.to_resolve_config();
 
     // Use the same code path to resolve migrations at compile time and runtime.
-    let migrations = sqlx_core::migrate::resolve_blocking_with_config(&path, &resolve_config)?
-        .into_iter()
-        .map(|(migration, path)| QuoteMigration { migration, path });
+    let migrations = sqlx_core::migrate::resolve_blocking_with_config(&path, &resolve_config)?;
+
+    let mut quoted = Vec::with_capacity(migrations.len() + rust_migrations.len());
+
+    for migration in rust_migrations {
+        let version = migration.version.base10_parse::<i64>()?;
+
+        if migrations.iter().any(|(m, _)| m.version == version)
+            || quoted.iter().any(|(v, _)| *v == version)
+        {
+            return Err(syn::Error::new_spanned(
+                &migration.version,
+                format!("duplicate migration version {version}"),
+            )
+            .into());
+        }
+
+        quoted.push((
+            version,
+            QuoteRustMigration { version, migration }.into_token_stream(),
+        ));
+    }
+
+    quoted.extend(migrations.into_iter().map(|(migration, path)| {
+        (
+            migration.version,
+            QuoteMigration { migration, path }.into_token_stream(),
+        )
+    }));
+
+    // `Migrator` expects the migrations to be ordered by version; the sort is stable,
+    // so the up and down parts of a reversible migration stay in order.
+    quoted.sort_by_key(|(version, _)| *version);
+
+    let migrations = quoted.into_iter().map(|(_, tokens)| tokens);
 
     let table_name = config.migrate.table_name();
 
+    let strict_order = config.migrate.strict_order;
+
     let create_schemas = config.migrate.create_schemas.iter().map(|schema_name| {
         quote! { ::std::borrow::Cow::Borrowed(#schema_name) }
     });
 
+    // the values are looked up when the migrations run, so they can come from the environment
+    let vars = match &config.migrate.vars {
+        Some(vars) => {
+            let vars = vars.iter().map(|(name, value)| {
+                let name = &**name;
+                let value = &**value;
+
+                quote! {
+                    (::std::borrow::Cow::Borrowed(#name), ::std::borrow::Cow::Borrowed(#value))
+                }
+            });
+
+            quote! { ::std::option::Option::Some(::std::borrow::Cow::Borrowed(&[#(#vars),*])) }
+        }
+        None => quote! { ::std::option::Option::None },
+    };
+
     #[cfg(any(sqlx_macros_unstable, procmacro2_semver_exempt))]
     {
         let path = path.to_str().ok_or_else(|| {
@@ -142,6 +323,8 @@ pub fn expand_with_path(config: &Config, path: &Path) -> crate::Result<TokenStre
             ]}),
             create_schemas: ::std::borrow::Cow::Borrowed(&[#(#create_schemas),*]),
             table_name: ::std::borrow::Cow::Borrowed(#table_name),
+            strict_order: #strict_order,
+            vars: #vars,
             ..::sqlx::migrate::Migrator::DEFAULT
         }
     })
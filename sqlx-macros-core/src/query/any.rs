@@ -0,0 +1,260 @@
+//! Checking queries for the `Any` driver against each backend in `macros.any-backends`.
+
+use std::path::Path;
+
+use either::Either;
+use proc_macro2::TokenStream;
+use sqlx_core::any::{Any, AnyTypeInfo, AnyTypeInfoKind};
+use sqlx_core::config::Config;
+use sqlx_core::describe::Describe;
+
+use crate::database::DatabaseExt;
+use crate::query::data::{hash_string, QueryData};
+use crate::query::metadata::{MacrosEnv, Metadata};
+use crate::query::{args, DescribeExt, QueryDataSource, QueryDriver, QueryMacroInput};
+
+pub(super) fn expand_input<'a>(
+    mut input: QueryMacroInput,
+    drivers: impl IntoIterator<Item = &'a QueryDriver>,
+    metadata: &Metadata,
+    metadata_env: &MacrosEnv,
+) -> crate::Result<TokenStream> {
+    let config = &metadata.config;
+    let drivers = drivers.into_iter().collect::<Vec<_>>();
+
+    // `Any` has no named parameters, so this only rejects `name = value` arguments.
+    args::expand_named_params::<Any>(&mut input)?;
+
+    let mut described = Vec::with_capacity(config.macros.any_backends.len());
+
+    for (backend, database_url_var) in &config.macros.any_backends {
+        let driver = drivers
+            .iter()
+            .find(|driver| driver.url_schemes.contains(&&**backend))
+            .ok_or_else(|| {
+                format!(
+                    "no database driver found for backend {backend:?} in `macros.any-backends`; \
+                     the corresponding Cargo feature may need to be enabled"
+                )
+            })?;
+
+        let data_source = if &**backend == "sqlite" && config.macros.sqlite_from_migrations {
+            super::migrations_source(metadata)
+        } else {
+            super::live_or_cached(
+                metadata,
+                metadata_env,
+                metadata_env
+                    .any_database_urls
+                    .get(backend)
+                    .map(|url| &**url),
+                database_url_var,
+                &backend_filename(&hash_string(&input.sql), backend),
+                &input.sql,
+            )?
+        };
+
+        if !data_source.matches_driver(driver) {
+            return Err(format!(
+                "the data for backend {backend:?} in `macros.any-backends` is not for {}",
+                driver.db_name
+            )
+            .into());
+        }
+
+        let describe = (driver.describe_any)(
+            config,
+            &input.sql,
+            data_source,
+            metadata_env.offline_dir.as_deref(),
+            backend,
+        )
+        .map_err(|e| format!("error describing query for backend {backend:?}: {e}"))?;
+
+        described.push((&**backend, describe));
+    }
+
+    let describe = merge(described)?;
+    let data = QueryData::from_describe(&input.sql, describe);
+
+    super::expand_with_data(config, input, data, None)
+}
+
+pub(super) type DescribeAny =
+    fn(&Config, &str, QueryDataSource, Option<&Path>, &str) -> crate::Result<Describe<Any>>;
+
+/// Describe `sql` for `DB`, saving the data for offline builds under `backend`,
+/// and convert the description to that for `Any`.
+pub(super) fn describe_with<DB: DatabaseExt>(
+    config: &Config,
+    sql: &str,
+    data_source: QueryDataSource,
+    offline_dir: Option<&Path>,
+    backend: &str,
+) -> crate::Result<Describe<Any>>
+where
+    Describe<DB>: DescribeExt,
+{
+    let (data, save_dir) = super::query_data_for::<DB>(config, sql, sql, data_source, offline_dir)?;
+
+    if let Some(save_dir) = save_dir {
+        data.save_as(save_dir, &backend_filename(&data.hash, backend))?;
+    }
+
+    Ok(DB::describe_into_any(data.describe)?)
+}
+
+/// The data for each backend is saved separately, as it is for the same query.
+fn backend_filename(hash: &str, backend: &str) -> String {
+    format!("query-{hash}-{backend}.json")
+}
+
+/// Merge the descriptions of a query by each backend into one that holds for all of them.
+fn merge(described: Vec<(&str, Describe<Any>)>) -> crate::Result<Describe<Any>> {
+    let mut described = described.into_iter();
+
+    let (first, mut merged) = described
+        .next()
+        .expect("BUG: `macros.any-backends` is not empty");
+
+    let mut seen = vec![first];
+
+    for (backend, describe) in described {
+        let previous = seen.join(", ");
+
+        if describe.columns.len() != merged.columns.len() {
+            return Err(format!(
+                "query returns {} columns for {previous} but {} columns for {backend}",
+                merged.columns.len(),
+                describe.columns.len()
+            )
+            .into());
+        }
+
+        for (column, other) in merged.columns.iter_mut().zip(&describe.columns) {
+            if column.name != other.name {
+                return Err(format!(
+                    "column {} is named {:?} for {previous} but {:?} for {backend}; \
+                     use a quoted alias to give it the same name",
+                    column.ordinal, &*column.name, &*other.name
+                )
+                .into());
+            }
+
+            column.type_info.kind = merge_kind(
+                column.type_info.kind,
+                other.type_info.kind,
+                IntegerWidth::Wider,
+            )
+            .ok_or_else(|| {
+                format!(
+                    "column {:?} is {} for {previous} but {} for {backend}; \
+                     cast it in the query to make them agree",
+                    &*column.name, column.type_info, other.type_info
+                )
+            })?;
+        }
+
+        merged.nullable = (0..merged.columns.len())
+            .map(|i| match (merged.nullable(i), describe.nullable(i)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            })
+            .collect();
+
+        merged.parameters =
+            merge_parameters(merged.parameters, describe.parameters, &previous, backend)?;
+
+        seen.push(backend);
+    }
+
+    // Parameters are only type-checked if the type of each is known.
+    if let Some(Either::Left(params)) = &merged.parameters {
+        if params
+            .iter()
+            .any(|param| param.kind == AnyTypeInfoKind::Null)
+        {
+            merged.parameters = Some(Either::Right(params.len()));
+        }
+    }
+
+    Ok(merged)
+}
+
+type Parameters = Option<Either<Vec<AnyTypeInfo>, usize>>;
+
+fn merge_parameters(
+    merged: Parameters,
+    other: Parameters,
+    previous: &str,
+    backend: &str,
+) -> crate::Result<Parameters> {
+    let count = |params: &Parameters| match params {
+        Some(Either::Left(params)) => Some(params.len()),
+        Some(Either::Right(count)) => Some(*count),
+        None => None,
+    };
+
+    if let (Some(merged_count), Some(other_count)) = (count(&merged), count(&other)) {
+        if merged_count != other_count {
+            return Err(format!(
+                "query expects {merged_count} parameters for {previous} \
+                 but {other_count} for {backend}"
+            )
+            .into());
+        }
+    }
+
+    Ok(match (merged, other) {
+        (Some(Either::Left(mut merged)), Some(Either::Left(other))) => {
+            for (i, (param, other)) in merged.iter_mut().zip(&other).enumerate() {
+                // A value must fit the narrower of two integer parameters.
+                param.kind = merge_kind(param.kind, other.kind, IntegerWidth::Narrower)
+                    .ok_or_else(|| {
+                        format!(
+                            "parameter ${} is {param} for {previous} but {other} for {backend}",
+                            i + 1
+                        )
+                    })?;
+            }
+
+            Some(Either::Left(merged))
+        }
+        (Some(Either::Left(params)), _) | (_, Some(Either::Left(params))) => {
+            Some(Either::Left(params))
+        }
+        (merged, other) => merged.or(other),
+    })
+}
+
+enum IntegerWidth {
+    Narrower,
+    Wider,
+}
+
+/// Returns the kind that `a` and `b` agree on, if any. `Null` means the kind is not known.
+fn merge_kind(
+    a: AnyTypeInfoKind,
+    b: AnyTypeInfoKind,
+    width: IntegerWidth,
+) -> Option<AnyTypeInfoKind> {
+    use AnyTypeInfoKind::*;
+
+    let rank = |kind| match kind {
+        SmallInt => 0,
+        Integer => 1,
+        _ => 2,
+    };
+
+    match (a, b) {
+        _ if a == b => Some(a),
+        (Null, kind) | (kind, Null) => Some(kind),
+        _ if a.is_integer() && b.is_integer() => Some(match width {
+            IntegerWidth::Narrower if rank(a) < rank(b) => a,
+            IntegerWidth::Wider if rank(a) > rank(b) => a,
+            _ => b,
+        }),
+        _ => None,
+    }
+}
@@ -1,14 +1,18 @@
-use crate::database::DatabaseExt;
+use crate::database::{DatabaseExt, ParamStyle};
+use crate::query::input::ParamArg;
 use crate::query::{QueryMacroInput, Warnings};
 use either::Either;
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote, quote_spanned};
 use sqlx_core::config::Config;
 use sqlx_core::describe::Describe;
 use sqlx_core::type_checking;
 use sqlx_core::type_info::TypeInfo;
+use std::fmt::Write;
+use std::ops::Range;
+use syn::ext::IdentExt;
 use syn::spanned::Spanned;
-use syn::{Expr, ExprCast, ExprGroup, Type};
+use syn::{Expr, ExprAssign, ExprCast, ExprGroup, ExprPath, Type};
 
 /// Returns a tokenstream which typechecks the arguments passed to the macro
 /// and binds them to `DB::Arguments` with the ident `query_args`.
@@ -17,25 +21,42 @@ pub fn quote_args<DB: DatabaseExt>(
     config: &Config,
     warnings: &mut Warnings,
     info: &Describe<DB>,
+) -> crate::Result<TokenStream> {
+    let mut tokens = quote_arg_bindings(input);
+    tokens.extend(quote_bound_args(input, config, warnings, info)?);
+
+    Ok(tokens)
+}
+
+/// Returns a tokenstream which evaluates each argument passed to the macro once,
+/// binding a reference to it to the variable named by [`arg_name()`].
+pub fn quote_arg_bindings(input: &QueryMacroInput) -> TokenStream {
+    let arg_name = (0..input.arg_exprs.len()).map(arg_name);
+    let arg_expr = input.arg_exprs.iter().cloned().map(strip_wildcard);
+
+    quote! {
+        #(let #arg_name = &(#arg_expr);)*
+    }
+}
+
+/// Returns a tokenstream which typechecks the arguments bound by [`quote_arg_bindings()`]
+/// against the parameters of the query, and binds them to `DB::Arguments` with the ident
+/// `query_args`.
+pub fn quote_bound_args<DB: DatabaseExt>(
+    input: &QueryMacroInput,
+    config: &Config,
+    warnings: &mut Warnings,
+    info: &Describe<DB>,
 ) -> crate::Result<TokenStream> {
     let db_path = DB::db_path();
 
-    if input.arg_exprs.is_empty() {
+    if input.param_args.is_empty() {
         return Ok(quote! {
             let query_args = ::core::result::Result::<_, ::sqlx::error::BoxDynError>::Ok(<#db_path as ::sqlx::database::Database>::Arguments::default());
         });
     }
 
-    let arg_names = (0..input.arg_exprs.len())
-        .map(|i| format_ident!("arg{}", i))
-        .collect::<Vec<_>>();
-
-    let arg_name = &arg_names;
-    let arg_expr = input.arg_exprs.iter().cloned().map(strip_wildcard);
-
-    let arg_bindings = quote! {
-        #(let #arg_name = &(#arg_expr);)*
-    };
+    let arg_names = (0..input.arg_exprs.len()).map(arg_name).collect::<Vec<_>>();
 
     let args_check = match info.parameters() {
         None | Some(Either::Right(_)) => {
@@ -51,9 +72,12 @@ pub fn quote_args<DB: DatabaseExt>(
         Some(Either::Left(params)) => {
             params
                 .iter()
-                .zip(arg_names.iter().zip(&input.arg_exprs))
+                .zip(&input.param_args)
                 .enumerate()
-                .map(|(i, (param_ty, (name, expr)))| -> crate::Result<_> {
+                .map(|(i, (param_ty, param_arg))| -> crate::Result<_> {
+                    let name = &arg_names[param_arg.arg];
+                    let expr = &input.arg_exprs[param_arg.arg];
+
                     if get_type_override(expr).is_some() {
                         // cast will fail to compile if the type does not match
                         // and we strip casts to wildcard
@@ -62,50 +86,396 @@ pub fn quote_args<DB: DatabaseExt>(
 
                     let param_ty = get_param_type::<DB>(param_ty, config, warnings, i)?;
 
-                    Ok(quote_spanned!(expr.span() =>
-                        // this shouldn't actually run
-                        #[allow(clippy::missing_panics_doc, clippy::unreachable)]
-                        if false {
-                            use ::sqlx::ty_match::{WrapSameExt as _, MatchBorrowExt as _};
-
-                            // evaluate the expression only once in case it contains moves
-                            let expr = ::sqlx::ty_match::dupe_value(#name);
-
-                            // if `expr` is `Option<T>`, get `Option<$ty>`, otherwise `$ty`
-                            let ty_check = ::sqlx::ty_match::WrapSame::<#param_ty, _>::new(&expr).wrap_same();
-
-                            // if `expr` is `&str`, convert `String` to `&str`
-                            let (mut _ty_check, match_borrow) = ::sqlx::ty_match::MatchBorrow::new(ty_check, &expr);
-
-                            _ty_check = match_borrow.match_borrow();
+                    // for a list, check the type of its elements
+                    let value = if param_arg.list_at.is_some() {
+                        quote!(#name.iter().next().unwrap())
+                    } else {
+                        quote!(#name)
+                    };
 
-                            // this causes move-analysis to effectively ignore this block
-                            ::std::unreachable!();
-                        }
-                    ))
+                    Ok(quote_param_check(expr.span(), &value, &param_ty))
                 })
                 .collect::<crate::Result<TokenStream>>()?
         }
     };
 
-    let args_count = input.arg_exprs.len();
+    let mut args_count = TokenStream::new();
+    let mut size_hint = TokenStream::new();
+    let mut add_args = TokenStream::new();
 
-    Ok(quote! {
-        #arg_bindings
+    for param_arg in &input.param_args {
+        let name = &arg_names[param_arg.arg];
+
+        if param_arg.list_at.is_some() {
+            args_count.extend(quote!(+ #name.iter().count()));
+            size_hint.extend(quote! {
+                + #name.iter().map(|value| ::sqlx::encode::Encode::<#db_path>::size_hint(value)).sum::<usize>()
+            });
+            add_args.extend(quote! {
+                .and_then(move |mut query_args| {
+                    for value in #name.iter() {
+                        query_args.add(value)?;
+                    }
+                    ::core::result::Result::Ok(query_args)
+                })
+            });
+        } else {
+            args_count.extend(quote!(+ 1));
+            size_hint.extend(quote!(+ ::sqlx::encode::Encode::<#db_path>::size_hint(#name)));
+            add_args.extend(quote! {
+                .and_then(move |mut query_args| query_args.add(#name).map(move |()| query_args))
+            });
+        }
+    }
 
+    Ok(quote! {
         #args_check
 
         let mut query_args = <#db_path as ::sqlx::database::Database>::Arguments::default();
-        query_args.reserve(
-            #args_count,
-            0 #(+ ::sqlx::encode::Encode::<#db_path>::size_hint(#arg_name))*
-        );
+        query_args.reserve(0 #args_count, 0 #size_hint);
         let query_args = ::core::result::Result::<_, ::sqlx::error::BoxDynError>::Ok(query_args)
-        #(.and_then(move |mut query_args| query_args.add(#arg_name).map(move |()| query_args) ))*;
+        #add_args;
     })
 }
 
-fn get_param_type<DB: DatabaseExt>(
+/// Returns a block which fails to compile if `value` cannot be bound to a parameter of type
+/// `param_ty`.
+pub fn quote_param_check(span: Span, value: &TokenStream, param_ty: &TokenStream) -> TokenStream {
+    quote_spanned!(span =>
+        // this shouldn't actually run
+        #[allow(clippy::missing_panics_doc, clippy::unreachable)]
+        if false {
+            use ::sqlx::ty_match::{WrapSameExt as _, MatchBorrowExt as _};
+
+            // evaluate the expression only once in case it contains moves
+            let expr = ::sqlx::ty_match::dupe_value(#value);
+
+            // if `expr` is `Option<T>`, get `Option<$ty>`, otherwise `$ty`
+            let ty_check = ::sqlx::ty_match::WrapSame::<#param_ty, _>::new(&expr).wrap_same();
+
+            // if `expr` is `&str`, convert `String` to `&str`
+            let (mut _ty_check, match_borrow) = ::sqlx::ty_match::MatchBorrow::new(ty_check, &expr);
+
+            _ty_check = match_borrow.match_borrow();
+
+            // this causes move-analysis to effectively ignore this block
+            ::std::unreachable!();
+        }
+    )
+}
+
+/// The name of the variable that the argument at index `i` is bound to.
+pub fn arg_name(i: usize) -> Ident {
+    format_ident!("arg{}", i)
+}
+
+/// Rewrites named parameters in the query (`:name`, or `@name` where the database allows it)
+/// to the native placeholders of `DB`.
+///
+/// Each name is bound to a `name = expr` argument if one was given, or else to the variable
+/// `name` in scope. Repeated names are bound once where the database can reference a parameter
+/// more than once, and are otherwise bound again for each occurrence.
+///
+/// Queries with positional arguments are left as-is, as are queries without arguments passed by
+/// name unless [`DatabaseExt::CAPTURE_NAMED_PARAMS`] is set, so that they keep their meaning where
+/// the prefixes are also SQL syntax, e.g. the `@` operator and array slices like `arr[:hi]` in
+/// Postgres.
+pub fn expand_named_params<DB: DatabaseExt>(input: &mut QueryMacroInput) -> crate::Result<()> {
+    let params = find_params(&input.sql, DB::NAMED_PARAM_PREFIXES)
+        .into_iter()
+        .filter_map(|param| match param.kind {
+            ParamKind::Named(name) => Some((name, param.range)),
+            ParamKind::Positional => None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut named_args: Vec<(&Ident, &Expr)> = Vec::new();
+    let mut positional_arg = None;
+
+    for expr in &input.arg_exprs {
+        match named_arg(expr) {
+            Some((name, value)) => {
+                if named_args.iter().any(|(other, _)| *other == name) {
+                    return Err(syn::Error::new_spanned(
+                        name,
+                        format!("duplicate named argument `{}`", name.unraw()),
+                    )
+                    .into());
+                }
+
+                named_args.push((name, value));
+            }
+            None => {
+                positional_arg.get_or_insert(expr);
+            }
+        }
+    }
+
+    if let Some(expr) = positional_arg {
+        if named_args.is_empty() {
+            // Any named parameters in the query are left for the database to interpret.
+            return Ok(());
+        }
+
+        return Err(syn::Error::new_spanned(
+            expr,
+            "positional arguments cannot be mixed with named arguments",
+        )
+        .into());
+    }
+
+    if let Some((name, _)) = named_args
+        .iter()
+        .find(|(name, _)| !params.iter().any(|(param, _)| name.unraw() == *param))
+    {
+        return Err(syn::Error::new_spanned(
+            name,
+            format!("named argument `{}` is not used in the query", name.unraw()),
+        )
+        .into());
+    }
+
+    if params.is_empty() || (named_args.is_empty() && !DB::CAPTURE_NAMED_PARAMS) {
+        return Ok(());
+    }
+
+    let mut names: Vec<&str> = Vec::new();
+    let mut param_args = Vec::with_capacity(params.len());
+    let mut sql = String::with_capacity(input.sql.len());
+    let mut copied = 0;
+
+    for (name, range) in &params {
+        let arg = match names.iter().position(|other| other == name) {
+            Some(arg) => arg,
+            None => {
+                names.push(name);
+                names.len() - 1
+            }
+        };
+
+        sql.push_str(&input.sql[copied..range.start]);
+        copied = range.end;
+
+        match DB::PARAM_STYLE {
+            ParamStyle::Numbered => {
+                if input.sql[range.end..].starts_with("...") {
+                    return Err(syn::Error::new(
+                        input.src_span,
+                        format!(
+                            "list parameter `{}...` is not supported by {}; \
+                             bind an array with `= ANY({0})` instead",
+                            &input.sql[range.clone()],
+                            DB::NAME
+                        ),
+                    )
+                    .into());
+                }
+
+                write!(sql, "${}", arg + 1).expect("writing to a String cannot fail");
+            }
+            ParamStyle::Positional => {
+                // a trailing `...` is kept, making this a list parameter
+                sql.push('?');
+                param_args.push(ParamArg::new(arg));
+            }
+        }
+    }
+
+    sql.push_str(&input.sql[copied..]);
+
+    if DB::PARAM_STYLE == ParamStyle::Numbered {
+        param_args = (0..names.len()).map(ParamArg::new).collect();
+    }
+
+    let arg_exprs = names
+        .iter()
+        .map(|name| {
+            if let Some((_, value)) = named_args.iter().find(|(arg, _)| arg.unraw() == name) {
+                return Ok((*value).clone());
+            }
+
+            // Spanned to the query so that the variable resolves in the caller's scope.
+            let mut ident = syn::parse_str::<Ident>(name).map_err(|_| {
+                syn::Error::new(
+                    input.src_span,
+                    format!("named parameter `{name}` is a keyword; bind it with `r#{name} = ...`"),
+                )
+            })?;
+            ident.set_span(input.src_span);
+
+            Ok(Expr::Path(syn::parse_quote!(#ident)))
+        })
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    input.sql = sql;
+    input.arg_exprs = arg_exprs;
+    input.param_args = param_args;
+    input.sql_rewritten = true;
+
+    Ok(())
+}
+
+/// Returns the name and value of a `name = expr` argument.
+pub(super) fn named_arg(expr: &Expr) -> Option<(&Ident, &Expr)> {
+    match expr {
+        Expr::Group(group) => named_arg(&group.expr),
+        Expr::Assign(ExprAssign { left, right, .. }) => match &**left {
+            Expr::Path(ExprPath {
+                qself: None, path, ..
+            }) => Some((path.get_ident()?, right)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Expands list parameters (`?...`) in the query, which bind each element of the argument,
+/// e.g. `WHERE id IN (?...)` with a slice.
+///
+/// The query is checked with a single placeholder in place of the list, and the placeholder is
+/// repeated for each element at runtime.
+pub fn expand_list_params<DB: DatabaseExt>(input: &mut QueryMacroInput) -> crate::Result<()> {
+    if DB::PARAM_STYLE != ParamStyle::Positional {
+        return Ok(());
+    }
+
+    let mut sql = String::with_capacity(input.sql.len());
+    let mut copied = 0;
+
+    let placeholders = find_params(&input.sql, &[])
+        .into_iter()
+        .filter(|param| matches!(param.kind, ParamKind::Positional));
+
+    // if the number of arguments doesn't match, that's reported after the query is described
+    for (param, param_arg) in placeholders.zip(&mut input.param_args) {
+        if !input.sql[param.range.end..].starts_with("...") {
+            continue;
+        }
+
+        sql.push_str(&input.sql[copied..param.range.end]);
+        copied = param.range.end + "...".len();
+
+        param_arg.list_at = Some(sql.len() - 1);
+    }
+
+    if copied > 0 {
+        sql.push_str(&input.sql[copied..]);
+        input.sql = sql;
+        input.sql_rewritten = true;
+    }
+
+    Ok(())
+}
+
+/// A bind parameter in the query.
+struct SqlParam {
+    kind: ParamKind,
+    /// The byte range of the parameter in the query, including any prefix.
+    range: Range<usize>,
+}
+
+enum ParamKind {
+    /// `:name` or `@name`; holds the name without its prefix.
+    Named(String),
+    /// `?`
+    Positional,
+}
+
+/// Finds the bind parameters in `sql`, skipping over string literals, quoted identifiers,
+/// comments, and casts (`::type`).
+///
+/// Named parameters are only recognized with one of `named_prefixes`.
+fn find_params(sql: &str, named_prefixes: &[u8]) -> Vec<SqlParam> {
+    let bytes = sql.as_bytes();
+
+    let mut params = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if let Some(end) = skip_quoted(bytes, i) {
+            i = end;
+            continue;
+        }
+
+        let follows_ident = i > 0 && is_ident_char(bytes[i - 1]);
+
+        match bytes[i] {
+            // `::type` casts, MySQL `@@variables`
+            b @ (b':' | b'@') if bytes.get(i + 1) == Some(&b) => i += 2,
+            b'?' => {
+                params.push(SqlParam {
+                    kind: ParamKind::Positional,
+                    range: i..i + 1,
+                });
+
+                i += 1;
+            }
+            b if named_prefixes.contains(&b)
+                && !follows_ident
+                && bytes
+                    .get(i + 1)
+                    .is_some_and(|b| b.is_ascii_alphabetic() || *b == b'_') =>
+            {
+                let end = bytes[i + 1..]
+                    .iter()
+                    .position(|b| !(b.is_ascii_alphanumeric() || *b == b'_'))
+                    .map_or(bytes.len(), |len| i + 1 + len);
+
+                params.push(SqlParam {
+                    kind: ParamKind::Named(sql[i + 1..end].to_string()),
+                    range: i..end,
+                });
+
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+
+    params
+}
+
+/// If a string literal, quoted identifier, comment, or Postgres dollar-quoted string
+/// (`$$ ... $$` or `$tag$ ... $tag$`) starts at index `i` of `bytes`,
+/// returns the index just past its end.
+pub(super) fn skip_quoted(bytes: &[u8], i: usize) -> Option<usize> {
+    let follows_ident = i > 0 && is_ident_char(bytes[i - 1]);
+
+    match bytes[i] {
+        quote @ (b'\'' | b'"' | b'`') => Some(skip_past(bytes, i + 1, &[quote])),
+        b'-' if bytes.get(i + 1) == Some(&b'-') => Some(skip_past(bytes, i + 2, b"\n")),
+        b'/' if bytes.get(i + 1) == Some(&b'*') => Some(skip_past(bytes, i + 2, b"*/")),
+        b'$' if !follows_ident && !bytes.get(i + 1).is_some_and(u8::is_ascii_digit) => {
+            let tag_end = bytes[i + 1..]
+                .iter()
+                .position(|&b| !is_ident_char(b))
+                .map_or(bytes.len(), |len| i + 1 + len);
+
+            (bytes.get(tag_end) == Some(&b'$'))
+                .then(|| skip_past(bytes, tag_end + 1, &bytes[i..=tag_end]))
+        }
+        _ => None,
+    }
+}
+
+/// Non-ASCII bytes are treated as part of identifiers so we never split a character.
+fn is_ident_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || !b.is_ascii()
+}
+
+/// Returns the index just past the next occurrence of `terminator` at or after `start`,
+/// or the end of `bytes` if there is none.
+fn skip_past(bytes: &[u8], start: usize, terminator: &[u8]) -> usize {
+    bytes
+        .get(start..)
+        .and_then(|rest| {
+            rest.windows(terminator.len())
+                .position(|window| window == terminator)
+        })
+        .map_or(bytes.len(), |pos| start + pos + terminator.len())
+}
+
+pub fn get_param_type<DB: DatabaseExt>(
     param_ty: &DB::TypeInfo,
     config: &Config,
     warnings: &mut Warnings,
@@ -137,9 +137,14 @@ where
     }
 
     pub(super) fn save_in(&self, dir: &Path) -> crate::Result<()> {
+        self.save_as(dir, &format!("query-{}.json", self.hash))
+    }
+
+    /// Save the data in `dir` under `filename`, which should match `query-*.json`.
+    pub(super) fn save_as(&self, dir: &Path, filename: &str) -> crate::Result<()> {
         use std::io::ErrorKind;
 
-        let path = dir.join(format!("query-{}.json", self.hash));
+        let path = dir.join(filename);
 
         if let Err(err) = fs::remove_file(&path) {
             match err.kind() {
@@ -0,0 +1,142 @@
+//! Pointing errors returned by the database at the part of the query they are about.
+//!
+//! This is only used by `expand_with()`; query scripts and `macros.any-backends` report errors
+//! from the database as-is. Spans inside a literal need `Span::subspan()`, which returns `None`
+//! on stable, so there the error is spanned to the whole literal and only the snippet in the
+//! message points at the token.
+
+use std::fmt::Write;
+
+use proc_macro2::Span;
+use syn::LitStr;
+
+use crate::database::DatabaseExt;
+use crate::query::QueryMacroInput;
+
+/// Converts an error from describing `input.sql` into one spanned to the offending token in the
+/// query literal where the compiler allows it, or else to the whole literal. The message shows
+/// the line of the query with the token marked, and any details given by the database.
+///
+/// Errors that weren't returned by the database are passed through as-is.
+pub(super) fn describe_error<DB: DatabaseExt>(
+    err: crate::Error,
+    input: &QueryMacroInput,
+) -> crate::Error {
+    let Some(db_err) = err
+        .downcast_ref::<sqlx_core::Error>()
+        .and_then(sqlx_core::Error::as_database_error)
+    else {
+        return err;
+    };
+
+    let details = DB::error_details(db_err, &input.sql);
+
+    let mut message = err.to_string();
+    let mut span = input.src_span;
+
+    if let Some(offset) = details
+        .offset
+        .filter(|&offset| input.sql.is_char_boundary(offset) && offset < input.sql.len())
+    {
+        let len = token_len(&input.sql[offset..]);
+
+        message.push_str(&snippet(input, offset, len));
+
+        // offsets into a rewritten query don't match those into the source
+        if !input.sql_rewritten {
+            if let Some(subspan) = literal_subspan(&input.src_lits, offset, len) {
+                span = subspan;
+            }
+        }
+    }
+
+    for note in &details.notes {
+        write!(message, "\n  = {note}").expect("writing to a String cannot fail");
+    }
+
+    syn::Error::new(span, message).into()
+}
+
+/// The length in bytes of the word or quoted string at the start of `sql`,
+/// or else of its first character.
+fn token_len(sql: &str) -> usize {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    if let Some(quote @ ('"' | '\'' | '`')) = sql.chars().next() {
+        if let Some(end) = sql[1..].find(quote) {
+            return end + 2;
+        }
+    }
+
+    match sql.find(|c| !is_word(c)) {
+        Some(0) => sql.chars().next().map_or(0, char::len_utf8),
+        Some(len) => len,
+        None => sql.len(),
+    }
+}
+
+/// Renders the line of the query at `offset`, marking the `len` bytes from there:
+///
+/// ```text
+///   --> queries/accounts.sql:2:6
+///    |
+///  2 | FROM acounts
+///    |      ^^^^^^^
+/// ```
+fn snippet(input: &QueryMacroInput, offset: usize, len: usize) -> String {
+    let sql = &input.sql;
+
+    let line_start = sql[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = sql[offset..].find('\n').map_or(sql.len(), |i| offset + i);
+
+    let line = sql[..offset].matches('\n').count() + 1;
+    let column = sql[line_start..offset].chars().count() + 1;
+
+    let location = match &input.file_path {
+        Some(path) if !input.sql_rewritten => format!("{path}:{line}:{column}"),
+        _ => format!("line {line}, column {column} of the query"),
+    };
+
+    // keep tabs so the marker lines up
+    let indent = sql[line_start..offset]
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect::<String>();
+
+    let marker = "^".repeat(sql[offset..offset + len].chars().count());
+    let gutter = " ".repeat(line.to_string().len());
+
+    format!(
+        "\n{gutter}--> {location}\n{gutter} |\n{line} | {}\n{gutter} | {indent}{marker}",
+        sql[line_start..line_end].trim_end()
+    )
+}
+
+/// The span of the `len` bytes at `offset` into the value of `lits` (concatenated),
+/// if the compiler supports spans inside a literal.
+fn literal_subspan(lits: &[LitStr], mut offset: usize, len: usize) -> Option<Span> {
+    for lit in lits {
+        let value_len = lit.value().len();
+
+        if offset >= value_len {
+            offset -= value_len;
+            continue;
+        }
+
+        let token = lit.token();
+        let repr = token.to_string();
+
+        // Escapes make offsets into the value differ from those into the source.
+        let start = if repr.starts_with('r') {
+            repr.find('"')? + 1
+        } else if !repr.contains('\\') {
+            1
+        } else {
+            return None;
+        };
+
+        return token.subspan(start + offset..start + offset + len);
+    }
+
+    None
+}
@@ -0,0 +1,616 @@
+//! `query_dyn!()`: a query assembled at runtime from a fixed set of optional clauses,
+//! where every combination of the clauses is checked at compile time.
+
+use std::path::Path;
+
+use either::Either;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use sqlx_core::column::Column;
+use sqlx_core::config::Config;
+use sqlx_core::describe::Describe;
+use sqlx_core::type_info::TypeInfo;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, ExprIf, ExprLit, Lit, LitStr, Stmt, Token, Type};
+
+use crate::database::{DatabaseExt, ParamStyle};
+use crate::query::data::QueryData;
+use crate::query::input::RecordType;
+use crate::query::{args, output, quote_record_struct, DescribeExt, QueryDataSource, Warnings};
+
+/// Every combination of the optional clauses is described, so this bounds the work done per
+/// invocation to `2^MAX_OPTIONAL_CLAUSES` queries.
+const MAX_OPTIONAL_CLAUSES: usize = 8;
+
+/// Macro input for `query_dyn!()` and `query_dyn_as!()`
+pub struct QueryDynInput {
+    pub(super) src_span: Span,
+
+    record_type: RecordType,
+
+    /// Attributes for the generated record type.
+    record_attrs: Vec<Attribute>,
+
+    /// The query, followed by the clauses appended to it, in order.
+    clauses: Vec<Clause>,
+}
+
+/// A piece of SQL which is part of the query if `condition` is true, or always if there is none.
+struct Clause {
+    condition: Option<Expr>,
+
+    /// The SQL as written, which identifies the query in the offline data.
+    source: String,
+
+    segments: Vec<Segment>,
+}
+
+enum Segment {
+    Sql(String),
+    /// `{name}`: binds the variable `name` to a parameter.
+    Param(Ident),
+}
+
+/// The query with a given set of optional clauses enabled.
+pub(super) struct Variant {
+    /// Bit `i` is set if the optional clause `i` is enabled.
+    mask: u32,
+
+    pub(super) src_sql: String,
+}
+
+impl QueryDynInput {
+    fn optional_clauses(&self) -> impl Iterator<Item = &Clause> {
+        self.clauses
+            .iter()
+            .filter(|clause| clause.condition.is_some())
+    }
+
+    /// Every combination of the optional clauses, ending with all of them enabled.
+    pub(super) fn variants(&self) -> Vec<Variant> {
+        let num_optional = self.optional_clauses().count();
+
+        (0..1u32 << num_optional)
+            .map(|mask| Variant {
+                mask,
+                src_sql: self.variant_sql(mask, |clause| clause.source.clone()),
+            })
+            .collect()
+    }
+
+    /// Joins the clauses enabled by `mask` with spaces.
+    fn variant_sql(&self, mask: u32, mut clause_sql: impl FnMut(&Clause) -> String) -> String {
+        let mut optional = 0;
+        let mut sql = String::new();
+
+        for clause in &self.clauses {
+            if clause.condition.is_some() {
+                let enabled = mask & (1 << optional) != 0;
+                optional += 1;
+
+                if !enabled {
+                    continue;
+                }
+            }
+
+            if !sql.is_empty() {
+                sql.push(' ');
+            }
+
+            sql.push_str(&clause_sql(clause));
+        }
+
+        sql
+    }
+
+    /// The SQL of the variant, with its parameters in the native syntax of `DB`.
+    fn native_sql<DB: DatabaseExt>(&self, mask: u32) -> String {
+        let mut num_params = 0;
+
+        self.variant_sql(mask, |clause| {
+            let mut sql = String::new();
+
+            for segment in &clause.segments {
+                match segment {
+                    Segment::Sql(text) => sql.push_str(text),
+                    Segment::Param(_) => {
+                        num_params += 1;
+
+                        match DB::PARAM_STYLE {
+                            ParamStyle::Numbered => sql.push_str(&format!("${num_params}")),
+                            ParamStyle::Positional => sql.push('?'),
+                        }
+                    }
+                }
+            }
+
+            sql
+        })
+    }
+
+    fn num_params(&self, mask: u32) -> usize {
+        let mut optional = 0;
+
+        self.clauses
+            .iter()
+            .filter(|clause| {
+                if clause.condition.is_none() {
+                    return true;
+                }
+
+                optional += 1;
+                mask & (1 << (optional - 1)) != 0
+            })
+            .flat_map(|clause| &clause.segments)
+            .filter(|segment| matches!(segment, Segment::Param(_)))
+            .count()
+    }
+
+    /// Names the optional clauses that are disabled in `mask`, for error messages.
+    fn describe_variant(&self, mask: u32) -> String {
+        let disabled = self
+            .optional_clauses()
+            .enumerate()
+            .filter(|(i, _)| mask & (1 << i) == 0)
+            .map(|(_, clause)| format!("`{}`", clause.source))
+            .collect::<Vec<_>>();
+
+        if disabled.is_empty() {
+            "with all clauses".to_string()
+        } else {
+            format!("without {}", disabled.join(", "))
+        }
+    }
+}
+
+impl Parse for QueryDynInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut source: Option<LitStr> = None;
+        let mut clause_exprs = Vec::new();
+        let mut record_type = RecordType::Generated;
+        let mut record_attrs = Vec::new();
+
+        let mut expect_comma = false;
+
+        while !input.is_empty() {
+            if expect_comma {
+                let _ = input.parse::<syn::token::Comma>()?;
+            }
+
+            let key: Ident = input.parse()?;
+
+            let _ = input.parse::<syn::token::Eq>()?;
+
+            if key == "source" {
+                source = Some(input.parse()?);
+            } else if key == "clauses" {
+                let content;
+                syn::bracketed!(content in input);
+                clause_exprs = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?
+                    .into_iter()
+                    .collect();
+            } else if key == "record" {
+                record_type = RecordType::Given(input.parse()?);
+            } else if key == "record_from_row" {
+                record_type = RecordType::FromRow(input.parse()?);
+            } else if key == "attrs" {
+                let content;
+                syn::bracketed!(content in input);
+                record_attrs = content.call(Attribute::parse_outer)?;
+            } else {
+                let message = format!("unexpected input key: {key}");
+                return Err(syn::Error::new_spanned(key, message));
+            }
+
+            expect_comma = true;
+        }
+
+        let source = source.ok_or_else(|| input.error("expected `source` key"))?;
+
+        let mut clauses = vec![Clause::parse_sql(None, &source)?];
+
+        for expr in clause_exprs {
+            clauses.push(Clause::from_expr(expr)?);
+        }
+
+        let num_optional = clauses.iter().filter(|c| c.condition.is_some()).count();
+
+        if num_optional > MAX_OPTIONAL_CLAUSES {
+            return Err(syn::Error::new(
+                source.span(),
+                format!(
+                    "at most {MAX_OPTIONAL_CLAUSES} optional clauses are supported, \
+                     as every combination of them is checked; got {num_optional}"
+                ),
+            ));
+        }
+
+        Ok(QueryDynInput {
+            src_span: source.span(),
+            record_type,
+            record_attrs,
+            clauses,
+        })
+    }
+}
+
+impl Clause {
+    /// Either a string literal, or `if <condition> { "<string literal>" }`.
+    fn from_expr(expr: Expr) -> syn::Result<Self> {
+        match expr {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) => Self::parse_sql(None, &lit),
+            Expr::If(ExprIf {
+                cond,
+                then_branch,
+                else_branch: None,
+                ..
+            }) => match &then_branch.stmts[..] {
+                [Stmt::Expr(
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(lit), ..
+                    }),
+                    None,
+                )] => Self::parse_sql(Some(*cond), lit),
+                _ => Err(syn::Error::new_spanned(
+                    then_branch,
+                    "expected the body of an optional clause to be a single string literal",
+                )),
+            },
+            Expr::If(ExprIf {
+                else_branch: Some((else_token, _)),
+                ..
+            }) => Err(syn::Error::new_spanned(
+                else_token,
+                "`else` is not supported in an optional clause",
+            )),
+            other => Err(syn::Error::new_spanned(
+                other,
+                "expected a string literal or `if <condition> { \"<SQL>\" }`",
+            )),
+        }
+    }
+
+    /// Splits the SQL into text and `{name}` parameters, where `{{` and `}}` are a literal
+    /// `{` and `}`.
+    fn parse_sql(condition: Option<Expr>, lit: &LitStr) -> syn::Result<Self> {
+        let source = lit.value();
+
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut chars = source.char_indices().peekable();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '{' if chars.next_if(|&(_, c)| c == '{').is_some() => text.push('{'),
+                '}' if chars.next_if(|&(_, c)| c == '}').is_some() => text.push('}'),
+                '{' => {
+                    let Some(end) = source[i..].find('}').map(|end| i + end) else {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "unclosed `{` in query; use `{{` for a literal `{`",
+                        ));
+                    };
+
+                    let name = source[i + 1..end].trim();
+
+                    // the variable must resolve at the call site, so give it the literal's span
+                    let ident = syn::parse_str::<Ident>(name)
+                        .map(|ident| Ident::new(&ident.to_string(), lit.span()))
+                        .map_err(|_| {
+                            syn::Error::new(
+                                lit.span(),
+                                format!("expected a variable name in `{{{name}}}`"),
+                            )
+                        })?;
+
+                    if !text.is_empty() {
+                        segments.push(Segment::Sql(std::mem::take(&mut text)));
+                    }
+
+                    segments.push(Segment::Param(ident));
+
+                    while chars.next_if(|&(j, _)| j <= end).is_some() {}
+                }
+                '}' => {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "unmatched `}` in query; use `}}` for a literal `}`",
+                    ));
+                }
+                c => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            segments.push(Segment::Sql(text));
+        }
+
+        Ok(Clause {
+            condition,
+            source,
+            segments,
+        })
+    }
+}
+
+pub(super) fn expand_with<DB: DatabaseExt>(
+    config: &Config,
+    input: QueryDynInput,
+    data_sources: Vec<QueryDataSource>,
+    offline_dir: Option<&Path>,
+) -> crate::Result<TokenStream>
+where
+    Describe<DB>: DescribeExt,
+{
+    let variants = input.variants();
+
+    let mut variant_data = Vec::with_capacity(variants.len());
+
+    for (variant, data_source) in variants.iter().zip(data_sources) {
+        let sql = input.native_sql::<DB>(variant.mask);
+
+        let (query_data, save_dir): (QueryData<DB>, Option<&Path>) = match data_source {
+            QueryDataSource::Cached(dyn_data) => (QueryData::from_dyn_data(dyn_data)?, None),
+            QueryDataSource::Live { database_url, .. } => {
+                let describe = DB::describe_blocking(&sql, database_url, &config.drivers)?;
+                (
+                    QueryData::from_describe(&variant.src_sql, describe),
+                    offline_dir,
+                )
+            }
+            QueryDataSource::Migrations { migrations_dir } => {
+                let describe = DB::describe_from_migrations(&sql, &migrations_dir, config)?;
+                (QueryData::from_describe(&variant.src_sql, describe), None)
+            }
+        };
+
+        let num_parameters = match query_data.describe.parameters() {
+            Some(Either::Left(params)) => Some(params.len()),
+            Some(Either::Right(num)) => Some(num),
+            None => None,
+        };
+
+        let expected = input.num_params(variant.mask);
+
+        if let Some(num) = num_parameters.filter(|&num| num != expected) {
+            return Err(syn::Error::new(
+                input.src_span,
+                format!(
+                    "expected {num} parameters, got {expected} for the query {}",
+                    input.describe_variant(variant.mask)
+                ),
+            )
+            .into());
+        }
+
+        variant_data.push((variant, query_data, save_dir));
+    }
+
+    for (_, data, save_dir) in &variant_data {
+        if let Some(save_dir) = save_dir {
+            data.save_in(save_dir)?;
+        }
+    }
+
+    // the last variant has every clause enabled, so it is the one that binds every parameter
+    let (_, full, _) = variant_data.pop().expect("BUG: no query variants");
+    let mut describe = full.describe;
+
+    for (variant, data, _) in &variant_data {
+        let same_columns = data.describe.columns.len() == describe.columns.len()
+            && data
+                .describe
+                .columns
+                .iter()
+                .zip(&describe.columns)
+                .all(|(a, b)| a.name() == b.name() && a.type_info() == b.type_info());
+
+        if !same_columns {
+            return Err(syn::Error::new(
+                input.src_span,
+                format!(
+                    "the query {} returns different columns than with all clauses; \
+                     optional clauses must not change the columns of the query",
+                    input.describe_variant(variant.mask)
+                ),
+            )
+            .into());
+        }
+
+        // a column is nullable if it is nullable in any variant
+        for (merged, nullable) in describe.nullable.iter_mut().zip(&data.describe.nullable) {
+            *merged = match (*merged, *nullable) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            };
+        }
+    }
+
+    let mut warnings = Warnings::default();
+
+    let param_types = match &describe.parameters {
+        Some(Either::Left(params)) => params
+            .iter()
+            .enumerate()
+            .map(|(i, param_ty)| {
+                args::get_param_type::<DB>(param_ty, config, &mut warnings, i).map(Some)
+            })
+            .collect::<crate::Result<Vec<_>>>()?,
+        _ => vec![None; input.num_params(u32::MAX)],
+    };
+
+    let args_tokens = quote_dyn_args::<DB>(&input, &param_types);
+
+    let sql_tokens = quote_dyn_sql::<DB>(&input, &variants);
+
+    let query_args = format_ident!("query_args");
+    let query_sql = quote!(query_sql);
+    let db_path = DB::db_path();
+
+    let output = if describe.columns().iter().all(|it| it.type_info().is_void()) {
+        quote! {
+            ::sqlx::__query_with_result::<#db_path, _>(#query_sql, #query_args)
+        }
+    } else {
+        match &input.record_type {
+            RecordType::Generated => {
+                let columns = output::columns_to_rust::<DB>(&describe, config, &mut warnings)?;
+
+                let record_name: Type = syn::parse_str("Record").unwrap();
+
+                let mut record_tokens =
+                    quote_record_struct(config, &record_name, &input.record_attrs, &columns)?;
+
+                record_tokens.extend(output::quote_query_as_sql::<DB>(
+                    &query_sql,
+                    true,
+                    &record_name,
+                    &query_args,
+                    &columns,
+                ));
+
+                record_tokens
+            }
+            RecordType::Given(out_ty) => {
+                let columns = output::columns_to_rust::<DB>(&describe, config, &mut warnings)?;
+
+                output::quote_query_as_sql::<DB>(&query_sql, true, out_ty, &query_args, &columns)
+            }
+            RecordType::FromRow(out_ty) => output::quote_query_as_from_row::<DB>(
+                &query_sql,
+                true,
+                out_ty,
+                &query_args,
+                &describe,
+                config,
+                &mut warnings,
+            ),
+            RecordType::Scalar => {
+                return Err("`query_dyn!()` does not support scalar queries".into());
+            }
+        }
+    };
+
+    let warnings_out = warnings.to_token_stream();
+
+    let ret_tokens = quote! {
+        {
+            #[allow(clippy::all)]
+            {
+                use ::sqlx::Arguments as _;
+
+                #warnings_out
+
+                #args_tokens
+
+                #sql_tokens
+
+                #output
+            }
+        }
+    };
+
+    Ok(ret_tokens)
+}
+
+/// Evaluates the conditions of the optional clauses, and binds the parameters of the enabled
+/// clauses to `query_args`. The enabled clauses are recorded in `query_variant`.
+fn quote_dyn_args<DB: DatabaseExt>(
+    input: &QueryDynInput,
+    param_types: &[Option<TokenStream>],
+) -> TokenStream {
+    let db_path = DB::db_path();
+
+    let mut param_types = param_types.iter();
+    let mut optional = 0u32;
+
+    let mut tokens = quote! {
+        let query_args = ::core::result::Result::<_, ::sqlx::error::BoxDynError>::Ok(
+            <#db_path as ::sqlx::database::Database>::Arguments::default()
+        );
+    };
+
+    if input.optional_clauses().next().is_some() {
+        tokens.extend(quote! {
+            let mut query_variant = 0u32;
+        });
+    }
+
+    for clause in &input.clauses {
+        let mut add_args = TokenStream::new();
+
+        for segment in &clause.segments {
+            let Segment::Param(ident) = segment else {
+                continue;
+            };
+
+            let check = param_types
+                .next()
+                .and_then(Option::as_ref)
+                .map(|param_ty| args::quote_param_check(ident.span(), &quote!(value), param_ty));
+
+            add_args.extend(quote! {
+                let query_args = {
+                    let value = &(#ident);
+
+                    #check
+
+                    query_args.and_then(move |mut query_args| {
+                        query_args.add(value).map(move |()| query_args)
+                    })
+                };
+            });
+        }
+
+        tokens.extend(match &clause.condition {
+            None => add_args,
+            Some(condition) => {
+                let bit = 1u32 << optional;
+                optional += 1;
+
+                quote! {
+                    let query_args = if #condition {
+                        query_variant |= #bit;
+
+                        #add_args
+
+                        query_args
+                    } else {
+                        query_args
+                    };
+                }
+            }
+        })
+    }
+
+    tokens
+}
+
+/// Selects the SQL of the variant recorded in `query_variant` as `query_sql`.
+fn quote_dyn_sql<DB: DatabaseExt>(input: &QueryDynInput, variants: &[Variant]) -> TokenStream {
+    if let [variant] = variants {
+        let sql = input.native_sql::<DB>(variant.mask);
+
+        return quote! {
+            let query_sql = #sql;
+        };
+    }
+
+    let arms = variants.iter().map(|variant| {
+        let mask = variant.mask;
+        let sql = input.native_sql::<DB>(mask);
+
+        quote!(#mask => #sql,)
+    });
+
+    quote! {
+        let query_sql = match query_variant {
+            #(#arms)*
+            _ => ::std::unreachable!(),
+        };
+    }
+}
@@ -3,7 +3,7 @@ use std::fs;
 use proc_macro2::{Ident, Span};
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{Expr, LitBool, LitStr, Token};
+use syn::{Attribute, Expr, LitBool, LitStr, Token};
 use syn::{ExprArray, Type};
 
 /// Macro input shared by `query!()` and `query_file!()`
@@ -12,22 +12,57 @@ pub struct QueryMacroInput {
 
     pub(super) src_span: Span,
 
+    /// The string literals the query was written in, if it was not read from a file.
+    pub(super) src_lits: Vec<LitStr>,
+
     pub(super) record_type: RecordType,
 
+    /// Attributes for the generated record type, e.g. `#[derive(serde::Serialize)]`.
+    pub(super) record_attrs: Vec<Attribute>,
+
     pub(super) arg_exprs: Vec<Expr>,
 
+    /// The argument bound to each parameter of the query, in order.
+    pub(super) param_args: Vec<ParamArg>,
+
+    /// `true` if `sql` was rewritten from the source, e.g. to replace named parameters.
+    pub(super) sql_rewritten: bool,
+
     pub(super) checked: bool,
 
     pub(super) file_path: Option<String>,
+
+    /// `true` for `query_file_script!()`: the file holds several statements, which are checked
+    /// and executed in order, with the output of the last one returned.
+    pub(super) script: bool,
+}
+
+/// The argument bound to a parameter of the query.
+#[derive(Copy, Clone)]
+pub(super) struct ParamArg {
+    /// The index into `QueryMacroInput::arg_exprs`.
+    pub(super) arg: usize,
+
+    /// For a list parameter (`?...`), the byte offset of its placeholder in `QueryMacroInput::sql`.
+    /// Each element of the argument is bound, and the placeholder expanded to match at runtime.
+    pub(super) list_at: Option<usize>,
+}
+
+impl ParamArg {
+    pub(super) fn new(arg: usize) -> Self {
+        ParamArg { arg, list_at: None }
+    }
 }
 
 enum QuerySrc {
-    String(String),
+    String(Vec<LitStr>),
     File(String),
 }
 
 pub enum RecordType {
     Given(Type),
+    /// Rows are mapped with the `FromRow` impl of the type.
+    FromRow(Type),
     Scalar,
     Generated,
 }
@@ -37,7 +72,9 @@ impl Parse for QueryMacroInput {
         let mut query_src: Option<(QuerySrc, Span)> = None;
         let mut args: Option<Vec<Expr>> = None;
         let mut record_type = RecordType::Generated;
+        let mut record_attrs = Vec::new();
         let mut checked = true;
+        let mut script = false;
 
         let mut expect_comma = false;
 
@@ -51,12 +88,11 @@ impl Parse for QueryMacroInput {
             let _ = input.parse::<syn::token::Eq>()?;
 
             if key == "source" {
-                let span = input.span();
-                let query_str = Punctuated::<LitStr, Token![+]>::parse_separated_nonempty(input)?
-                    .iter()
-                    .map(LitStr::value)
-                    .collect();
-                query_src = Some((QuerySrc::String(query_str), span));
+                let lits = Punctuated::<LitStr, Token![+]>::parse_separated_nonempty(input)?;
+                // the span of the literal itself, rather than of the `macro_rules!` fragment
+                // wrapping it, so that named parameters resolve in the caller's scope
+                let span = lits[0].span();
+                query_src = Some((QuerySrc::String(lits.into_iter().collect()), span));
             } else if key == "source_file" {
                 let lit_str = input.parse::<LitStr>()?;
                 query_src = Some((QuerySrc::File(lit_str.value()), lit_str.span()));
@@ -69,6 +105,12 @@ impl Parse for QueryMacroInput {
                 }
 
                 record_type = RecordType::Given(input.parse()?);
+            } else if key == "record_from_row" {
+                if !matches!(record_type, RecordType::Generated) {
+                    return Err(input.error("colliding `scalar` or `record` key"));
+                }
+
+                record_type = RecordType::FromRow(input.parse()?);
             } else if key == "scalar" {
                 if !matches!(record_type, RecordType::Generated) {
                     return Err(input.error("colliding `scalar` or `record` key"));
@@ -79,9 +121,16 @@ impl Parse for QueryMacroInput {
                 // of the column in SQL
                 input.parse::<syn::Token![_]>()?;
                 record_type = RecordType::Scalar;
+            } else if key == "attrs" {
+                let content;
+                syn::bracketed!(content in input);
+                record_attrs = content.call(Attribute::parse_outer)?;
             } else if key == "checked" {
                 let lit_bool = input.parse::<LitBool>()?;
                 checked = lit_bool.value;
+            } else if key == "script" {
+                let lit_bool = input.parse::<LitBool>()?;
+                script = lit_bool.value;
             } else {
                 let message = format!("unexpected input key: {key}");
                 return Err(syn::Error::new_spanned(key, message));
@@ -95,15 +144,27 @@ impl Parse for QueryMacroInput {
 
         let arg_exprs = args.unwrap_or_default();
 
+        let param_args = (0..arg_exprs.len()).map(ParamArg::new).collect();
+
         let file_path = src.file_path(src_span)?;
 
+        let src_lits = match &src {
+            QuerySrc::String(lits) => lits.clone(),
+            QuerySrc::File(_) => Vec::new(),
+        };
+
         Ok(QueryMacroInput {
             sql: src.resolve(src_span)?,
             src_span,
+            src_lits,
             record_type,
+            record_attrs,
             arg_exprs,
+            param_args,
+            sql_rewritten: false,
             checked,
             file_path,
+            script,
         })
     }
 }
@@ -112,7 +173,7 @@ impl QuerySrc {
     /// If the query source is a file, read it to a string. Otherwise return the query string.
     fn resolve(self, source_span: Span) -> syn::Result<String> {
         match self {
-            QuerySrc::String(string) => Ok(string),
+            QuerySrc::String(lits) => Ok(lits.iter().map(LitStr::value).collect()),
             QuerySrc::File(file) => read_file_src(&file, source_span),
         }
     }
@@ -1,4 +1,5 @@
 use sqlx_core::config::Config;
+use std::collections::BTreeMap;
 use std::hash::{BuildHasherDefault, DefaultHasher};
 use std::io;
 use std::path::{Path, PathBuf};
@@ -16,6 +17,8 @@ pub struct Metadata {
 
 pub struct MacrosEnv {
     pub database_url: Option<String>,
+    /// The database URL for each backend in `macros.any-backends` that has one set.
+    pub any_database_urls: BTreeMap<Box<str>, String>,
     pub offline_dir: Option<PathBuf>,
     pub offline: Option<bool>,
 }
@@ -104,6 +107,7 @@ fn load_env(
 
     let mut from_dotenv = MacrosEnv {
         database_url: None,
+        any_database_urls: BTreeMap::new(),
         offline_dir: None,
         offline: None,
     };
@@ -138,15 +142,32 @@ fn load_env(
                 _ if name == config.common.database_url_var() => {
                     from_dotenv.database_url = Some(val)
                 }
-                _ => continue,
+                _ => {
+                    for (backend, var) in &config.macros.any_backends {
+                        if name == **var {
+                            from_dotenv
+                                .any_database_urls
+                                .insert(backend.clone(), val.clone());
+                        }
+                    }
+                }
             }
         }
     }
 
+    let mut any_database_urls = from_dotenv.any_database_urls;
+
+    for (backend, var) in &config.macros.any_backends {
+        if let Some(url) = crate::env_opt(var)? {
+            any_database_urls.insert(backend.clone(), url);
+        }
+    }
+
     Ok(Arc::new(MacrosEnv {
         // Make set variables take precedent
         database_url: crate::env_opt(config.common.database_url_var())?
             .or(from_dotenv.database_url),
+        any_database_urls,
         offline_dir: crate::env_opt("SQLX_OFFLINE_DIR")?
             .map(PathBuf::from)
             .or(from_dotenv.offline_dir),
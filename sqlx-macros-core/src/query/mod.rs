@@ -3,6 +3,7 @@ use std::path::{Path, PathBuf};
 use proc_macro2::TokenStream;
 use syn::Type;
 
+pub use dynamic::QueryDynInput;
 pub use input::QueryMacroInput;
 use quote::{format_ident, quote};
 use sqlx_core::database::Database;
@@ -17,12 +18,17 @@ use metadata::Metadata;
 use sqlx_core::config::Config;
 use url::Url;
 
+#[cfg(feature = "any")]
+mod any;
 mod args;
 mod cache;
 mod data;
+mod diagnostic;
+mod dynamic;
 mod input;
 mod metadata;
 mod output;
+mod script;
 
 #[derive(Copy, Clone)]
 pub struct QueryDriver {
@@ -30,6 +36,20 @@ pub struct QueryDriver {
     url_schemes: &'static [&'static str],
     expand:
         fn(&Config, QueryMacroInput, QueryDataSource, Option<&Path>) -> crate::Result<TokenStream>,
+    expand_dyn: fn(
+        &Config,
+        QueryDynInput,
+        Vec<QueryDataSource>,
+        Option<&Path>,
+    ) -> crate::Result<TokenStream>,
+    expand_script: fn(
+        &Config,
+        QueryMacroInput,
+        Vec<QueryDataSource>,
+        Option<&Path>,
+    ) -> crate::Result<TokenStream>,
+    #[cfg(feature = "any")]
+    describe_any: any::DescribeAny,
 }
 
 impl QueryDriver {
@@ -41,6 +61,10 @@ impl QueryDriver {
             db_name: DB::NAME,
             url_schemes: DB::URL_SCHEMES,
             expand: expand_with::<DB>,
+            expand_dyn: dynamic::expand_with::<DB>,
+            expand_script: script::expand_with::<DB>,
+            #[cfg(feature = "any")]
+            describe_any: any::describe_with::<DB>,
         }
     }
 }
@@ -50,6 +74,10 @@ pub enum QueryDataSource<'a> {
         database_url_parsed: Url,
     },
     Cached(DynQueryData),
+    /// Queries are checked against a new database with these migrations applied.
+    Migrations {
+        migrations_dir: PathBuf,
+    },
 }
 
 impl<'a> QueryDataSource<'a> {
@@ -67,6 +95,7 @@ impl<'a> QueryDataSource<'a> {
                 ..
             } => driver.url_schemes.contains(&database_url_parsed.scheme()),
             Self::Cached(dyn_data) => dyn_data.db_name == driver.db_name,
+            Self::Migrations { .. } => driver.db_name == "SQLite",
         }
     }
 }
@@ -78,21 +107,187 @@ pub fn expand_input<'a>(
 
     let metadata_env = metadata.env()?;
 
-    let data_source = match &*metadata_env {
-        MacrosEnv {
-            offline: None | Some(false),
-            database_url: Some(db_url),
-            ..
+    if input.script {
+        return script::expand_input(input, drivers, &metadata, &metadata_env);
+    }
+
+    if !metadata.config.macros.any_backends.is_empty() {
+        #[cfg(feature = "any")]
+        return any::expand_input(input, drivers, &metadata, &metadata_env);
+
+        #[cfg(not(feature = "any"))]
+        return Err(
+            "`macros.any-backends` is set in sqlx.toml but the `any` feature is not enabled".into(),
+        );
+    }
+
+    let data_source = data_source_for(&metadata, &metadata_env, &input.sql)?;
+
+    for driver in drivers {
+        if data_source.matches_driver(driver) {
+            let tracked = quote_tracked_migrations(&metadata.config, &data_source)?;
+
+            let expanded = (driver.expand)(
+                &metadata.config,
+                input,
+                data_source,
+                metadata_env.offline_dir.as_deref(),
+            )?;
+
+            return Ok(quote! {{ #tracked #expanded }});
+        }
+    }
+
+    Err(no_driver_error(&data_source))
+}
+
+pub fn expand_dyn_input<'a>(
+    input: QueryDynInput,
+    drivers: impl IntoIterator<Item = &'a QueryDriver>,
+) -> crate::Result<TokenStream> {
+    let metadata = metadata::try_for_crate()?;
+
+    let metadata_env = metadata.env()?;
+
+    if !metadata.config.macros.any_backends.is_empty() {
+        return Err(syn::Error::new(
+            input.src_span,
+            "`query_dyn!()` is not supported with `macros.any-backends`",
+        )
+        .into());
+    }
+
+    let data_sources = input
+        .variants()
+        .iter()
+        .map(|variant| data_source_for(&metadata, &metadata_env, &variant.src_sql))
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    let first = &data_sources[0];
+
+    for driver in drivers {
+        if first.matches_driver(driver) {
+            if let Some(other) = data_sources.iter().find(|it| !it.matches_driver(driver)) {
+                return Err(no_driver_error(other));
+            }
+
+            let tracked = quote_tracked_migrations(&metadata.config, first)?;
+
+            let expanded = (driver.expand_dyn)(
+                &metadata.config,
+                input,
+                data_sources,
+                metadata_env.offline_dir.as_deref(),
+            )?;
+
+            return Ok(quote! {{ #tracked #expanded }});
         }
+    }
+
+    Err(no_driver_error(first))
+}
+
+/// Determine where to get the data for `sql` from: a database, or the cached data for the query.
+fn data_source_for<'a>(
+    metadata: &Metadata,
+    metadata_env: &'a MacrosEnv,
+    sql: &str,
+) -> crate::Result<QueryDataSource<'a>> {
+    if metadata.config.macros.sqlite_from_migrations {
+        return Ok(migrations_source(metadata));
+    }
+
+    live_or_cached(
+        metadata,
+        metadata_env,
+        metadata_env.database_url.as_deref(),
+        metadata.config.common.database_url_var(),
+        &format!("query-{}.json", hash_string(sql)),
+        sql,
+    )
+}
+
+fn migrations_source(metadata: &Metadata) -> QueryDataSource<'static> {
+    QueryDataSource::Migrations {
+        migrations_dir: metadata
+            .manifest_dir
+            .join(metadata.config.migrate.migrations_dir()),
+    }
+}
+
+/// Includes each migration file if queries are checked against the migrations, which tells the
+/// compiler to check them again when a migration changes, like `migrate!()` does.
+///
+/// Migrations added later are not tracked; see `macros.sqlite-from-migrations` in sqlx.toml.
+fn quote_tracked_migrations(
+    config: &Config,
+    data_source: &QueryDataSource,
+) -> crate::Result<TokenStream> {
+    let QueryDataSource::Migrations { migrations_dir } = data_source else {
+        return Ok(TokenStream::new());
+    };
+
+    #[cfg(feature = "_sqlite")]
+    {
+        let migrations = sqlx_core::migrate::resolve_blocking_with_config(
+            migrations_dir,
+            &config.migrate.to_resolve_config(),
+        )?;
+
+        let paths = migrations
+            .iter()
+            .map(|(_, path)| {
+                path.to_str().ok_or_else(|| {
+                    format!(
+                        "migration path cannot be represented as a string: {}",
+                        path.display()
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(quote! {
+            #(const _: &[u8] = include_bytes!(#paths);)*
+        })
+    }
+
+    // There is no driver to check the queries against the migrations, which is reported instead.
+    #[cfg(not(feature = "_sqlite"))]
+    {
+        let _ = (config, migrations_dir);
+        Ok(TokenStream::new())
+    }
+}
+
+/// Use the database at `database_url` (read from `database_url_var`) for `sql` if the build is
+/// online, or else the data cached for it in `filename`.
+fn live_or_cached<'a>(
+    metadata: &Metadata,
+    metadata_env: &MacrosEnv,
+    database_url: Option<&'a str>,
+    database_url_var: &str,
+    filename: &str,
+    sql: &str,
+) -> crate::Result<QueryDataSource<'a>> {
+    Ok(match (metadata_env, database_url) {
+        (
+            MacrosEnv {
+                offline: None | Some(false),
+                ..
+            },
+            Some(db_url),
+        )
         // Allow `DATABASE_URL=''`
         if !db_url.is_empty() => QueryDataSource::live(db_url)?,
-        MacrosEnv {
-            offline,
-            offline_dir,
-            ..
-        } => {
+        (
+            MacrosEnv {
+                offline,
+                offline_dir,
+                ..
+            },
+            _,
+        ) => {
             // Try load the cached query metadata file.
-            let filename = format!("query-{}.json", hash_string(&input.sql));
 
             // Check SQLX_OFFLINE_DIR, then local .sqlx, then workspace .sqlx.
             let dirs = [
@@ -103,70 +298,92 @@ pub fn expand_input<'a>(
 
             let Some(data_file_path) = dirs
                 .iter()
-                .filter_map(|path| path(&metadata, offline_dir.as_deref()))
-                .map(|path| path.join(&filename))
+                .filter_map(|path| path(metadata, offline_dir.as_deref()))
+                .map(|path| path.join(filename))
                 .find(|path| path.exists())
             else {
                 return Err(
                     if offline.unwrap_or(false) {
-                        "`SQLX_OFFLINE=true` but there is no cached data for this query, run `cargo sqlx prepare` to update the query cache or unset `SQLX_OFFLINE`"
+                        "`SQLX_OFFLINE=true` but there is no cached data for this query, run `cargo sqlx prepare` to update the query cache or unset `SQLX_OFFLINE`".into()
                     } else {
-                        "set `DATABASE_URL` to use query macros online, or run `cargo sqlx prepare` to update the query cache"
-                    }.into()
+                        format!("set `{database_url_var}` to use query macros online, or run `cargo sqlx prepare` to update the query cache").into()
+                    }
                 );
             };
 
-            QueryDataSource::Cached(DynQueryData::from_data_file(&data_file_path, &input.sql)?)
-        }
-    };
-
-    for driver in drivers {
-        if data_source.matches_driver(driver) {
-            return (driver.expand)(
-                &metadata.config,
-                input,
-                data_source,
-                metadata_env.offline_dir.as_deref(),
-            );
+            QueryDataSource::Cached(DynQueryData::from_data_file(&data_file_path, sql)?)
         }
-    }
+    })
+}
 
+fn no_driver_error(data_source: &QueryDataSource) -> crate::Error {
     match data_source {
         QueryDataSource::Live {
             database_url_parsed,
             ..
-        } => Err(format!(
+        } => format!(
             "no database driver found matching URL scheme {:?}; the corresponding Cargo feature may need to be enabled", 
             database_url_parsed.scheme()
-        ).into()),
+        ).into(),
         QueryDataSource::Cached(data) => {
-            Err(format!(
+            format!(
                 "found cached data for database {:?} but no matching driver; the corresponding Cargo feature may need to be enabled",
                 data.db_name
-            ).into())
+            ).into()
+        }
+        QueryDataSource::Migrations { .. } => {
+            "`macros.sqlite-from-migrations` is set in sqlx.toml but the SQLite driver is not enabled".into()
         }
     }
 }
 
 fn expand_with<DB: DatabaseExt>(
     config: &Config,
-    input: QueryMacroInput,
+    mut input: QueryMacroInput,
     data_source: QueryDataSource,
     offline_dir: Option<&Path>,
 ) -> crate::Result<TokenStream>
 where
     Describe<DB>: DescribeExt,
 {
-    let (query_data, save_dir): (QueryData<DB>, Option<&Path>) = match data_source {
+    // Query data is saved under the query as written, before it is rewritten for `DB`.
+    let src_sql = input.sql.clone();
+
+    args::expand_named_params::<DB>(&mut input)?;
+    args::expand_list_params::<DB>(&mut input)?;
+
+    let (query_data, save_dir) =
+        query_data_for::<DB>(config, &src_sql, &input.sql, data_source, offline_dir)
+            .map_err(|e| diagnostic::describe_error::<DB>(e, &input))?;
+
+    expand_with_data(config, input, query_data, save_dir)
+}
+
+/// Get the data for `sql` (written as `src_sql`) from `data_source`,
+/// and the directory to save it in for offline builds, if any.
+fn query_data_for<'a, DB: DatabaseExt>(
+    config: &Config,
+    src_sql: &str,
+    sql: &str,
+    data_source: QueryDataSource,
+    offline_dir: Option<&'a Path>,
+) -> crate::Result<(QueryData<DB>, Option<&'a Path>)>
+where
+    Describe<DB>: DescribeExt,
+{
+    Ok(match data_source {
         // If the build is offline, the cache is our input so it's pointless to also write data for it.
         QueryDataSource::Cached(dyn_data) => (QueryData::from_dyn_data(dyn_data)?, None),
         QueryDataSource::Live { database_url, .. } => {
-            let describe = DB::describe_blocking(&input.sql, database_url, &config.drivers)?;
-            (QueryData::from_describe(&input.sql, describe), offline_dir)
+            let describe = DB::describe_blocking(sql, database_url, &config.drivers)?;
+            (QueryData::from_describe(src_sql, describe), offline_dir)
         }
-    };
-
-    expand_with_data(config, input, query_data, save_dir)
+        // The migrations are the input, so there is no need to save data for offline builds.
+        QueryDataSource::Migrations { migrations_dir } => {
+            let describe = DB::describe_from_migrations(sql, &migrations_dir, config)?;
+            (QueryData::from_describe(src_sql, describe), None)
+        }
+    })
 }
 
 // marker trait for `Describe` that lets us conditionally require it to be `Serialize + Deserialize`
@@ -183,6 +400,71 @@ struct Warnings {
     ambiguous_numeric: bool,
 }
 
+impl Warnings {
+    /// Statements which emit the warnings at runtime, to surface them from the macro expansion.
+    fn to_token_stream(&self) -> TokenStream {
+        let mut warnings_out = TokenStream::new();
+
+        if self.ambiguous_datetime {
+            // Warns if the date-time crate is inferred but both `chrono` and `time` are enabled
+            warnings_out.extend(quote! {
+                ::sqlx::warn_on_ambiguous_inferred_date_time_crate();
+            });
+        }
+
+        if self.ambiguous_numeric {
+            // Warns if the numeric crate is inferred but both `bigdecimal` and `rust_decimal` are enabled
+            warnings_out.extend(quote! {
+                ::sqlx::warn_on_ambiguous_inferred_numeric_crate();
+            });
+        }
+
+        warnings_out
+    }
+}
+
+/// The definition of the anonymous record struct generated for `query!()`.
+fn quote_record_struct(
+    config: &Config,
+    record_name: &Type,
+    record_attrs: &[syn::Attribute],
+    columns: &[output::RustColumn],
+) -> crate::Result<TokenStream> {
+    for rust_col in columns {
+        if rust_col.type_.is_wildcard() {
+            return Err(
+                "wildcard overrides are only allowed with an explicit record type, \
+                 e.g. `query_as!()` and its variants"
+                    .into(),
+            );
+        }
+    }
+
+    let record_fields = columns
+        .iter()
+        .map(|output::RustColumn { ident, type_, .. }| quote!(#ident: #type_,));
+
+    let derives = config
+        .macros
+        .record_derives
+        .iter()
+        .map(|derive| {
+            syn::parse_str::<syn::Path>(derive).map_err(|e| {
+                format!("invalid derive {derive:?} in `macros.record-derives` in sqlx.toml: {e}")
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(quote! {
+        #[derive(Debug, #(#derives),*)]
+        #(#record_attrs)*
+        #[allow(non_snake_case)]
+        struct #record_name {
+            #(#record_fields)*
+        }
+    })
+}
+
 fn expand_with_data<DB: DatabaseExt>(
     config: &Config,
     input: QueryMacroInput,
@@ -201,10 +483,13 @@ where
     };
 
     if let Some(num) = num_parameters {
-        if num != input.arg_exprs.len() {
-            return Err(
-                format!("expected {} parameters, got {}", num, input.arg_exprs.len()).into(),
-            );
+        if num != input.param_args.len() {
+            return Err(format!(
+                "expected {} parameters, got {}",
+                num,
+                input.param_args.len()
+            )
+            .into());
         }
     }
 
@@ -214,86 +499,9 @@ where
 
     let query_args = format_ident!("query_args");
 
-    let output = if data
-        .describe
-        .columns()
-        .iter()
-        .all(|it| it.type_info().is_void())
-    {
-        let db_path = DB::db_path();
-        let sql = &input.sql;
-
-        quote! {
-            ::sqlx::__query_with_result::<#db_path, _>(#sql, #query_args)
-        }
-    } else {
-        match input.record_type {
-            RecordType::Generated => {
-                let columns = output::columns_to_rust::<DB>(&data.describe, config, &mut warnings)?;
-
-                let record_name: Type = syn::parse_str("Record").unwrap();
-
-                for rust_col in &columns {
-                    if rust_col.type_.is_wildcard() {
-                        return Err(
-                            "wildcard overrides are only allowed with an explicit record type, \
-                             e.g. `query_as!()` and its variants"
-                                .into(),
-                        );
-                    }
-                }
-
-                let record_fields = columns
-                    .iter()
-                    .map(|output::RustColumn { ident, type_, .. }| quote!(#ident: #type_,));
-
-                let mut record_tokens = quote! {
-                    #[derive(Debug)]
-                    #[allow(non_snake_case)]
-                    struct #record_name {
-                        #(#record_fields)*
-                    }
-                };
-
-                record_tokens.extend(output::quote_query_as::<DB>(
-                    &input,
-                    &record_name,
-                    &query_args,
-                    &columns,
-                ));
+    let output = quote_output(config, &input, &data.describe, &mut warnings, &query_args)?;
 
-                record_tokens
-            }
-            RecordType::Given(ref out_ty) => {
-                let columns = output::columns_to_rust::<DB>(&data.describe, config, &mut warnings)?;
-
-                output::quote_query_as::<DB>(&input, out_ty, &query_args, &columns)
-            }
-            RecordType::Scalar => output::quote_query_scalar::<DB>(
-                &input,
-                config,
-                &mut warnings,
-                &query_args,
-                &data.describe,
-            )?,
-        }
-    };
-
-    let mut warnings_out = TokenStream::new();
-
-    if warnings.ambiguous_datetime {
-        // Warns if the date-time crate is inferred but both `chrono` and `time` are enabled
-        warnings_out.extend(quote! {
-            ::sqlx::warn_on_ambiguous_inferred_date_time_crate();
-        });
-    }
-
-    if warnings.ambiguous_numeric {
-        // Warns if the numeric crate is inferred but both `bigdecimal` and `rust_decimal` are enabled
-        warnings_out.extend(quote! {
-            ::sqlx::warn_on_ambiguous_inferred_numeric_crate();
-        });
-    }
+    let warnings_out = warnings.to_token_stream();
 
     let ret_tokens = quote! {
         {
@@ -316,3 +524,101 @@ where
 
     Ok(ret_tokens)
 }
+
+/// The query expression for the output of `input`, mapping its rows to the record type
+/// if it returns any.
+fn quote_output<DB: DatabaseExt>(
+    config: &Config,
+    input: &QueryMacroInput,
+    describe: &Describe<DB>,
+    warnings: &mut Warnings,
+    query_args: &syn::Ident,
+) -> crate::Result<TokenStream> {
+    Ok(
+        if describe.columns().iter().all(|it| it.type_info().is_void()) {
+            let db_path = DB::db_path();
+            let sql = output::quote_sql::<DB>(input);
+
+            quote! {
+                ::sqlx::__query_with_result::<#db_path, _>(#sql, #query_args)
+            }
+        } else {
+            match input.record_type {
+                RecordType::Generated => {
+                    let columns = output::columns_to_rust::<DB>(describe, config, warnings)?;
+
+                    let record_name: Type = syn::parse_str("Record").unwrap();
+
+                    let mut record_tokens =
+                        quote_record_struct(config, &record_name, &input.record_attrs, &columns)?;
+
+                    record_tokens.extend(output::quote_query_as::<DB>(
+                        input,
+                        &record_name,
+                        query_args,
+                        &columns,
+                    ));
+
+                    record_tokens
+                }
+                RecordType::Given(ref out_ty) => {
+                    let columns = output::columns_to_rust::<DB>(describe, config, warnings)?;
+
+                    output::quote_query_as::<DB>(input, out_ty, query_args, &columns)
+                }
+                RecordType::FromRow(ref out_ty) => output::quote_query_as_from_row::<DB>(
+                    &output::quote_sql::<DB>(input),
+                    input.checked,
+                    out_ty,
+                    query_args,
+                    describe,
+                    config,
+                    warnings,
+                ),
+                RecordType::Scalar => {
+                    output::quote_query_scalar::<DB>(input, config, warnings, query_args, describe)?
+                }
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "_sqlite")]
+    #[test]
+    fn tracked_migrations() {
+        let migrations_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../tests/sqlite/migrations_reversible")
+            .canonicalize()
+            .unwrap();
+
+        let tracked = quote_tracked_migrations(
+            &Config::default(),
+            &QueryDataSource::Migrations {
+                migrations_dir: migrations_dir.clone(),
+            },
+        )
+        .unwrap()
+        .to_string();
+
+        // both the up and down migrations, since either is a change to the migrations
+        for file in [
+            "20220721124650_add_table.up.sql",
+            "20220721124650_add_table.down.sql",
+            "20220721125033_modify_column.up.sql",
+            "20220721125033_modify_column.down.sql",
+        ] {
+            let path = migrations_dir.join(file);
+            let path = path.to_str().unwrap();
+            let include = quote!(include_bytes!(#path)).to_string();
+
+            assert!(
+                tracked.contains(&include),
+                "{file} is not tracked: {tracked}"
+            );
+        }
+    }
+}
@@ -7,7 +7,8 @@ use sqlx_core::describe::Describe;
 
 use crate::database::DatabaseExt;
 
-use crate::query::{QueryMacroInput, Warnings};
+use crate::query::{args, QueryMacroInput, Warnings};
+use sqlx_core::config::macros::Nullability;
 use sqlx_core::config::Config;
 use sqlx_core::type_checking;
 use sqlx_core::type_checking::TypeChecking;
@@ -106,7 +107,7 @@ fn column_to_rust<DB: DatabaseExt>(
     let nullable = match nullability {
         ColumnNullabilityOverride::NonNull => false,
         ColumnNullabilityOverride::Nullable => true,
-        ColumnNullabilityOverride::None => describe.nullable(i).unwrap_or(true),
+        ColumnNullabilityOverride::None => inferred_nullable(describe, config, i),
     };
     let type_ = match (type_, nullable) {
         (ColumnTypeOverride::Exact(type_), false) => ColumnType::Exact(type_.to_token_stream()),
@@ -136,11 +137,43 @@ fn column_to_rust<DB: DatabaseExt>(
     })
 }
 
+/// Whether column `i` is nullable, absent an override in the query itself.
+///
+/// Overrides in `[macros]` take precedence over what the database reports.
+fn inferred_nullable<DB: DatabaseExt>(describe: &Describe<DB>, config: &Config, i: usize) -> bool {
+    let column = &describe.columns()[i];
+
+    let configured = match column.origin() {
+        ColumnOrigin::Table(origin) => config
+            .macros
+            .column_nullability(&origin.table, &origin.name),
+        _ => config.macros.function_nullability(column.name()),
+    };
+
+    match configured {
+        Some(nullability) => nullability == Nullability::Nullable,
+        None => describe.nullable(i).unwrap_or(true),
+    }
+}
+
 pub fn quote_query_as<DB: DatabaseExt>(
     input: &QueryMacroInput,
     out_ty: &Type,
     bind_args: &Ident,
     columns: &[RustColumn],
+) -> TokenStream {
+    let sql = quote_sql::<DB>(input);
+
+    quote_query_as_sql::<DB>(&sql, input.checked, out_ty, bind_args, columns)
+}
+
+/// Like [`quote_query_as()`], but for a query whose SQL is the expression `sql`.
+pub fn quote_query_as_sql<DB: DatabaseExt>(
+    sql: &TokenStream,
+    checked: bool,
+    out_ty: &Type,
+    bind_args: &Ident,
+    columns: &[RustColumn],
 ) -> TokenStream {
     let instantiations = columns.iter().enumerate().map(
         |(
@@ -149,7 +182,7 @@ pub fn quote_query_as<DB: DatabaseExt>(
                 var_name, type_, ..
             },
         )| {
-            match (input.checked, type_) {
+            match (checked, type_) {
                 // we guarantee the type is valid so we can skip the runtime check
                 (true, ColumnType::Exact(type_)) => quote! {
                     // binding to a `let` avoids confusing errors about
@@ -184,14 +217,6 @@ pub fn quote_query_as<DB: DatabaseExt>(
     let db_path = DB::db_path();
     let row_path = DB::row_path();
 
-    // if this query came from a file, use `include_str!()` to tell the compiler where it came from
-    let sql = if let Some(ref path) = &input.file_path {
-        quote::quote_spanned! { input.src_span => include_str!(#path) }
-    } else {
-        let sql = &input.sql;
-        quote! { #sql }
-    };
-
     quote! {
         ::sqlx::__query_with_result::<#db_path, _>(#sql, #bind_args).try_map(|row: #row_path| {
             use ::sqlx::Row as _;
@@ -203,6 +228,128 @@ pub fn quote_query_as<DB: DatabaseExt>(
     }
 }
 
+/// Like [`quote_query_as_sql()`], but maps each row with the `FromRow` impl of `out_ty`, which
+/// must be derived so that the columns it reads are checked against the query.
+pub fn quote_query_as_from_row<DB: DatabaseExt>(
+    sql: &TokenStream,
+    checked: bool,
+    out_ty: &Type,
+    bind_args: &Ident,
+    describe: &Describe<DB>,
+    config: &Config,
+    warnings: &mut Warnings,
+) -> TokenStream {
+    let db_path = DB::db_path();
+    let row_path = DB::row_path();
+
+    let mut checks = TokenStream::new();
+
+    if checked {
+        let names = describe.columns().iter().map(|column| column.name());
+
+        checks.extend(quote! {
+            const _: () = ::sqlx::__from_row::check_columns(
+                <#out_ty as ::sqlx::__from_row::FromRowColumns>::COLUMNS,
+                &[#(#names),*],
+            );
+        });
+
+        for (i, column) in describe.columns().iter().enumerate() {
+            let hash = crate::common::column_name_hash(column.name());
+
+            let type_ = get_column_type::<DB>(config, warnings, i, column);
+
+            let type_ = if inferred_nullable(describe, config, i) {
+                quote! { ::std::option::Option<#type_> }
+            } else {
+                type_
+            };
+
+            checks.extend(quote! {
+                ::sqlx::ty_match::match_nullable(
+                    (&::sqlx::ty_match::ColumnField::<#out_ty, #type_, #hash>::new()).field_type(),
+                    ::std::marker::PhantomData::<#type_>,
+                );
+            });
+        }
+    }
+
+    quote! {
+        {
+            use ::sqlx::ty_match::ColumnFieldExt as _;
+
+            #checks
+
+            ::sqlx::__query_with_result::<#db_path, _>(#sql, #bind_args).try_map(|row: #row_path| {
+                <#out_ty as ::sqlx::FromRow<'_, #row_path>>::from_row(&row)
+            })
+        }
+    }
+}
+
+/// Returns an expression for the SQL of the query, which is a `&'static str` unless it has list
+/// parameters to expand at runtime.
+pub fn quote_sql<DB: DatabaseExt>(input: &QueryMacroInput) -> TokenStream {
+    let sql = &input.sql;
+    let empty_list = DB::EMPTY_LIST;
+
+    let mut expand_lists = TokenStream::new();
+    let mut copied = 0;
+
+    for param_arg in &input.param_args {
+        let Some(list_at) = param_arg.list_at else {
+            continue;
+        };
+
+        let segment = &sql[copied..list_at];
+        let name = args::arg_name(param_arg.arg);
+        copied = list_at + 1;
+
+        expand_lists.extend(quote! {
+            query_sql.push_str(#segment);
+
+            // `IN ()` is not valid SQL, so an empty list is a subquery without rows
+            match #name.iter().count() {
+                0 => query_sql.push_str(#empty_list),
+                len => {
+                    query_sql.push('?');
+                    for _ in 1..len {
+                        query_sql.push_str(", ?");
+                    }
+                }
+            }
+        });
+    }
+
+    let sql = if expand_lists.is_empty() {
+        match &input.file_path {
+            // if this query came from a file, use `include_str!()` to tell the compiler where it came from
+            Some(path) if !input.sql_rewritten => {
+                return quote::quote_spanned! { input.src_span => include_str!(#path) };
+            }
+            _ => quote! { #sql },
+        }
+    } else {
+        let rest = &sql[copied..];
+
+        quote! {{
+            let mut query_sql = ::std::string::String::with_capacity(#sql.len());
+            #expand_lists
+            query_sql.push_str(#rest);
+            ::sqlx::AssertSqlSafe(query_sql)
+        }}
+    };
+
+    match &input.file_path {
+        // still include the file so the query is rebuilt when it changes
+        Some(path) => quote::quote_spanned! { input.src_span => {
+            const _: &str = include_str!(#path);
+            #sql
+        }},
+        None => sql,
+    }
+}
+
 pub fn quote_query_scalar<DB: DatabaseExt>(
     input: &QueryMacroInput,
     config: &Config,
@@ -225,7 +372,7 @@ pub fn quote_query_scalar<DB: DatabaseExt>(
         rust_col.type_.to_token_stream()
     } else if input.checked {
         let ty = get_column_type::<DB>(config, warnings, 0, &columns[0]);
-        if describe.nullable(0).unwrap_or(true) {
+        if inferred_nullable(describe, config, 0) {
             quote! { ::std::option::Option<#ty> }
         } else {
             ty
@@ -235,7 +382,7 @@ pub fn quote_query_scalar<DB: DatabaseExt>(
     };
 
     let db = DB::db_path();
-    let query = &input.sql;
+    let query = quote_sql::<DB>(input);
 
     Ok(quote! {
         ::sqlx::__query_scalar_with_result::<#db, #ty, _>(#query, #bind_args)
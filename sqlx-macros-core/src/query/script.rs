@@ -0,0 +1,306 @@
+//! `query_file_script!()`: checking each statement of a script in order,
+//! and typing the output of the last.
+
+use std::path::Path;
+
+use either::Either;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use sqlx_core::column::Column;
+use sqlx_core::config::Config;
+use sqlx_core::describe::Describe;
+use sqlx_core::type_info::TypeInfo;
+
+use crate::database::{DatabaseExt, ParamStyle};
+use crate::query::data::QueryData;
+use crate::query::input::{ParamArg, RecordType};
+use crate::query::metadata::{MacrosEnv, Metadata};
+use crate::query::{
+    args, output, DescribeExt, QueryDataSource, QueryDriver, QueryMacroInput, Warnings,
+};
+
+pub(super) fn expand_input<'a>(
+    input: QueryMacroInput,
+    drivers: impl IntoIterator<Item = &'a QueryDriver>,
+    metadata: &Metadata,
+    metadata_env: &MacrosEnv,
+) -> crate::Result<TokenStream> {
+    if !metadata.config.macros.any_backends.is_empty() {
+        return Err(syn::Error::new(
+            input.src_span,
+            "`query_file_script!()` is not supported with `macros.any-backends`",
+        )
+        .into());
+    }
+
+    if metadata.config.macros.sqlite_from_migrations {
+        return Err(syn::Error::new(
+            input.src_span,
+            "`query_file_script!()` is not supported with `macros.sqlite-from-migrations`",
+        )
+        .into());
+    }
+
+    if let Some((name, _)) = input.arg_exprs.iter().find_map(args::named_arg) {
+        return Err(syn::Error::new_spanned(
+            name,
+            "named arguments are not supported by `query_file_script!()`",
+        )
+        .into());
+    }
+
+    let statements = split_statements(&input.sql);
+
+    if statements.is_empty() {
+        return Err(syn::Error::new(input.src_span, "query script contains no statements").into());
+    }
+
+    let data_sources = statements
+        .iter()
+        .map(|statement| super::data_source_for(metadata, metadata_env, statement))
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    let first = &data_sources[0];
+
+    for driver in drivers {
+        if first.matches_driver(driver) {
+            if let Some(other) = data_sources.iter().find(|it| !it.matches_driver(driver)) {
+                return Err(super::no_driver_error(other));
+            }
+
+            return (driver.expand_script)(
+                &metadata.config,
+                input,
+                data_sources,
+                metadata_env.offline_dir.as_deref(),
+            );
+        }
+    }
+
+    Err(super::no_driver_error(first))
+}
+
+pub(super) fn expand_with<DB: DatabaseExt>(
+    config: &Config,
+    mut input: QueryMacroInput,
+    data_sources: Vec<QueryDataSource>,
+    offline_dir: Option<&Path>,
+) -> crate::Result<TokenStream>
+where
+    Describe<DB>: DescribeExt,
+{
+    let statements = split_statements(&input.sql)
+        .into_iter()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    // The statements are described on one connection, so each can see the effects of those
+    // before it. The data for each is cached separately.
+    let (data, save_dir) = match &data_sources[0] {
+        QueryDataSource::Live { database_url, .. } => {
+            let statements = statements.iter().map(String::as_str).collect::<Vec<_>>();
+            let described =
+                DB::describe_script_blocking(&statements, database_url, &config.drivers)?;
+
+            let data = statements
+                .iter()
+                .zip(described)
+                .map(|(statement, describe)| QueryData::<DB>::from_describe(statement, describe))
+                .collect::<Vec<_>>();
+
+            (data, offline_dir)
+        }
+        _ => {
+            let data = data_sources
+                .into_iter()
+                .zip(&statements)
+                .map(|(data_source, statement)| {
+                    let (data, _) = super::query_data_for::<DB>(
+                        config,
+                        statement,
+                        statement,
+                        data_source,
+                        None,
+                    )?;
+                    Ok(data)
+                })
+                .collect::<crate::Result<Vec<_>>>()?;
+
+            (data, None)
+        }
+    };
+
+    // With numbered parameters, each statement binds the arguments from the first;
+    // otherwise, each statement binds the arguments after those bound by the one before it.
+    let mut next_arg = 0;
+    let mut num_args = 0;
+
+    let mut inputs = Vec::with_capacity(statements.len());
+
+    for (statement, data) in statements.into_iter().zip(&data) {
+        let num_params = match data.describe.parameters() {
+            Some(Either::Left(params)) => params.len(),
+            Some(Either::Right(num)) => num,
+            None => 0,
+        };
+
+        let first_arg = match DB::PARAM_STYLE {
+            ParamStyle::Numbered => 0,
+            ParamStyle::Positional => next_arg,
+        };
+
+        next_arg = first_arg + num_params;
+        num_args = num_args.max(next_arg);
+
+        inputs.push(QueryMacroInput {
+            sql: statement,
+            src_span: input.src_span,
+            src_lits: Vec::new(),
+            record_type: RecordType::Generated,
+            record_attrs: Vec::new(),
+            arg_exprs: input.arg_exprs.clone(),
+            param_args: (first_arg..next_arg).map(ParamArg::new).collect(),
+            // each statement is quoted on its own, while still including the file
+            sql_rewritten: true,
+            checked: input.checked,
+            file_path: input.file_path.clone(),
+            script: false,
+        });
+    }
+
+    if num_args != input.arg_exprs.len() {
+        return Err(format!(
+            "expected {} parameters, got {}",
+            num_args,
+            input.arg_exprs.len()
+        )
+        .into());
+    }
+
+    let mut last = inputs.pop().expect("BUG: script has no statements");
+    last.record_type = std::mem::replace(&mut input.record_type, RecordType::Generated);
+    last.record_attrs = std::mem::take(&mut input.record_attrs);
+
+    let db_path = DB::db_path();
+    let query_args = format_ident!("query_args");
+    let mut warnings = Warnings::default();
+
+    let setup = inputs
+        .iter()
+        .zip(&data)
+        .map(|(input, data)| -> crate::Result<_> {
+            let args_tokens = args::quote_bound_args(input, config, &mut warnings, &data.describe)?;
+            let sql = output::quote_sql::<DB>(input);
+
+            Ok(quote! {{
+                #args_tokens
+
+                ::sqlx::__query_with_result::<#db_path, _>(#sql, #query_args)
+            }})
+        })
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    let last_data = data.last().expect("BUG: script has no statements");
+
+    let args_tokens = args::quote_bound_args(&last, config, &mut warnings, &last_data.describe)?;
+
+    // The last statement is always mapped, so that the script has one type either way.
+    let output = if last_data
+        .describe
+        .columns()
+        .iter()
+        .all(|it| it.type_info().is_void())
+    {
+        let sql = output::quote_sql::<DB>(&last);
+
+        quote! {
+            ::sqlx::__query_with_result::<#db_path, _>(#sql, #query_args)
+                .try_map(|_| ::core::result::Result::Ok(()))
+        }
+    } else {
+        super::quote_output(
+            config,
+            &last,
+            &last_data.describe,
+            &mut warnings,
+            &query_args,
+        )?
+    };
+
+    let warnings_out = warnings.to_token_stream();
+    let arg_bindings = args::quote_arg_bindings(&input);
+
+    let ret_tokens = quote! {
+        {
+            #[allow(clippy::all)]
+            {
+                use ::sqlx::Arguments as _;
+
+                #warnings_out
+
+                #arg_bindings
+
+                let setup = ::std::vec![#(#setup),*];
+
+                let query = {
+                    #args_tokens
+
+                    #output
+                };
+
+                ::sqlx::__query_script(setup, query)
+            }
+        }
+    };
+
+    if let Some(save_dir) = save_dir {
+        for data in &data {
+            data.save_in(save_dir)?;
+        }
+    }
+
+    Ok(ret_tokens)
+}
+
+/// Splits `sql` into its statements at each `;`, skipping over string literals,
+/// quoted identifiers, comments, and dollar-quoted strings.
+///
+/// Statements which are empty, or consist only of comments, are omitted.
+fn split_statements(sql: &str) -> Vec<&str> {
+    let bytes = sql.as_bytes();
+
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut has_code = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if let Some(end) = args::skip_quoted(bytes, i) {
+            // comments alone don't make a statement
+            has_code |= !(bytes[i..].starts_with(b"--") || bytes[i..].starts_with(b"/*"));
+            i = end;
+            continue;
+        }
+
+        match bytes[i] {
+            b';' => {
+                if has_code {
+                    statements.push(sql[start..i].trim());
+                }
+
+                start = i + 1;
+                has_code = false;
+            }
+            b if !b.is_ascii_whitespace() => has_code = true,
+            _ => (),
+        }
+
+        i += 1;
+    }
+
+    if has_code {
+        statements.push(sql[start..].trim());
+    }
+
+    statements
+}
@@ -145,7 +145,10 @@ fn expand_advanced(args: AttributeArgs, input: syn::ItemFn) -> crate::Result<Tok
 
     let migrations = match args.migrations {
         MigrationsOpt::ExplicitPath(path) => {
-            let migrator = crate::migrate::expand(Some(path))?;
+            let migrator = crate::migrate::expand(crate::migrate::MigrateInput {
+                path: Some(path),
+                rust_migrations: Vec::new(),
+            })?;
             quote! { args.migrator(&#migrator); }
         }
         MigrationsOpt::InferredPath if !inputs.is_empty() => {
@@ -154,7 +157,7 @@ fn expand_advanced(args: AttributeArgs, input: syn::ItemFn) -> crate::Result<Tok
             let resolved_path = crate::common::resolve_path(path, proc_macro2::Span::call_site())?;
 
             if resolved_path.is_dir() {
-                let migrator = crate::migrate::expand_with_path(&config, &resolved_path)?;
+                let migrator = crate::migrate::expand_with_path(&config, &resolved_path, &[])?;
                 quote! { args.migrator(&#migrator); }
             } else {
                 quote! {}
@@ -22,6 +22,24 @@ pub fn expand_query(input: TokenStream) -> TokenStream {
     }
 }
 
+#[cfg(feature = "macros")]
+#[proc_macro]
+pub fn expand_query_dyn(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as query::QueryDynInput);
+
+    match query::expand_dyn_input(input, FOSS_DRIVERS) {
+        Ok(ts) => ts.into(),
+        Err(e) => {
+            if let Some(parse_err) = e.downcast_ref::<syn::Error>() {
+                parse_err.to_compile_error().into()
+            } else {
+                let msg = e.to_string();
+                quote!(::std::compile_error!(#msg)).into()
+            }
+        }
+    }
+}
+
 #[cfg(feature = "derive")]
 #[proc_macro_derive(Encode, attributes(sqlx))]
 pub fn derive_encode(tokenstream: TokenStream) -> TokenStream {
@@ -66,9 +84,7 @@ pub fn derive_from_row(input: TokenStream) -> TokenStream {
 #[cfg(feature = "migrate")]
 #[proc_macro]
 pub fn migrate(input: TokenStream) -> TokenStream {
-    use syn::LitStr;
-
-    let input = syn::parse_macro_input!(input as Option<LitStr>);
+    let input = syn::parse_macro_input!(input as migrate::MigrateInput);
     match migrate::expand(input) {
         Ok(ts) => ts.into(),
         Err(e) => {
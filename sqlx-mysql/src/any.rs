@@ -11,11 +11,12 @@ use sqlx_core::any::{
     AnyArguments, AnyColumn, AnyConnectOptions, AnyConnectionBackend, AnyQueryResult, AnyRow,
     AnyStatement, AnyTypeInfo, AnyTypeInfoKind,
 };
-use sqlx_core::connection::Connection;
+use sqlx_core::common::QueryCanceller;
+use sqlx_core::connection::{Connection, ResetMode};
 use sqlx_core::database::Database;
 use sqlx_core::executor::Executor;
 use sqlx_core::sql_str::SqlStr;
-use sqlx_core::transaction::TransactionManager;
+use sqlx_core::transaction::{TransactionManager, TransactionOptions};
 use std::{future, pin::pin};
 
 sqlx_core::declare_driver_with_optional_migrate!(DRIVER = MySql);
@@ -41,6 +42,10 @@ impl AnyConnectionBackend for MySqlConnection {
         MySqlTransactionManager::begin(self, statement).boxed()
     }
 
+    fn begin_statement(&self, options: &TransactionOptions) -> SqlStr {
+        MySqlTransactionManager::begin_statement(self, options)
+    }
+
     fn commit(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
         MySqlTransactionManager::commit(self).boxed()
     }
@@ -57,6 +62,10 @@ impl AnyConnectionBackend for MySqlConnection {
         MySqlTransactionManager::get_transaction_depth(self)
     }
 
+    fn reset(&mut self, mode: ResetMode) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::reset(self, mode).boxed()
+    }
+
     fn shrink_buffers(&mut self) {
         Connection::shrink_buffers(self);
     }
@@ -76,6 +85,10 @@ impl AnyConnectionBackend for MySqlConnection {
         Ok(self)
     }
 
+    fn query_canceller(&self) -> QueryCanceller {
+        MySqlConnection::query_canceller(self)
+    }
+
     fn fetch_many(
         &mut self,
         query: SqlStr,
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use bytes::buf::Buf;
 use bytes::Bytes;
 
@@ -28,7 +30,9 @@ impl MySqlConnection {
                 transaction_depth: 0,
                 status_flags: Default::default(),
                 cache_statement: StatementCache::new(options.statement_cache_capacity),
+                session_init_statement: None,
                 log_settings: options.log_settings.clone(),
+                options: Arc::new(options.clone()),
             }),
         })
     }
@@ -61,6 +65,7 @@ impl<'a> DoHandshake<'a> {
         // https://mariadb.com/kb/en/connection/
 
         let handshake: Handshake = stream.recv_packet().await?.decode()?;
+        stream.connection_id = handshake.connection_id;
 
         let mut plugin = handshake.auth_plugin;
         let nonce = handshake.auth_plugin_data;
@@ -285,9 +285,12 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
     {
         let arguments = query.take_arguments().map_err(Error::Encode);
         let persistent = query.persistent();
+        let canceller = query
+            .timeout()
+            .map(|timeout| (self.query_canceller(), timeout));
 
-        Box::pin(try_stream! {
-        let sql = query.sql();
+        let stream = Box::pin(try_stream! {
+            let sql = query.sql();
             let arguments = arguments?;
             let mut s = pin!(self.run(sql, arguments, persistent).await?);
 
@@ -296,7 +299,12 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
             }
 
             Ok(())
-        })
+        });
+
+        match canceller {
+            Some((canceller, timeout)) => canceller.timeout_stream(stream, timeout),
+            None => stream,
+        }
     }
 
     fn fetch_optional<'e, 'q, E>(self, query: E) -> BoxFuture<'e, Result<Option<MySqlRow>, Error>>
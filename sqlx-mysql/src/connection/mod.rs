@@ -1,19 +1,21 @@
 use std::fmt::{self, Debug, Formatter};
 use std::future::Future;
+use std::sync::Arc;
 
 pub(crate) use sqlx_core::connection::*;
-use sqlx_core::sql_str::SqlSafeStr;
+use sqlx_core::sql_str::AssertSqlSafe;
 pub(crate) use stream::{MySqlStream, Waiting};
 
 use crate::collation::Collation;
-use crate::common::StatementCache;
-use crate::error::Error;
+use crate::common::{QueryCanceller, StatementCache};
+use crate::error::{error_codes, Error};
+use crate::executor::Executor;
 use crate::protocol::response::Status;
 use crate::protocol::statement::StmtClose;
-use crate::protocol::text::{Ping, Quit};
+use crate::protocol::text::{Ping, Quit, ResetConnection};
 use crate::statement::MySqlStatementMetadata;
-use crate::transaction::Transaction;
-use crate::{MySql, MySqlConnectOptions};
+use crate::transaction::{IntoBeginStatement, Transaction};
+use crate::{MySql, MySqlConnectOptions, MySqlDatabaseError};
 
 mod auth;
 mod establish;
@@ -48,7 +50,14 @@ pub(crate) struct MySqlConnectionInner {
     // cache by query string to the statement id and metadata
     cache_statement: StatementCache<(u32, MySqlStatementMetadata)>,
 
+    // the `SET ...` statement run after connecting, re-applied after `COM_RESET_CONNECTION`
+    pub(crate) session_init_statement: Option<String>,
+
     log_settings: LogSettings,
+
+    // options this connection was opened with
+    // used to open a second connection to cancel a running query
+    options: Arc<MySqlConnectOptions>,
 }
 
 impl MySqlConnection {
@@ -57,6 +66,29 @@ impl MySqlConnection {
             .status_flags
             .intersects(Status::SERVER_STATUS_IN_TRANS)
     }
+
+    pub(crate) fn query_canceller(&self) -> QueryCanceller {
+        let options = Arc::clone(&self.inner.options);
+        let connection_id = self.inner.stream.connection_id;
+
+        QueryCanceller::new(
+            move || {
+                Box::pin(async move {
+                    let mut conn = MySqlConnection::establish(&options).await?;
+
+                    conn.execute(AssertSqlSafe(format!("KILL QUERY {connection_id}")))
+                        .await?;
+
+                    conn.close().await
+                })
+            },
+            |e| {
+                e.as_database_error()
+                    .and_then(|e| e.try_downcast_ref::<MySqlDatabaseError>())
+                    .is_some_and(|e| e.number() == error_codes::ER_QUERY_INTERRUPTED)
+            },
+        )
+    }
 }
 
 impl Debug for MySqlConnection {
@@ -112,6 +144,28 @@ impl Connection for MySqlConnection {
         Ok(())
     }
 
+    async fn reset(&mut self, _mode: ResetMode) -> Result<(), Error> {
+        // There's no way to reset the session without also deallocating prepared statements,
+        // so both modes are the same.
+        self.inner.stream.wait_until_ready().await?;
+        self.inner.stream.send_packet(ResetConnection).await?;
+        let ok = self.inner.stream.recv_ok().await?;
+
+        // Any open transaction was rolled back.
+        self.inner.transaction_depth = 0;
+        self.inner.status_flags = ok.status;
+
+        // The statements were deallocated on the server, so there's nothing to close.
+        self.inner.cache_statement.clear();
+
+        // Session variables were reset to their global values.
+        if let Some(statement) = self.inner.session_init_statement.clone() {
+            self.execute(AssertSqlSafe(statement)).await?;
+        }
+
+        Ok(())
+    }
+
     #[doc(hidden)]
     fn should_flush(&self) -> bool {
         !self.inner.stream.write_buffer().is_empty()
@@ -125,12 +179,13 @@ impl Connection for MySqlConnection {
 
     fn begin_with(
         &mut self,
-        statement: impl SqlSafeStr,
+        statement: impl IntoBeginStatement<Self::Database>,
     ) -> impl Future<Output = Result<Transaction<'_, Self::Database>, Error>> + Send + '_
     where
         Self: Sized,
     {
-        Transaction::begin(self, Some(statement.into_sql_str()))
+        let statement = statement.into_begin_statement(self);
+        Transaction::begin(self, Some(statement))
     }
 
     fn shrink_buffers(&mut self) {
@@ -15,6 +15,8 @@ pub struct MySqlStream<S = Box<dyn Socket>> {
     // Wrapping the socket in `Box` allows us to unsize in-place.
     pub(crate) socket: BufferedSocket<S>,
     pub(crate) server_version: (u16, u16, u16),
+    // the ID the server assigned to this connection, used to `KILL QUERY` from another connection
+    pub(crate) connection_id: u32,
     pub(super) capabilities: Capabilities,
     pub(crate) sequence_id: u8,
     pub(crate) waiting: VecDeque<Waiting>,
@@ -53,6 +55,7 @@ impl<S: Socket> MySqlStream<S> {
             waiting: VecDeque::new(),
             capabilities,
             server_version: (0, 0, 0),
+            connection_id: 0,
             sequence_id: 0,
             socket: BufferedSocket::new(socket),
             is_tls: false,
@@ -209,6 +212,7 @@ impl<S: Socket> MySqlStream<S> {
         MySqlStream {
             socket: self.socket.boxed(),
             server_version: self.server_version,
+            connection_id: self.connection_id,
             capabilities: self.capabilities,
             sequence_id: self.sequence_id,
             waiting: self.waiting,
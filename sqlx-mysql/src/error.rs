@@ -113,6 +113,13 @@ impl DatabaseError for MySqlDatabaseError {
             _ => ErrorKind::Other,
         }
     }
+
+    fn is_transient(&self) -> bool {
+        matches!(
+            self.number(),
+            error_codes::ER_LOCK_DEADLOCK | error_codes::ER_LOCK_WAIT_TIMEOUT
+        )
+    }
 }
 
 /// The MySQL server uses SQLSTATEs as a generic error category,
@@ -165,6 +172,13 @@ pub(crate) mod error_codes {
     /// Only available after 8.0.16.
     pub const ER_CHECK_CONSTRAINT_VIOLATED: u16 = 3819;
 
+    /// Caused when a lock could not be acquired before `innodb_lock_wait_timeout` elapsed.
+    pub const ER_LOCK_WAIT_TIMEOUT: u16 = 1205;
+    /// Caused when a deadlock was detected; the transaction is rolled back.
+    pub const ER_LOCK_DEADLOCK: u16 = 1213;
+    /// Caused when a running query was stopped with `KILL QUERY`.
+    pub const ER_QUERY_INTERRUPTED: u16 = 1317;
+
     pub(crate) mod mariadb {
         /// Error code emitted by MariaDB for constraint errors: <https://mariadb.com/kb/en/e4025/>
         ///
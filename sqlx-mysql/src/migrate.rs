@@ -101,12 +101,48 @@ CREATE TABLE IF NOT EXISTS {table_name} (
     installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
     success BOOLEAN NOT NULL,
     checksum BLOB NOT NULL,
-    execution_time BIGINT NOT NULL
+    execution_time BIGINT NOT NULL,
+    squashed_from BIGINT,
+    installed_rank BIGINT
 );
                 "#
             )))
             .await?;
 
+            // added for `sqlx migrate squash`; MySQL has no `ADD COLUMN IF NOT EXISTS`
+            // language=MySQL
+            let has_squashed_from = self
+                .execute(AssertSqlSafe(format!(
+                    "SELECT squashed_from FROM {table_name} LIMIT 0"
+                )))
+                .await
+                .is_ok();
+
+            if !has_squashed_from {
+                // language=MySQL
+                self.execute(AssertSqlSafe(format!(
+                    "ALTER TABLE {table_name} ADD COLUMN squashed_from BIGINT"
+                )))
+                .await?;
+            }
+
+            // added for out-of-order migrations, to record the order they were applied in
+            // language=MySQL
+            let has_installed_rank = self
+                .execute(AssertSqlSafe(format!(
+                    "SELECT installed_rank FROM {table_name} LIMIT 0"
+                )))
+                .await
+                .is_ok();
+
+            if !has_installed_rank {
+                // language=MySQL
+                self.execute(AssertSqlSafe(format!(
+                    "ALTER TABLE {table_name} ADD COLUMN installed_rank BIGINT"
+                )))
+                .await?;
+            }
+
             Ok(())
         })
     }
@@ -132,19 +168,29 @@ CREATE TABLE IF NOT EXISTS {table_name} (
         table_name: &'e str,
     ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
         Box::pin(async move {
+            #[allow(clippy::type_complexity)]
             // language=SQL
-            let rows: Vec<(i64, Vec<u8>)> = query_as(AssertSqlSafe(format!(
-                "SELECT version, checksum FROM {table_name} ORDER BY version"
-            )))
-            .fetch_all(self)
-            .await?;
+            let rows: Vec<(i64, Vec<u8>, Option<i64>, Option<i64>)> =
+                query_as(AssertSqlSafe(format!(
+                    r#"
+    SELECT version, checksum, squashed_from, installed_rank
+    FROM {table_name}
+    ORDER BY version
+                    "#
+                )))
+                .fetch_all(self)
+                .await?;
 
             let migrations = rows
                 .into_iter()
-                .map(|(version, checksum)| AppliedMigration {
-                    version,
-                    checksum: checksum.into(),
-                })
+                .map(
+                    |(version, checksum, squashed_from, installed_rank)| AppliedMigration {
+                        version,
+                        checksum: checksum.into(),
+                        squashed_from,
+                        installed_rank,
+                    },
+                )
                 .collect();
 
             Ok(migrations)
@@ -210,20 +256,18 @@ CREATE TABLE IF NOT EXISTS {table_name} (
             // language=MySQL
             let _ = query(AssertSqlSafe(format!(
                 r#"
-    INSERT INTO {table_name} ( version, description, success, checksum, execution_time )
-    VALUES ( ?, ?, FALSE, ?, -1 )
+    INSERT INTO {table_name} ( version, description, success, checksum, execution_time, squashed_from, installed_rank )
+    SELECT ?, ?, FALSE, ?, -1, ?, COALESCE(MAX(installed_rank), 0) + 1 FROM {table_name}
                 "#
             )))
             .bind(migration.version)
             .bind(&*migration.description)
             .bind(&*migration.checksum)
+            .bind(migration.squashed_from)
             .execute(&mut *tx)
             .await?;
 
-            let _ = tx
-                .execute(migration.sql.clone())
-                .await
-                .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
+            migration.execute(&mut *tx).await?;
 
             // language=MySQL
             let _ = query(AssertSqlSafe(format!(
@@ -262,6 +306,104 @@ CREATE TABLE IF NOT EXISTS {table_name} (
         })
     }
 
+    fn squash<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=MySQL
+            let _ = query(AssertSqlSafe(format!(
+                r#"
+    UPDATE {table_name}
+    SET description = ?, checksum = ?, squashed_from = ?
+    WHERE version = ?
+                "#
+            )))
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .bind(migration.squashed_from)
+            .bind(migration.version)
+            .execute(self)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn repair<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=MySQL
+            let _ = query(AssertSqlSafe(format!(
+                r#"
+    UPDATE {table_name}
+    SET description = ?, checksum = ?
+    WHERE version = ?
+                "#
+            )))
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .bind(migration.version)
+            .execute(self)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn mark_applied<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=MySQL
+            let _ = query(AssertSqlSafe(format!(
+                r#"
+    INSERT INTO {table_name} ( version, description, success, checksum, execution_time, squashed_from, installed_rank )
+    SELECT ?, ?, TRUE, ?, -1, ?, COALESCE(MAX(installed_rank), 0) + 1 FROM {table_name}
+                "#
+            )))
+            .bind(migration.version)
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .bind(migration.squashed_from)
+            .execute(self)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn mark_dirty_resolved<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+        applied: bool,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=MySQL
+            let sql = if applied {
+                format!(
+                    "UPDATE {table_name} SET success = TRUE WHERE version = ? AND success = FALSE"
+                )
+            } else {
+                format!("DELETE FROM {table_name} WHERE version = ? AND success = FALSE")
+            };
+
+            let _ = query(AssertSqlSafe(sql))
+                .bind(version)
+                .execute(self)
+                .await?;
+
+            Ok(())
+        })
+    }
+
     fn revert<'e>(
         &'e mut self,
         table_name: &'e str,
@@ -26,7 +26,31 @@ impl ConnectOptions for MySqlConnectOptions {
 
         // After the connection is established, we initialize by configuring a few
         // connection parameters
+        if let Some(statement) = self.session_init_statement() {
+            conn.execute(AssertSqlSafe(statement.clone())).await?;
+            conn.inner.session_init_statement = Some(statement);
+        }
+
+        Ok(conn)
+    }
+
+    fn log_statements(mut self, level: LevelFilter) -> Self {
+        self.log_settings.log_statements(level);
+        self
+    }
+
+    fn log_slow_statements(mut self, level: LevelFilter, duration: Duration) -> Self {
+        self.log_settings.log_slow_statements(level, duration);
+        self
+    }
+}
 
+impl MySqlConnectOptions {
+    /// The statement used to configure the session after connecting, if any.
+    ///
+    /// Also re-applied after [`Connection::reset()`][sqlx_core::connection::Connection::reset],
+    /// which resets these settings to the server defaults.
+    pub(crate) fn session_init_statement(&self) -> Option<String> {
         // https://mariadb.com/kb/en/sql-mode/
 
         // PIPES_AS_CONCAT - Allows using the pipe character (ASCII 124) as string concatenation operator.
@@ -83,21 +107,10 @@ impl ConnectOptions for MySqlConnectOptions {
             options.push(set_names);
         }
 
-        if !options.is_empty() {
-            conn.execute(AssertSqlSafe(format!(r#"SET {};"#, options.join(","))))
-                .await?;
+        if options.is_empty() {
+            return None;
         }
 
-        Ok(conn)
-    }
-
-    fn log_statements(mut self, level: LevelFilter) -> Self {
-        self.log_settings.log_statements(level);
-        self
-    }
-
-    fn log_slow_statements(mut self, level: LevelFilter, duration: Duration) -> Self {
-        self.log_settings.log_slow_statements(level, duration);
-        self
+        Some(format!(r#"SET {};"#, options.join(",")))
     }
 }
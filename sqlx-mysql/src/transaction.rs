@@ -1,4 +1,4 @@
-use sqlx_core::sql_str::SqlStr;
+use sqlx_core::sql_str::{AssertSqlSafe, SqlSafeStr, SqlStr};
 
 use crate::connection::Waiting;
 use crate::error::Error;
@@ -33,6 +33,26 @@ impl TransactionManager for MySqlTransactionManager {
         Ok(())
     }
 
+    fn begin_statement(_conn: &MySqlConnection, options: &TransactionOptions) -> SqlStr {
+        // `START TRANSACTION` does not accept an isolation level, but `SET TRANSACTION`
+        // without `SESSION` or `GLOBAL` applies to the next transaction only.
+        let mut statement = String::new();
+
+        if let Some(isolation) = options.get_isolation() {
+            statement = format!("SET TRANSACTION ISOLATION LEVEL {}; ", isolation.as_sql());
+        }
+
+        if options.get_read_only() {
+            statement.push_str("START TRANSACTION READ ONLY");
+        } else if statement.is_empty() {
+            return begin_ansi_transaction_sql(0);
+        } else {
+            statement.push_str("START TRANSACTION");
+        }
+
+        AssertSqlSafe(statement).into_sql_str()
+    }
+
     async fn commit(conn: &mut MySqlConnection) -> Result<(), Error> {
         let depth = conn.inner.transaction_depth;
 
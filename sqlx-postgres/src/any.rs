@@ -14,11 +14,12 @@ use sqlx_core::any::{
 };
 
 use crate::type_info::PgType;
-use sqlx_core::connection::Connection;
+use sqlx_core::common::QueryCanceller;
+use sqlx_core::connection::{Connection, ResetMode};
 use sqlx_core::database::Database;
 use sqlx_core::executor::Executor;
 use sqlx_core::ext::ustr::UStr;
-use sqlx_core::transaction::TransactionManager;
+use sqlx_core::transaction::{TransactionManager, TransactionOptions};
 
 sqlx_core::declare_driver_with_optional_migrate!(DRIVER = Postgres);
 
@@ -43,6 +44,10 @@ impl AnyConnectionBackend for PgConnection {
         PgTransactionManager::begin(self, statement).boxed()
     }
 
+    fn begin_statement(&self, options: &TransactionOptions) -> SqlStr {
+        PgTransactionManager::begin_statement(self, options)
+    }
+
     fn commit(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
         PgTransactionManager::commit(self).boxed()
     }
@@ -59,6 +64,10 @@ impl AnyConnectionBackend for PgConnection {
         PgTransactionManager::get_transaction_depth(self)
     }
 
+    fn reset(&mut self, mode: ResetMode) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::reset(self, mode).boxed()
+    }
+
     fn shrink_buffers(&mut self) {
         Connection::shrink_buffers(self);
     }
@@ -78,6 +87,10 @@ impl AnyConnectionBackend for PgConnection {
         Ok(self)
     }
 
+    fn query_canceller(&self) -> QueryCanceller {
+        PgConnection::query_canceller(self)
+    }
+
     fn fetch_many(
         &mut self,
         query: SqlStr,
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use crate::HashMap;
 
 use crate::common::StatementCache;
@@ -150,6 +152,7 @@ impl PgConnection {
                 cache_elem_type_to_array: HashMap::new(),
                 cache_table_to_column_names: HashMap::new(),
                 log_settings: options.log_settings.clone(),
+                options: Arc::new(options.clone()),
             }),
         })
     }
@@ -408,9 +408,12 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
         let arguments = query.take_arguments().map_err(Error::Encode);
         let persistent = query.persistent();
+        let canceller = query
+            .timeout()
+            .map(|timeout| (self.query_canceller(), timeout));
         let sql = query.sql();
 
-        Box::pin(try_stream! {
+        let stream = Box::pin(try_stream! {
             let arguments = arguments?;
             let mut s = pin!(self.run(sql, arguments, persistent, metadata).await?);
 
@@ -419,7 +422,12 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
             }
 
             Ok(())
-        })
+        });
+
+        match canceller {
+            Some((canceller, timeout)) => canceller.timeout_stream(stream, timeout),
+            None => stream,
+        }
     }
 
     fn fetch_optional<'e, 'q, E>(self, mut query: E) -> BoxFuture<'e, Result<Option<PgRow>, Error>>
@@ -434,8 +442,11 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
         let arguments = query.take_arguments().map_err(Error::Encode);
         let persistent = query.persistent();
+        let canceller = query
+            .timeout()
+            .map(|timeout| (self.query_canceller(), timeout));
 
-        Box::pin(async move {
+        let future = Box::pin(async move {
             let sql = query.sql();
             let arguments = arguments?;
             let mut s = pin!(self.run(sql, arguments, persistent, metadata).await?);
@@ -452,7 +463,12 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
                 }
             }
             Ok(ret)
-        })
+        });
+
+        match canceller {
+            Some((canceller, timeout)) => canceller.timeout_future(future, timeout),
+            None => future,
+        }
     }
 
     fn prepare_with<'e>(
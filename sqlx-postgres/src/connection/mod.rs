@@ -5,21 +5,21 @@ use std::sync::Arc;
 
 use crate::HashMap;
 
-use crate::common::StatementCache;
-use crate::error::Error;
+use crate::common::{QueryCanceller, StatementCache};
+use crate::error::{error_codes, Error};
+use crate::executor::Executor;
 use crate::ext::ustr::UStr;
 use crate::io::StatementId;
 use crate::message::{
-    BackendMessageFormat, Close, Query, ReadyForQuery, ReceivedMessage, Terminate,
+    BackendMessageFormat, CancelRequest, Close, Query, ReadyForQuery, ReceivedMessage, Terminate,
     TransactionStatus,
 };
 use crate::statement::PgStatementMetadata;
-use crate::transaction::Transaction;
+use crate::transaction::{IntoBeginStatement, Transaction};
 use crate::types::Oid;
 use crate::{PgConnectOptions, PgTypeInfo, Postgres};
 
 pub(crate) use sqlx_core::connection::*;
-use sqlx_core::sql_str::SqlSafeStr;
 
 pub use self::stream::PgStream;
 
@@ -47,14 +47,16 @@ pub struct PgConnectionInner {
 
     // process id of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
     process_id: u32,
 
     // secret key of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
     secret_key: u32,
 
+    // options this connection was opened with
+    // used to open a second connection to send cancel requests over
+    options: Arc<PgConnectOptions>,
+
     // sequence of statement IDs for use in preparing statements
     // in PostgreSQL, the statement is prepared to a user-supplied identifier
     next_statement_id: StatementId,
@@ -90,6 +92,36 @@ impl PgConnection {
         self.inner.stream.server_version_num
     }
 
+    pub(crate) fn query_canceller(&self) -> QueryCanceller {
+        let options = Arc::clone(&self.inner.options);
+        let request = CancelRequest {
+            process_id: self.inner.process_id,
+            secret_key: self.inner.secret_key,
+        };
+
+        QueryCanceller::new(
+            move || {
+                Box::pin(async move {
+                    let mut stream = PgStream::connect(&options).await?;
+
+                    stream.write(request)?;
+                    stream.flush().await?;
+
+                    // The server closes the connection without a reply once it has
+                    // handled the request.
+                    let _ = stream.read_buffered(1).await;
+
+                    Ok(())
+                })
+            },
+            |e| {
+                e.as_database_error()
+                    .and_then(|e| e.code())
+                    .is_some_and(|code| code == error_codes::QUERY_CANCELED)
+            },
+        )
+    }
+
     // will return when the connection is ready for another query
     pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
         if !self.inner.stream.write_buffer_mut().is_empty() {
@@ -195,12 +227,13 @@ impl Connection for PgConnection {
 
     fn begin_with(
         &mut self,
-        statement: impl SqlSafeStr,
+        statement: impl IntoBeginStatement<Self::Database>,
     ) -> impl Future<Output = Result<Transaction<'_, Self::Database>, Error>> + Send + '_
     where
         Self: Sized,
     {
-        Transaction::begin(self, Some(statement.into_sql_str()))
+        let statement = statement.into_begin_statement(self);
+        Transaction::begin(self, Some(statement))
     }
 
     fn cached_statements_size(&self) -> usize {
@@ -230,6 +263,34 @@ impl Connection for PgConnection {
         Ok(())
     }
 
+    async fn reset(&mut self, mode: ResetMode) -> Result<(), Error> {
+        match mode {
+            ResetMode::All => {
+                self.execute("DISCARD ALL").await?;
+
+                // `DISCARD ALL` deallocates all prepared statements on the server,
+                // so there's nothing left to close.
+                self.inner.cache_statement.clear();
+            }
+            ResetMode::Session => {
+                // Everything `DISCARD ALL` does except `DEALLOCATE ALL` and `DISCARD PLANS`:
+                // https://www.postgresql.org/docs/current/sql-discard.html
+                self.execute(
+                    "CLOSE ALL; \
+                     SET SESSION AUTHORIZATION DEFAULT; \
+                     RESET ALL; \
+                     UNLISTEN *; \
+                     SELECT pg_advisory_unlock_all(); \
+                     DISCARD TEMP; \
+                     DISCARD SEQUENCES;",
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
     fn shrink_buffers(&mut self) {
         self.inner.stream.shrink_buffers();
     }
@@ -218,6 +218,13 @@ impl DatabaseError for PgDatabaseError {
             _ => ErrorKind::Other,
         }
     }
+
+    fn is_transient(&self) -> bool {
+        matches!(
+            self.code(),
+            error_codes::SERIALIZATION_FAILURE | error_codes::DEADLOCK_DETECTED
+        )
+    }
 }
 
 // ErrorResponse is the same structure as NoticeResponse but a different format code.
@@ -242,4 +249,10 @@ pub(crate) mod error_codes {
     pub const CHECK_VIOLATION: &str = "23514";
     /// Caused when a exclude constraint is violated.
     pub const EXCLUSION_VIOLATION: &str = "23P01";
+    /// Caused when a transaction could not be serialized with concurrent transactions.
+    pub const SERIALIZATION_FAILURE: &str = "40001";
+    /// Caused when a deadlock between transactions was detected.
+    pub const DEADLOCK_DETECTED: &str = "40P01";
+    /// Caused when a query was cancelled, e.g. by a cancel request.
+    pub const QUERY_CANCELED: &str = "57014";
 }
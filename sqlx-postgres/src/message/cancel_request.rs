@@ -0,0 +1,48 @@
+use crate::io::ProtocolEncode;
+
+/// Asks the server to cancel the query running on another connection.
+///
+/// Sent in place of a startup message on a new connection, which the server then closes
+/// without a reply.
+///
+/// <https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-FLOW-CANCELING-REQUESTS>
+#[derive(Debug, Copy, Clone)]
+pub struct CancelRequest {
+    /// The process ID of the backend running the query, from [`BackendKeyData`][super::BackendKeyData].
+    pub process_id: u32,
+
+    /// The secret key of the backend running the query, from [`BackendKeyData`][super::BackendKeyData].
+    pub secret_key: u32,
+}
+
+impl CancelRequest {
+    // The cancel request code. The value is chosen to contain 1234 in the most significant 16 bits,
+    // and 5678 in the least significant 16 bits.
+    const CODE: u32 = (1234 << 16) | 5678;
+}
+
+// Cannot impl FrontendMessage because it does not have a format code
+impl ProtocolEncode<'_> for CancelRequest {
+    fn encode_with(&self, buf: &mut Vec<u8>, _context: ()) -> Result<(), crate::Error> {
+        buf.extend_from_slice(&16_u32.to_be_bytes());
+        buf.extend_from_slice(&Self::CODE.to_be_bytes());
+        buf.extend_from_slice(&self.process_id.to_be_bytes());
+        buf.extend_from_slice(&self.secret_key.to_be_bytes());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_encode_cancel_request() {
+    const EXPECTED: &[u8] = b"\x00\x00\x00\x10\x04\xd2\x16\x2e\x00\x00\x30\x39\xde\xad\xbe\xef";
+
+    let mut buf = Vec::new();
+    CancelRequest {
+        process_id: 12345,
+        secret_key: 0xdeadbeef,
+    }
+    .encode(&mut buf)
+    .unwrap();
+
+    assert_eq!(buf, EXPECTED);
+}
@@ -7,14 +7,16 @@ use futures_core::future::BoxFuture;
 pub(crate) use sqlx_core::migrate::MigrateError;
 pub(crate) use sqlx_core::migrate::{AppliedMigration, Migration};
 pub(crate) use sqlx_core::migrate::{Migrate, MigrateDatabase};
-use sqlx_core::sql_str::AssertSqlSafe;
+use sqlx_core::sql_str::{AssertSqlSafe, SqlSafeStr};
 
+use crate::column::Column;
 use crate::connection::{ConnectOptions, Connection};
 use crate::error::Error;
 use crate::executor::Executor;
 use crate::query::query;
 use crate::query_as::query_as;
 use crate::query_scalar::query_scalar;
+use crate::statement::Statement;
 use crate::{PgConnectOptions, PgConnection, Postgres};
 
 fn parse_for_maintenance(url: &str) -> Result<(PgConnectOptions, String), Error> {
@@ -133,12 +135,46 @@ CREATE TABLE IF NOT EXISTS {table_name} (
     installed_on TIMESTAMPTZ NOT NULL DEFAULT now(),
     success BOOLEAN NOT NULL,
     checksum BYTEA NOT NULL,
-    execution_time BIGINT NOT NULL
+    execution_time BIGINT NOT NULL,
+    squashed_from BIGINT,
+    installed_rank BIGINT
 );
                 "#
             )))
             .await?;
 
+            // Check for columns added since the table was created, so the `ALTER TABLE` (which
+            // takes an exclusive lock) only runs once. A failed `SELECT` would abort the current
+            // transaction, if any, so the columns are looked up by preparing one instead.
+            // language=SQL
+            let columns = self
+                .prepare(AssertSqlSafe(format!("SELECT * FROM {table_name}")).into_sql_str())
+                .await?
+                .columns()
+                .iter()
+                .map(|column| column.name().to_owned())
+                .collect::<Vec<_>>();
+
+            let has_column = |name: &str| columns.iter().any(|column| column == name);
+
+            // added for `sqlx migrate squash`
+            if !has_column("squashed_from") {
+                // language=SQL
+                self.execute(AssertSqlSafe(format!(
+                    r#"ALTER TABLE {table_name} ADD COLUMN IF NOT EXISTS squashed_from BIGINT;"#
+                )))
+                .await?;
+            }
+
+            // added for out-of-order migrations, to record the order they were applied in
+            if !has_column("installed_rank") {
+                // language=SQL
+                self.execute(AssertSqlSafe(format!(
+                    r#"ALTER TABLE {table_name} ADD COLUMN IF NOT EXISTS installed_rank BIGINT;"#
+                )))
+                .await?;
+            }
+
             Ok(())
         })
     }
@@ -164,19 +200,29 @@ CREATE TABLE IF NOT EXISTS {table_name} (
         table_name: &'e str,
     ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
         Box::pin(async move {
+            #[allow(clippy::type_complexity)]
             // language=SQL
-            let rows: Vec<(i64, Vec<u8>)> = query_as(AssertSqlSafe(format!(
-                "SELECT version, checksum FROM {table_name} ORDER BY version"
-            )))
-            .fetch_all(self)
-            .await?;
+            let rows: Vec<(i64, Vec<u8>, Option<i64>, Option<i64>)> =
+                query_as(AssertSqlSafe(format!(
+                    r#"
+    SELECT version, checksum, squashed_from, installed_rank
+    FROM {table_name}
+    ORDER BY version
+                    "#
+                )))
+                .fetch_all(self)
+                .await?;
 
             let migrations = rows
                 .into_iter()
-                .map(|(version, checksum)| AppliedMigration {
-                    version,
-                    checksum: checksum.into(),
-                })
+                .map(
+                    |(version, checksum, squashed_from, installed_rank)| AppliedMigration {
+                        version,
+                        checksum: checksum.into(),
+                        squashed_from,
+                        installed_rank,
+                    },
+                )
                 .collect();
 
             Ok(migrations)
@@ -264,6 +310,104 @@ CREATE TABLE IF NOT EXISTS {table_name} (
         })
     }
 
+    fn squash<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            let _ = query(AssertSqlSafe(format!(
+                r#"
+    UPDATE {table_name}
+    SET description = $1, checksum = $2, squashed_from = $3
+    WHERE version = $4
+                "#
+            )))
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .bind(migration.squashed_from)
+            .bind(migration.version)
+            .execute(self)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn repair<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            let _ = query(AssertSqlSafe(format!(
+                r#"
+    UPDATE {table_name}
+    SET description = $1, checksum = $2
+    WHERE version = $3
+                "#
+            )))
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .bind(migration.version)
+            .execute(self)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn mark_applied<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            let _ = query(AssertSqlSafe(format!(
+                r#"
+    INSERT INTO {table_name} ( version, description, success, checksum, execution_time, squashed_from, installed_rank )
+    SELECT $1, $2, TRUE, $3, -1, $4, COALESCE(MAX(installed_rank), 0) + 1 FROM {table_name}
+                "#
+            )))
+            .bind(migration.version)
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .bind(migration.squashed_from)
+            .execute(self)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn mark_dirty_resolved<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+        applied: bool,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            let sql = if applied {
+                format!(
+                    "UPDATE {table_name} SET success = TRUE WHERE version = $1 AND success = FALSE"
+                )
+            } else {
+                format!("DELETE FROM {table_name} WHERE version = $1 AND success = FALSE")
+            };
+
+            let _ = query(AssertSqlSafe(sql))
+                .bind(version)
+                .execute(self)
+                .await?;
+
+            Ok(())
+        })
+    }
+
     fn revert<'e>(
         &'e mut self,
         table_name: &'e str,
@@ -295,21 +439,19 @@ async fn execute_migration(
     table_name: &str,
     migration: &Migration,
 ) -> Result<(), MigrateError> {
-    let _ = conn
-        .execute(migration.sql.clone())
-        .await
-        .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
+    migration.execute(conn).await?;
 
     // language=SQL
     let _ = query(AssertSqlSafe(format!(
         r#"
-    INSERT INTO {table_name} ( version, description, success, checksum, execution_time )
-    VALUES ( $1, $2, TRUE, $3, -1 )
+    INSERT INTO {table_name} ( version, description, success, checksum, execution_time, squashed_from, installed_rank )
+    SELECT $1, $2, TRUE, $3, -1, $4, COALESCE(MAX(installed_rank), 0) + 1 FROM {table_name}
                 "#
     )))
     .bind(migration.version)
     .bind(&*migration.description)
     .bind(&*migration.checksum)
+    .bind(migration.squashed_from)
     .execute(conn)
     .await?;
 
@@ -1,5 +1,5 @@
 use sqlx_core::database::Database;
-use sqlx_core::sql_str::SqlStr;
+use sqlx_core::sql_str::{AssertSqlSafe, SqlSafeStr, SqlStr};
 
 use crate::error::Error;
 use crate::executor::Executor;
@@ -37,6 +37,28 @@ impl TransactionManager for PgTransactionManager {
         Ok(())
     }
 
+    fn begin_statement(_conn: &PgConnection, options: &TransactionOptions) -> SqlStr {
+        let mut modes = Vec::new();
+
+        if let Some(isolation) = options.get_isolation() {
+            modes.push(format!("ISOLATION LEVEL {}", isolation.as_sql()));
+        }
+
+        if options.get_read_only() {
+            modes.push("READ ONLY".into());
+        }
+
+        if options.get_deferrable() {
+            modes.push("DEFERRABLE".into());
+        }
+
+        if modes.is_empty() {
+            return begin_ansi_transaction_sql(0);
+        }
+
+        AssertSqlSafe(format!("BEGIN {}", modes.join(", "))).into_sql_str()
+    }
+
     async fn commit(conn: &mut PgConnection) -> Result<(), Error> {
         if conn.inner.transaction_depth > 0 {
             conn.execute(commit_ansi_transaction_sql(conn.inner.transaction_depth))
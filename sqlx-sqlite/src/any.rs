@@ -14,10 +14,11 @@ use sqlx_core::sql_str::SqlStr;
 
 use crate::arguments::SqliteArgumentsBuffer;
 use crate::type_info::DataType;
-use sqlx_core::connection::{ConnectOptions, Connection};
+use sqlx_core::common::QueryCanceller;
+use sqlx_core::connection::{ConnectOptions, Connection, ResetMode};
 use sqlx_core::database::Database;
 use sqlx_core::executor::Executor;
-use sqlx_core::transaction::TransactionManager;
+use sqlx_core::transaction::{TransactionManager, TransactionOptions};
 use std::pin::pin;
 use std::sync::Arc;
 
@@ -44,6 +45,10 @@ impl AnyConnectionBackend for SqliteConnection {
         SqliteTransactionManager::begin(self, statement).boxed()
     }
 
+    fn begin_statement(&self, options: &TransactionOptions) -> SqlStr {
+        SqliteTransactionManager::begin_statement(self, options)
+    }
+
     fn commit(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
         SqliteTransactionManager::commit(self).boxed()
     }
@@ -60,6 +65,10 @@ impl AnyConnectionBackend for SqliteConnection {
         SqliteTransactionManager::get_transaction_depth(self)
     }
 
+    fn reset(&mut self, mode: ResetMode) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::reset(self, mode).boxed()
+    }
+
     fn shrink_buffers(&mut self) {
         // NO-OP.
     }
@@ -79,6 +88,10 @@ impl AnyConnectionBackend for SqliteConnection {
         Ok(self)
     }
 
+    fn query_canceller(&self) -> QueryCanceller {
+        SqliteConnection::query_canceller(self)
+    }
+
     fn fetch_many(
         &mut self,
         query: SqlStr,
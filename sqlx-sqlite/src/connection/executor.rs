@@ -28,14 +28,22 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
             Err(error) => return stream::once(future::ready(Err(error))).boxed(),
         };
         let persistent = query.persistent() && arguments.is_some();
+        let canceller = query
+            .timeout()
+            .map(|timeout| (self.query_canceller(), timeout));
         let sql = query.sql();
 
-        Box::pin(
+        let stream = Box::pin(
             self.worker
                 .execute(sql, arguments, self.row_channel_size, persistent, None)
                 .map_ok(flume::Receiver::into_stream)
                 .try_flatten_stream(),
-        )
+        );
+
+        match canceller {
+            Some((canceller, timeout)) => canceller.timeout_stream(stream, timeout),
+            None => stream,
+        }
     }
 
     fn fetch_optional<'e, 'q, E>(
@@ -53,8 +61,11 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
             Err(error) => return future::ready(Err(error)).boxed(),
         };
         let persistent = query.persistent() && arguments.is_some();
+        let canceller = query
+            .timeout()
+            .map(|timeout| (self.query_canceller(), timeout));
 
-        Box::pin(async move {
+        let future = Box::pin(async move {
             let sql = query.sql();
             let mut stream = pin!(self
                 .worker
@@ -69,7 +80,12 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
             }
 
             Ok(None)
-        })
+        });
+
+        match canceller {
+            Some((canceller, timeout)) => canceller.timeout_future(future, timeout),
+            None => future,
+        }
     }
 
     fn prepare_with<'e>(
@@ -5,7 +5,7 @@ use std::{io, ptr};
 use crate::error::Error;
 use libsqlite3_sys::{
     sqlite3, sqlite3_close, sqlite3_exec, sqlite3_extended_result_codes, sqlite3_get_autocommit,
-    sqlite3_last_insert_rowid, sqlite3_open_v2, SQLITE_OK,
+    sqlite3_interrupt, sqlite3_last_insert_rowid, sqlite3_open_v2, SQLITE_OK,
 };
 
 use crate::SqliteError;
@@ -25,6 +25,27 @@ pub(crate) struct ConnectionHandle(NonNull<sqlite3>);
 
 unsafe impl Send for ConnectionHandle {}
 
+/// Handle used to interrupt the statement running on a connection from another thread.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct InterruptHandle(NonNull<sqlite3>);
+
+// `sqlite3_interrupt()` is the one function that may be called from any thread,
+// while the connection is in use by another.
+//
+// <https://www.sqlite.org/c3ref/interrupt.html>
+unsafe impl Send for InterruptHandle {}
+unsafe impl Sync for InterruptHandle {}
+
+impl InterruptHandle {
+    /// Interrupt the statement currently running on the connection, if any.
+    ///
+    /// # Safety
+    /// The connection must not have been closed.
+    pub(crate) unsafe fn interrupt(&self) {
+        sqlite3_interrupt(self.0.as_ptr());
+    }
+}
+
 impl ConnectionHandle {
     pub(crate) fn open(filename: &CStr, flags: c_int) -> Result<Self, Error> {
         let mut handle = ptr::null_mut();
@@ -67,6 +88,10 @@ impl ConnectionHandle {
         self.0
     }
 
+    pub(crate) fn interrupt_handle(&self) -> InterruptHandle {
+        InterruptHandle(self.0)
+    }
+
     pub(crate) fn call_with_result(
         &mut self,
         call: impl FnOnce(*mut sqlite3) -> c_int,
@@ -2,11 +2,13 @@ use std::cmp::Ordering;
 use std::ffi::CStr;
 use std::fmt::Write;
 use std::fmt::{self, Debug, Formatter};
+use std::future;
 use std::future::Future;
 use std::os::raw::{c_char, c_int, c_void};
 use std::panic::catch_unwind;
 use std::ptr;
 use std::ptr::NonNull;
+use std::sync::Arc;
 
 use futures_intrusive::sync::MutexGuard;
 use libsqlite3_sys::{
@@ -16,17 +18,18 @@ use libsqlite3_sys::{
 #[cfg(feature = "preupdate-hook")]
 pub use preupdate_hook::*;
 
-pub(crate) use handle::ConnectionHandle;
-use sqlx_core::common::StatementCache;
+pub(crate) use handle::{ConnectionHandle, InterruptHandle};
+use sqlx_core::common::{QueryCanceller, StatementCache};
 pub(crate) use sqlx_core::connection::*;
 use sqlx_core::error::Error;
 use sqlx_core::executor::Executor;
-use sqlx_core::sql_str::{AssertSqlSafe, SqlSafeStr};
-use sqlx_core::transaction::Transaction;
+use sqlx_core::sql_str::AssertSqlSafe;
+use sqlx_core::transaction::{IntoBeginStatement, Transaction};
 
 use crate::connection::establish::EstablishParams;
 use crate::connection::worker::ConnectionWorker;
 use crate::options::OptimizeOnClose;
+use crate::query_as::query_as;
 use crate::statement::VirtualStatement;
 use crate::{Sqlite, SqliteConnectOptions, SqliteError};
 
@@ -192,6 +195,22 @@ impl SqliteConnection {
         })
     }
 
+    pub(crate) fn query_canceller(&self) -> QueryCanceller {
+        let shared = Arc::clone(&self.worker.shared);
+
+        QueryCanceller::new(
+            move || {
+                shared.interrupt();
+                Box::pin(future::ready(Ok(())))
+            },
+            |e| {
+                e.as_database_error()
+                    .and_then(|e| e.try_downcast_ref::<SqliteError>())
+                    .is_some_and(SqliteError::is_interrupt)
+            },
+        )
+    }
+
     /// Lock the SQLite database handle out from the worker thread so direct SQLite API calls can
     /// be made safely.
     ///
@@ -252,12 +271,13 @@ impl Connection for SqliteConnection {
 
     fn begin_with(
         &mut self,
-        statement: impl SqlSafeStr,
+        statement: impl IntoBeginStatement<Self::Database>,
     ) -> impl Future<Output = Result<Transaction<'_, Self::Database>, Error>> + Send + '_
     where
         Self: Sized,
     {
-        Transaction::begin(self, Some(statement.into_sql_str()))
+        let statement = statement.into_begin_statement(self);
+        Transaction::begin(self, Some(statement))
     }
 
     fn cached_statements_size(&self) -> usize {
@@ -268,6 +288,34 @@ impl Connection for SqliteConnection {
         self.worker.clear_cache()
     }
 
+    async fn reset(&mut self, mode: ResetMode) -> Result<(), Error> {
+        // SQLite doesn't have much in the way of session state besides the `temp` schema.
+        let temp_objects: Vec<(String, String)> = query_as(
+            "SELECT type, name FROM sqlite_temp_master \
+             WHERE type IN ('table', 'view', 'trigger') AND name NOT LIKE 'sqlite_%' \
+             ORDER BY CASE type WHEN 'trigger' THEN 0 WHEN 'view' THEN 1 ELSE 2 END",
+        )
+        .fetch_all(&mut *self)
+        .await?;
+
+        if !temp_objects.is_empty() {
+            let mut drop_objects = String::new();
+
+            for (kind, name) in temp_objects {
+                let name = name.replace('"', "\"\"");
+                let _ = writeln!(drop_objects, "DROP {kind} IF EXISTS temp.\"{name}\";");
+            }
+
+            self.execute(AssertSqlSafe(drop_objects)).await?;
+        }
+
+        if mode == ResetMode::All {
+            self.worker.clear_cache().await?;
+        }
+
+        Ok(())
+    }
+
     #[inline]
     fn shrink_buffers(&mut self) {
         // No-op.
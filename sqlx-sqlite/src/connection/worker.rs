@@ -16,7 +16,7 @@ use sqlx_core::Either;
 
 use crate::connection::establish::EstablishParams;
 use crate::connection::execute;
-use crate::connection::ConnectionState;
+use crate::connection::{ConnectionState, InterruptHandle};
 use crate::{SqliteArguments, SqliteQueryResult, SqliteRow, SqliteStatement};
 
 #[cfg(feature = "deserialize")]
@@ -38,6 +38,8 @@ pub(crate) struct WorkerSharedState {
     transaction_depth: AtomicUsize,
     cached_statements_size: AtomicUsize,
     pub(crate) conn: Mutex<ConnectionState>,
+    /// Interrupts the running statement without waiting for `conn` to be unlocked.
+    interrupt: InterruptHandle,
 }
 
 impl WorkerSharedState {
@@ -48,6 +50,12 @@ impl WorkerSharedState {
     pub(crate) fn get_cached_statements_size(&self) -> usize {
         self.cached_statements_size.load(Ordering::Acquire)
     }
+
+    /// Interrupt the statement currently running on the worker thread, if any.
+    pub(crate) fn interrupt(&self) {
+        // SAFETY: the handle is owned by `self.conn`, so it stays open as long as `self` lives.
+        unsafe { self.interrupt.interrupt() }
+    }
 }
 
 enum Command {
@@ -121,6 +129,7 @@ impl ConnectionWorker {
                 let shared = Arc::new(WorkerSharedState {
                     transaction_depth: AtomicUsize::new(0),
                     cached_statements_size: AtomicUsize::new(0),
+                    interrupt: conn.handle.interrupt_handle(),
                     // note: must be fair because in `Command::UnlockDb` we unlock the mutex
                     // and then immediately try to relock it; an unfair mutex would immediately
                     // grant us the lock even if another task is waiting.
@@ -5,9 +5,10 @@ use std::os::raw::c_int;
 use std::{borrow::Cow, str};
 
 use libsqlite3_sys::{
-    sqlite3, sqlite3_errmsg, sqlite3_errstr, sqlite3_extended_errcode, SQLITE_CONSTRAINT_CHECK,
-    SQLITE_CONSTRAINT_FOREIGNKEY, SQLITE_CONSTRAINT_NOTNULL, SQLITE_CONSTRAINT_PRIMARYKEY,
-    SQLITE_CONSTRAINT_UNIQUE, SQLITE_ERROR, SQLITE_NOMEM,
+    sqlite3, sqlite3_errmsg, sqlite3_errstr, sqlite3_extended_errcode, SQLITE_BUSY,
+    SQLITE_CONSTRAINT_CHECK, SQLITE_CONSTRAINT_FOREIGNKEY, SQLITE_CONSTRAINT_NOTNULL,
+    SQLITE_CONSTRAINT_PRIMARYKEY, SQLITE_CONSTRAINT_UNIQUE, SQLITE_ERROR, SQLITE_INTERRUPT,
+    SQLITE_LOCKED, SQLITE_NOMEM,
 };
 
 pub(crate) use sqlx_core::error::*;
@@ -19,6 +20,7 @@ pub(crate) use sqlx_core::error::*;
 pub struct SqliteError {
     code: c_int,
     message: Cow<'static, str>,
+    offset: Option<usize>,
 }
 
 impl SqliteError {
@@ -45,9 +47,34 @@ impl SqliteError {
         Some(Self {
             code,
             message: message.into(),
+            offset: None,
         })
     }
 
+    /// For errors while preparing a statement, record the byte offset of the offending token,
+    /// where `sql_offset` is the offset of the statement in the query.
+    ///
+    /// `sqlite3_error_offset()` was added in SQLite 3.38.0, so the offset is only known
+    /// with the bundled SQLite.
+    #[cfg_attr(not(feature = "bundled"), allow(unused_mut, unused_variables))]
+    pub(crate) unsafe fn with_error_offset(
+        mut self,
+        handle: *mut sqlite3,
+        sql_offset: usize,
+    ) -> Self {
+        // returns -1 if the error does not refer to a token in the SQL
+        #[cfg(feature = "bundled")]
+        {
+            let offset = unsafe { libsqlite3_sys::sqlite3_error_offset(handle) };
+
+            self.offset = usize::try_from(offset)
+                .ok()
+                .map(|offset| sql_offset + offset);
+        }
+
+        self
+    }
+
     /// For errors during extension load, the error message is supplied via a separate pointer
     #[allow(dead_code)]
     pub(crate) fn with_message(mut self, error_msg: String) -> Self {
@@ -55,6 +82,11 @@ impl SqliteError {
         self
     }
 
+    /// Returns `true` if the statement was interrupted with `sqlite3_interrupt()`.
+    pub(crate) fn is_interrupt(&self) -> bool {
+        self.code & 0xFF == SQLITE_INTERRUPT
+    }
+
     #[allow(dead_code)]
     pub(crate) fn from_code(code: c_int) -> Self {
         let message = unsafe {
@@ -71,7 +103,11 @@ impl SqliteError {
             }
         };
 
-        SqliteError { code, message }
+        SqliteError {
+            code,
+            message,
+            offset: None,
+        }
     }
 
     #[allow(dead_code)]
@@ -79,6 +115,7 @@ impl SqliteError {
         Self {
             code: SQLITE_ERROR,
             message: message.into(),
+            offset: None,
         }
     }
 
@@ -86,6 +123,13 @@ impl SqliteError {
     pub(crate) fn nomem() -> Self {
         Self::from_code(SQLITE_NOMEM)
     }
+
+    /// The byte offset into the query of the token that caused the error, if SQLite reports one.
+    ///
+    /// Only known for errors while preparing a statement, and only with the bundled SQLite.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
 }
 
 impl Display for SqliteError {
@@ -136,4 +180,10 @@ impl DatabaseError for SqliteError {
             _ => ErrorKind::Other,
         }
     }
+
+    fn is_transient(&self) -> bool {
+        // The primary result code is the least significant byte of the extended result code:
+        // https://www.sqlite.org/rescode.html#primary_result_codes_versus_extended_result_codes
+        matches!(self.code & 0xFF, SQLITE_BUSY | SQLITE_LOCKED)
+    }
 }
@@ -96,12 +96,48 @@ CREATE TABLE IF NOT EXISTS {table_name} (
     installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
     success BOOLEAN NOT NULL,
     checksum BLOB NOT NULL,
-    execution_time BIGINT NOT NULL
+    execution_time BIGINT NOT NULL,
+    squashed_from BIGINT,
+    installed_rank BIGINT
 );
                 "#
             )))
             .await?;
 
+            // added for `sqlx migrate squash`; SQLite has no `ADD COLUMN IF NOT EXISTS`
+            // language=SQLite
+            let has_squashed_from = self
+                .execute(AssertSqlSafe(format!(
+                    "SELECT squashed_from FROM {table_name} LIMIT 0"
+                )))
+                .await
+                .is_ok();
+
+            if !has_squashed_from {
+                // language=SQLite
+                self.execute(AssertSqlSafe(format!(
+                    "ALTER TABLE {table_name} ADD COLUMN squashed_from BIGINT"
+                )))
+                .await?;
+            }
+
+            // added for out-of-order migrations, to record the order they were applied in
+            // language=SQLite
+            let has_installed_rank = self
+                .execute(AssertSqlSafe(format!(
+                    "SELECT installed_rank FROM {table_name} LIMIT 0"
+                )))
+                .await
+                .is_ok();
+
+            if !has_installed_rank {
+                // language=SQLite
+                self.execute(AssertSqlSafe(format!(
+                    "ALTER TABLE {table_name} ADD COLUMN installed_rank BIGINT"
+                )))
+                .await?;
+            }
+
             Ok(())
         })
     }
@@ -127,19 +163,29 @@ CREATE TABLE IF NOT EXISTS {table_name} (
         table_name: &'e str,
     ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
         Box::pin(async move {
+            #[allow(clippy::type_complexity)]
             // language=SQLite
-            let rows: Vec<(i64, Vec<u8>)> = query_as(AssertSqlSafe(format!(
-                "SELECT version, checksum FROM {table_name} ORDER BY version"
-            )))
-            .fetch_all(self)
-            .await?;
+            let rows: Vec<(i64, Vec<u8>, Option<i64>, Option<i64>)> =
+                query_as(AssertSqlSafe(format!(
+                    r#"
+    SELECT version, checksum, squashed_from, installed_rank
+    FROM {table_name}
+    ORDER BY version
+                    "#
+                )))
+                .fetch_all(self)
+                .await?;
 
             let migrations = rows
                 .into_iter()
-                .map(|(version, checksum)| AppliedMigration {
-                    version,
-                    checksum: checksum.into(),
-                })
+                .map(
+                    |(version, checksum, squashed_from, installed_rank)| AppliedMigration {
+                        version,
+                        checksum: checksum.into(),
+                        squashed_from,
+                        installed_rank,
+                    },
+                )
                 .collect();
 
             Ok(migrations)
@@ -198,6 +244,104 @@ CREATE TABLE IF NOT EXISTS {table_name} (
         })
     }
 
+    fn squash<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQLite
+            let _ = query(AssertSqlSafe(format!(
+                r#"
+    UPDATE {table_name}
+    SET description = ?1, checksum = ?2, squashed_from = ?3
+    WHERE version = ?4
+                "#
+            )))
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .bind(migration.squashed_from)
+            .bind(migration.version)
+            .execute(self)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn repair<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQLite
+            let _ = query(AssertSqlSafe(format!(
+                r#"
+    UPDATE {table_name}
+    SET description = ?1, checksum = ?2
+    WHERE version = ?3
+                "#
+            )))
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .bind(migration.version)
+            .execute(self)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn mark_applied<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQLite
+            let _ = query(AssertSqlSafe(format!(
+                r#"
+    INSERT INTO {table_name} ( version, description, success, checksum, execution_time, squashed_from, installed_rank )
+    SELECT ?1, ?2, TRUE, ?3, -1, ?4, COALESCE(MAX(installed_rank), 0) + 1 FROM {table_name}
+                "#
+            )))
+            .bind(migration.version)
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .bind(migration.squashed_from)
+            .execute(self)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn mark_dirty_resolved<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+        applied: bool,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQLite
+            let sql = if applied {
+                format!(
+                    "UPDATE {table_name} SET success = TRUE WHERE version = ?1 AND success = FALSE"
+                )
+            } else {
+                format!("DELETE FROM {table_name} WHERE version = ?1 AND success = FALSE")
+            };
+
+            let _ = query(AssertSqlSafe(sql))
+                .bind(version)
+                .execute(self)
+                .await?;
+
+            Ok(())
+        })
+    }
+
     fn revert<'e>(
         &'e mut self,
         table_name: &'e str,
@@ -228,21 +372,19 @@ async fn execute_migration(
     table_name: &str,
     migration: &Migration,
 ) -> Result<(), MigrateError> {
-    let _ = conn
-        .execute(migration.sql.clone())
-        .await
-        .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
+    migration.execute(conn).await?;
 
     // language=SQLite
     let _ = query(AssertSqlSafe(format!(
         r#"
-    INSERT INTO {table_name} ( version, description, success, checksum, execution_time )
-    VALUES ( ?1, ?2, TRUE, ?3, -1 )
+    INSERT INTO {table_name} ( version, description, success, checksum, execution_time, squashed_from, installed_rank )
+    SELECT ?1, ?2, TRUE, ?3, -1, ?4, COALESCE(MAX(installed_rank), 0) + 1 FROM {table_name}
         "#
     )))
     .bind(migration.version)
     .bind(&*migration.description)
     .bind(&*migration.checksum)
+    .bind(migration.squashed_from)
     .execute(conn)
     .await?;
 
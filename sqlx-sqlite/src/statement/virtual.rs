@@ -31,6 +31,10 @@ pub struct VirtualStatement {
     /// there are no more statements to execute and `reset()` must be called
     index: Option<usize>,
 
+    /// the end of the query, as an offset into the query string passed to `new()`;
+    /// the tail starts at `query_end - tail.len()`
+    query_end: usize,
+
     /// tail of the most recently prepared SQL statement within this container
     tail: Bytes,
 
@@ -53,8 +57,9 @@ pub struct PreparedStatement<'a> {
 }
 
 impl VirtualStatement {
-    pub(crate) fn new(mut query: &str, persistent: bool) -> Result<Self, Error> {
-        query = query.trim();
+    pub(crate) fn new(query: &str, persistent: bool) -> Result<Self, Error> {
+        let query_end = query.trim_end().len();
+        let query = query.trim();
 
         if query.len() > i32::MAX as usize {
             return Err(err_protocol!(
@@ -65,6 +70,7 @@ impl VirtualStatement {
 
         Ok(Self {
             persistent,
+            query_end,
             tail: Bytes::from(String::from(query)),
             handles: SmallVec::with_capacity(1),
             index: None,
@@ -88,7 +94,11 @@ impl VirtualStatement {
                 return Ok(None);
             }
 
-            if let Some(statement) = prepare(conn.as_ptr(), &mut self.tail, self.persistent)? {
+            let offset = self.query_end - self.tail.len();
+
+            if let Some(statement) =
+                prepare(conn.as_ptr(), &mut self.tail, offset, self.persistent)?
+            {
                 let num = statement.column_count();
 
                 let mut columns = Vec::with_capacity(num);
@@ -141,9 +151,11 @@ impl VirtualStatement {
     }
 }
 
+/// `offset` is where `query` starts in the query the statements are prepared from.
 fn prepare(
     conn: *mut sqlite3,
     query: &mut Bytes,
+    mut offset: usize,
     persistent: bool,
 ) -> Result<Option<StatementHandle>, Error> {
     let mut flags = 0;
@@ -185,7 +197,11 @@ fn prepare(
         };
 
         if status != SQLITE_OK {
-            return Err(unsafe { SqliteError::new(conn).into() });
+            return Err(unsafe {
+                SqliteError::new(conn)
+                    .with_error_offset(conn, offset)
+                    .into()
+            });
         }
 
         // tail should point to the first byte past the end of the first SQL
@@ -194,6 +210,7 @@ fn prepare(
 
         let n = (tail as usize) - (query_ptr as usize);
         query.advance(n);
+        offset += n;
 
         if let Some(handle) = NonNull::new(statement_handle) {
             return Ok(Some(StatementHandle::new(handle)));
@@ -1,7 +1,8 @@
 use std::future::Future;
 
-use sqlx_core::transaction::TransactionManager;
-use sqlx_core::{error::Error, sql_str::SqlStr};
+use sqlx_core::error::Error;
+use sqlx_core::sql_str::{SqlSafeStr, SqlStr};
+use sqlx_core::transaction::{IsolationLevel, TransactionManager, TransactionOptions};
 
 use crate::{Sqlite, SqliteConnection};
 
@@ -15,6 +16,21 @@ impl TransactionManager for SqliteTransactionManager {
         conn.worker.begin(statement).await
     }
 
+    fn begin_statement(_conn: &SqliteConnection, options: &TransactionOptions) -> SqlStr {
+        // SQLite transactions are always serializable;
+        // stricter isolation levels take their locks earlier instead.
+        let statement = match options.get_isolation() {
+            _ if options.get_read_only() => "BEGIN DEFERRED",
+            Some(IsolationLevel::Serializable) => "BEGIN EXCLUSIVE",
+            Some(IsolationLevel::RepeatableRead) => "BEGIN IMMEDIATE",
+            Some(IsolationLevel::ReadCommitted | IsolationLevel::ReadUncommitted) | None => {
+                "BEGIN DEFERRED"
+            }
+        };
+
+        statement.into_sql_str()
+    }
+
     fn commit(conn: &mut SqliteConnection) -> impl Future<Output = Result<(), Error>> + Send + '_ {
         conn.worker.commit()
     }
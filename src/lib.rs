@@ -14,7 +14,7 @@ pub use sqlx_core::arguments::{Arguments, IntoArguments};
 pub use sqlx_core::column::Column;
 pub use sqlx_core::column::ColumnIndex;
 pub use sqlx_core::column::ColumnOrigin;
-pub use sqlx_core::connection::{ConnectOptions, Connection};
+pub use sqlx_core::connection::{ConnectOptions, Connection, ResetMode};
 pub use sqlx_core::database::{self, Database};
 pub use sqlx_core::describe::Describe;
 pub use sqlx_core::executor::{Execute, Executor};
@@ -28,11 +28,14 @@ pub use sqlx_core::query_builder::{self, QueryBuilder};
 #[doc(hidden)]
 pub use sqlx_core::query_scalar::query_scalar_with_result as __query_scalar_with_result;
 pub use sqlx_core::query_scalar::{query_scalar, query_scalar_with};
+#[doc(hidden)]
+pub use sqlx_core::query_script::query_script as __query_script;
 pub use sqlx_core::raw_sql::{raw_sql, RawSql};
+pub use sqlx_core::retry::{self, RetryPolicy};
 pub use sqlx_core::row::Row;
 pub use sqlx_core::sql_str::{AssertSqlSafe, SqlSafeStr, SqlStr};
 pub use sqlx_core::statement::Statement;
-pub use sqlx_core::transaction::Transaction;
+pub use sqlx_core::transaction::{IsolationLevel, Transaction, TransactionOptions};
 pub use sqlx_core::type_info::TypeInfo;
 pub use sqlx_core::types::Type;
 pub use sqlx_core::value::{Value, ValueRef};
@@ -109,6 +112,9 @@ pub mod spec_error;
 #[doc(hidden)]
 pub use sqlx_core::rt as __rt;
 
+#[doc(hidden)]
+pub use sqlx_core::from_row as __from_row;
+
 /// Conversions between Rust and SQL types.
 ///
 /// To see how each SQL type maps to a Rust type, see the corresponding `types` module for each
@@ -158,6 +164,7 @@ pub mod query {
     pub use sqlx_core::query::{Map, Query};
     pub use sqlx_core::query_as::QueryAs;
     pub use sqlx_core::query_scalar::QueryScalar;
+    pub use sqlx_core::query_script::Script;
 }
 
 /// Convenience re-export of common traits.
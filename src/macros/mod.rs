@@ -84,11 +84,15 @@
 /// * global type overrides (useful for custom types!)
 /// * per-column type overrides
 /// * force use of a specific crate (e.g. `chrono` when both it and `time` are enabled)
+/// * check SQLite queries against an in-memory database built from the crate's migrations,
+///   instead of `DATABASE_URL` or `.sqlx` (`macros.sqlite-from-migrations`)
+/// * check queries for the `Any` driver against several databases (`macros.any-backends`,
+///   see [below](#checking-queries-for-any))
 ///
 /// See the [configuration guide] and [reference `sqlx.toml`] for details.
 ///
-/// See also `examples/postgres/multi-database` and `examples/postgres/preferred-crates`
-/// for example usage.
+/// See also `examples/postgres/multi-database`, `examples/postgres/preferred-crates`,
+/// `examples/sqlite/from-migrations` and `examples/any/multi-backend` for example usage.
 ///
 /// [configuration guide]: crate::_config::macros::Config
 /// [reference `sqlx.toml`]: crate::_config::_reference
@@ -129,6 +133,58 @@
 /// * Postgres: `$N` where `N` is the 1-based positional argument index
 /// * MySQL/SQLite: `?` which matches arguments in order that it appears in the query
 ///
+/// ## Named Parameters
+/// Instead of positional arguments, the query may use named parameters, `:name` or `@name`
+/// (only `:name` on MySQL, where `@name` is a user-defined variable). Each is bound to a
+/// `name = expr` argument if one is given, or otherwise to the variable `name` in scope,
+/// like the implicit arguments of `format!()`:
+///
+/// ```rust,ignore
+/// let id = 1i32;
+///
+/// let account = sqlx::query!(
+///         "select * from accounts where id = :id and name = :name",
+///         name = "Herp Derpinson"
+///     )
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
+/// Named parameters are rewritten to the native syntax of the database before the query is
+/// checked. A name used more than once is bound once on Postgres (as `$N`) and once per
+/// occurrence on MySQL and SQLite.
+///
+/// It is an error to pass a `name = expr` argument that the query does not use, or to mix named
+/// and positional arguments.
+///
+/// If only positional arguments are passed, the query is left as-is.
+///
+/// On Postgres, the prefixes are also SQL syntax: `@` is the absolute value operator, and `:`
+/// separates the bounds of array slices such as `arr[:hi]`. So named parameters are only
+/// recognized there if at least one argument is passed by name; to bind only variables in scope,
+/// pass one of them explicitly, e.g. `id = id`. In such a query, separate the prefix from the
+/// following name with a space to keep it as SQL, e.g. `@ x` or `arr[: hi]`.
+///
+/// ## List Parameters (MySQL and SQLite)
+/// MySQL and SQLite have no array type to bind for `WHERE id IN (...)`, so a parameter followed
+/// by `...` (`?...`, or `:name...` for a named parameter) binds each element of its argument
+/// instead. The argument may be anything with an `.iter()` method, such as a `Vec` or slice:
+///
+/// ```rust,ignore
+/// let ids: Vec<i64> = vec![1, 2, 3];
+///
+/// let accounts = sqlx::query!("select * from accounts where id in (?...)", ids)
+///     .fetch_all(&mut conn)
+///     .await?;
+/// ```
+///
+/// The query is checked with a single parameter in place of the list, against which the type of
+/// the elements is checked. At runtime the placeholder is repeated for each element, so each
+/// length of list is a distinct prepared statement. An empty list is expanded to a subquery
+/// without rows, so `IN (?...)` matches no rows and `NOT IN (?...)` matches all of them.
+///
+/// On Postgres, bind an array instead: `WHERE id = ANY($1)`.
+///
 /// ## Nullability: Bind Parameters
 /// For a given expected type `T`, both `T` and `Option<T>` are allowed (as well as either
 /// behind references). `Option::None` will be bound as `NULL`, so if binding a type behind `Option`
@@ -304,6 +360,69 @@
 /// | `foo!: T` | Forced not-null | Overridden |
 /// | `foo?: T` | Forced nullable | Overridden |
 ///
+/// ##### Overriding Nullability in `sqlx.toml`
+/// If the same column or function is selected in many queries, its nullability can instead be
+/// overridden once with `macros.nullability-overrides` (for columns of a table or view)
+/// or `macros.function-nullability` (for columns computed by a call to a function)
+/// in `sqlx.toml`. Inline `!` and `?` overrides take precedence.
+///
+/// ```toml
+/// [macros.nullability-overrides.'active_users']
+/// 'id' = "non-null"
+///
+/// [macros.function-nullability]
+/// 'coalesce_user' = "non-null"
+/// ```
+///
+/// See the documentation of `sqlx::_config::macros::Config` for details.
+///
+/// ## Record Attributes
+/// The generated record type always derives `Debug`. Attributes placed before the query string
+/// are added to it, e.g. to derive more traits:
+///
+/// ```rust,ignore
+/// let accounts = sqlx::query!(
+///     #[derive(serde::Serialize)]
+///     #[serde(rename_all = "camelCase")]
+///     "select id, display_name from accounts"
+/// )
+/// .fetch_all(&mut conn)
+/// .await?;
+///
+/// // e.g. return `axum::Json(accounts)` from a handler
+/// ```
+///
+/// With attributes, the query must be a single string literal.
+/// To add derives to every generated record type, set `macros.record-derives` in `sqlx.toml`.
+///
+/// The record type is still anonymous, so it can only be named through inference,
+/// e.g. `impl Serialize`. Use [`query_as!`][`crate::query_as!`] with your own struct if you need to
+/// name the type, such as in the signature of a function.
+///
+/// ## Errors from the Database
+/// If the database rejects the query, the error shows the line of the query it is about,
+/// with the offending token marked, along with any detail or hint from Postgres:
+///
+/// ```text
+/// error: error returned from database: column "nme" does not exist
+///         --> line 1, column 8 of the query
+///          |
+///        1 | SELECT nme FROM accounts
+///          |        ^^^
+///          = hint: Perhaps you meant to reference the column "accounts.name".
+/// ```
+///
+/// For [`query_file!`][`crate::query_file!`], the location is given as `path:line:column`
+/// in the query file. On compilers which support spans within a string literal
+/// (currently only nightly), the error also points at the token in the literal itself.
+///
+/// Postgres reports the exact position of an error. For MySQL and SQLite, the position is
+/// found from the part of the query quoted in the message, so it is not always available.
+///
+/// Errors for [`query_file_script!`][`crate::query_file_script!`], and for queries checked
+/// against several databases with `macros.any-backends`, are reported as the database returned
+/// them, without the marked line.
+///
 /// ## Offline Mode
 /// The macros can be configured to not require a live database connection for compilation,
 /// but it requires a couple extra steps:
@@ -324,13 +443,43 @@
 ///
 /// See [the README for `sqlx-cli`](https://crates.io/crates/sqlx-cli) for more information.
 ///
+/// ## Checking Queries for `Any`
+/// A crate written against [`AnyPool`][crate::AnyPool] can list the databases it supports in
+/// `sqlx.toml`, each with the environment variable that holds a URL for it:
+///
+/// ```toml
+/// [macros.any-backends]
+/// postgres = "POSTGRES_DATABASE_URL"
+/// sqlite = "SQLITE_DATABASE_URL"
+/// ```
+///
+/// The macros then expand for `Any`, and check each query against every listed backend
+/// (or the data saved for it by `cargo sqlx prepare`). A query only compiles if the backends agree
+/// on its parameters and columns, as mapped to [`AnyTypeInfoKind`][crate::any::AnyTypeInfoKind].
+/// Integer columns read as the widest integer type any backend reports, and integer parameters
+/// take the narrowest.
+///
+/// The query is sent to the database as written, so it must be valid SQL for every backend,
+/// and named parameters are not supported. Postgres and SQLite both accept `$1`, `$2`, etc.
+///
+/// `query_dyn!()` is not supported in this mode. Requires the `any` feature.
+///
 /// ## See Also
 /// * [`query_as!`][`crate::query_as!`] if you want to use a struct you can name,
 /// * [`query_file!`][`crate::query_file!`] if you want to define the SQL query out-of-line,
 /// * [`query_file_as!`][`crate::query_file_as!`] if you want both of the above.
+/// * [`query_dyn!`][`crate::query_dyn!`] if you want to add optional clauses to the query.
+/// * [`query_file_script!`][`crate::query_file_script!`] if you want to run a script of several statements.
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query (
+    // attributes for the record type; `#[attr] expr` is also an expression, so take a literal
+    ($(#[$attr:meta])+ $query:literal) => ({
+        $crate::sqlx_macros::expand_query!(attrs = [$(#[$attr])*], source = $query)
+    });
+    ($(#[$attr:meta])+ $query:literal, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(attrs = [$(#[$attr])*], source = $query, args = [$($args)*])
+    });
     // in Rust 1.45 we can now invoke proc macros in expression position
     ($query:expr) => ({
         $crate::sqlx_macros::expand_query!(source = $query)
@@ -353,6 +502,12 @@ macro_rules! query (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_unchecked (
+    ($(#[$attr:meta])+ $query:literal) => ({
+        $crate::sqlx_macros::expand_query!(attrs = [$(#[$attr])*], source = $query, checked = false)
+    });
+    ($(#[$attr:meta])+ $query:literal, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(attrs = [$(#[$attr])*], source = $query, args = [$($args)*], checked = false)
+    });
     ($query:expr) => ({
         $crate::sqlx_macros::expand_query!(source = $query, checked = false)
     });
@@ -406,11 +561,11 @@ macro_rules! query_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file (
-    ($path:literal) => ({
-        $crate::sqlx_macros::expand_query!(source_file = $path)
+    ($(#[$attr:meta])* $path:literal) => ({
+        $crate::sqlx_macros::expand_query!(attrs = [$(#[$attr])*], source_file = $path)
     });
-    ($path:literal, $($args:tt)*) => ({
-        $crate::sqlx_macros::expand_query!(source_file = $path, args = [$($args)*])
+    ($(#[$attr:meta])* $path:literal, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(attrs = [$(#[$attr])*], source_file = $path, args = [$($args)*])
     })
 );
 
@@ -420,11 +575,11 @@ macro_rules! query_file (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_unchecked (
-    ($path:literal) => ({
-        $crate::sqlx_macros::expand_query!(source_file = $path, checked = false)
+    ($(#[$attr:meta])* $path:literal) => ({
+        $crate::sqlx_macros::expand_query!(attrs = [$(#[$attr])*], source_file = $path, checked = false)
     });
-    ($path:literal, $($args:tt)*) => ({
-        $crate::sqlx_macros::expand_query!(source_file = $path, args = [$($args)*], checked = false)
+    ($(#[$attr:meta])* $path:literal, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(attrs = [$(#[$attr])*], source_file = $path, args = [$($args)*], checked = false)
     })
 );
 
@@ -534,6 +689,51 @@ macro_rules! query_file_unchecked (
 /// assert_eq!(record.id, MyInt4(1));
 /// ```
 ///
+/// ### Mapping Rows with `FromRow`
+/// By default, the struct is built from the columns of the query by name, so attributes for
+/// [`FromRow`][crate::FromRow] have no effect. Write the struct as `Type: FromRow` to map each row
+/// with its `FromRow` impl instead, which honors `#[sqlx(rename)]`, `#[sqlx(rename_all)]`,
+/// `#[sqlx(flatten)]`, `#[sqlx(try_from)]`, `#[sqlx(json)]`, `#[sqlx(default)]`
+/// and `#[sqlx(skip)]`:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// struct Account {
+///     id: i64,
+///     #[sqlx(rename = "name")]
+///     display_name: String,
+///     #[sqlx(flatten)]
+///     address: Address,
+/// }
+///
+/// #[derive(sqlx::FromRow)]
+/// struct Address {
+///     street: String,
+///     city: String,
+/// }
+///
+/// let account = sqlx::query_as!(
+///         Account: FromRow,
+///         "select id, name, street, city from accounts where id = ?",
+///         1i64
+///     )
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
+/// The struct and any types it flattens must `#[derive(FromRow)]`, so that the columns they read
+/// are known. It is then checked at compile time that:
+///
+/// * every column that is read, except for `#[sqlx(default)]` fields, is returned by the query;
+/// * every column that is read has the type of its field, or of its `#[sqlx(try_from)]` type.
+///   `#[sqlx(json)]` fields, and the fields of types that are `#[sqlx(flatten)]`ed, are only
+///   checked at runtime; a flattened type's columns are only checked to be returned.
+///
+/// Columns are read by their name in the query, so the overrides in column names described for
+/// [`query!`][`crate::query!`] become part of the name and will not match a field.
+/// As the nullability of a column then cannot be overridden, a field may be non-`Option`
+/// for a column that is inferred to be nullable; decoding a `NULL` to it fails at runtime.
+///
 /// ### Troubleshooting: "error: mismatched types"
 /// If you get a "mismatched types" error from an invocation of this macro and the error
 /// isn't pointing specifically at a parameter.
@@ -588,6 +788,12 @@ macro_rules! query_file_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_as (
+    ($out_struct:path: FromRow, $query:expr) => ({
+        $crate::sqlx_macros::expand_query!(record_from_row = $out_struct, source = $query)
+    });
+    ($out_struct:path: FromRow, $query:expr, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(record_from_row = $out_struct, source = $query, args = [$($args)*])
+    });
     ($out_struct:path, $query:expr) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query)
     });
@@ -632,6 +838,12 @@ macro_rules! query_as (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_as (
+    ($out_struct:path: FromRow, $path:literal) => ({
+        $crate::sqlx_macros::expand_query!(record_from_row = $out_struct, source_file = $path)
+    });
+    ($out_struct:path: FromRow, $path:literal, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(record_from_row = $out_struct, source_file = $path, args = [$($args)*])
+    });
     ($out_struct:path, $path:literal) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path)
     });
@@ -645,6 +857,12 @@ macro_rules! query_file_as (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_as_unchecked (
+    ($out_struct:path: FromRow, $query:expr) => ({
+        $crate::sqlx_macros::expand_query!(record_from_row = $out_struct, source = $query, checked = false)
+    });
+    ($out_struct:path: FromRow, $query:expr, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(record_from_row = $out_struct, source = $query, args = [$($args)*], checked = false)
+    });
     ($out_struct:path, $query:expr) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, checked = false)
     });
@@ -660,6 +878,12 @@ macro_rules! query_as_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_as_unchecked (
+    ($out_struct:path: FromRow, $path:literal) => ({
+        $crate::sqlx_macros::expand_query!(record_from_row = $out_struct, source_file = $path, checked = false)
+    });
+    ($out_struct:path: FromRow, $path:literal, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(record_from_row = $out_struct, source_file = $path, args = [$($args)*], checked = false)
+    });
     ($out_struct:path, $path:literal) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path, checked = false)
     });
@@ -743,6 +967,177 @@ macro_rules! query_file_scalar_unchecked (
     )
 );
 
+/// A query with optional clauses, where every combination of the clauses is checked at compile
+/// time.
+///
+/// The first argument is the query; each following argument is a clause which is appended to it,
+/// separated by a space. A clause is either a string literal, which is always appended, or
+/// `if <condition> { "<SQL>" }`, which is only appended if the condition is true:
+///
+/// ```rust,ignore
+/// let status: Option<String> = ...;
+/// let min_id: i64 = ...;
+///
+/// let accounts = sqlx::query_dyn!(
+///     "SELECT id, name FROM accounts WHERE id >= {min_id}",
+///     if let Some(status) = &status { "AND status = {status}" },
+///     if only_named { "AND name IS NOT NULL" },
+///     "ORDER BY id",
+/// )
+/// .fetch_all(&mut conn)
+/// .await?;
+/// ```
+///
+/// Parameters are written as `{name}` to bind the variable `name` in scope, including bindings
+/// introduced by `if let` in the condition of the clause; a literal `{` or `}` is written
+/// as `{{` or `}}`.
+///
+/// Every combination of the optional clauses is checked against the database like a
+/// [`query!`][`crate::query!`], so at most 8 optional clauses are allowed per query.
+/// The query is then assembled at runtime from the SQL of the checked combinations only.
+/// Every combination must return the same columns. A column is inferred to be nullable if it
+/// is nullable in any of them.
+///
+/// The output is an anonymous record type like with [`query!`][`crate::query!`].
+/// Use [`query_dyn_as!`][`crate::query_dyn_as!`] to output a named struct instead.
+///
+/// Each combination is a separate query for [offline mode](crate::query!#offline-mode), so
+/// `cargo sqlx prepare` saves the data for all of them.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_dyn (
+    ($(#[$attr:meta])+ $query:literal) => ({
+        $crate::sqlx_macros::expand_query_dyn!(attrs = [$(#[$attr])*], source = $query)
+    });
+    ($(#[$attr:meta])+ $query:literal, $($clauses:tt)*) => ({
+        $crate::sqlx_macros::expand_query_dyn!(attrs = [$(#[$attr])*], source = $query, clauses = [$($clauses)*])
+    });
+    ($query:expr) => ({
+        $crate::sqlx_macros::expand_query_dyn!(source = $query)
+    });
+    ($query:expr, $($clauses:tt)*) => ({
+        $crate::sqlx_macros::expand_query_dyn!(source = $query, clauses = [$($clauses)*])
+    })
+);
+
+/// Combines the syntaxes of [`query_as!`][`crate::query_as!`] and [`query_dyn!`][`crate::query_dyn!`].
+///
+/// Enforces requirements of both macros; see them for details.
+///
+/// ```rust,ignore
+/// let accounts = sqlx::query_dyn_as!(
+///     Account,
+///     "SELECT id, name FROM accounts",
+///     if let Some(name) = &name { "WHERE name = {name}" },
+/// )
+/// .fetch_all(&mut conn)
+/// .await?;
+/// ```
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_dyn_as (
+    ($out_struct:path: FromRow, $query:expr) => ({
+        $crate::sqlx_macros::expand_query_dyn!(record_from_row = $out_struct, source = $query)
+    });
+    ($out_struct:path: FromRow, $query:expr, $($clauses:tt)*) => ({
+        $crate::sqlx_macros::expand_query_dyn!(record_from_row = $out_struct, source = $query, clauses = [$($clauses)*])
+    });
+    ($out_struct:path, $query:expr) => ({
+        $crate::sqlx_macros::expand_query_dyn!(record = $out_struct, source = $query)
+    });
+    ($out_struct:path, $query:expr, $($clauses:tt)*) => ({
+        $crate::sqlx_macros::expand_query_dyn!(record = $out_struct, source = $query, clauses = [$($clauses)*])
+    })
+);
+
+/// Checks and runs a SQL script of several statements, returning the output of the last.
+///
+/// The file is split into statements at each `;` outside of string literals, quoted identifiers,
+/// comments and dollar-quoted strings. Each statement is checked against the database in order,
+/// like a [`query!`][`crate::query!`], on one connection and in a transaction which is rolled
+/// back afterwards. Statements without parameters are also executed as they are checked, so
+/// later statements can refer to their effects, e.g. a temporary table:
+///
+/// `queries/active-accounts.sql`:
+/// ```sql
+/// CREATE TEMPORARY TABLE active_ids ON COMMIT DROP AS
+///     SELECT account_id AS id FROM sessions WHERE expires_at > now();
+/// DELETE FROM active_ids WHERE id IN (SELECT account_id FROM bans);
+/// SELECT accounts.id, accounts.name FROM accounts JOIN active_ids USING (id) WHERE accounts.id > $1;
+/// ```
+///
+/// ```rust,ignore
+/// let accounts = sqlx::query_file_script!("queries/active-accounts.sql", min_id)
+///     .in_transaction()
+///     .fetch_all(&pool)
+///     .await?;
+/// ```
+///
+/// The output is an anonymous record type for the columns of the last statement, like with
+/// [`query!`][`crate::query!`]; use [`query_file_script_as!`][`crate::query_file_script_as!`]
+/// to output a named struct instead. If the last statement returns no columns, use `.execute()`.
+///
+/// The result is a [`Script`][crate::query::Script], which runs the statements in order on one
+/// connection acquired from the given pool or connection, or in a transaction with
+/// `.in_transaction()`.
+///
+/// Arguments are positional; named parameters are not supported. With `$N` parameters
+/// (Postgres), every statement can refer to any of the arguments. With `?` parameters
+/// (MySQL and SQLite), each statement binds the arguments after those bound by the statements
+/// before it.
+///
+/// Each statement is a separate query for [offline mode](crate::query!#offline-mode).
+/// Not supported with `macros.sqlite-from-migrations` or `macros.any-backends` in `sqlx.toml`.
+///
+/// ### Note: Statements That Commit
+/// Statements which commit implicitly, such as `CREATE TABLE` in MySQL, take effect when
+/// they are checked. Use a scratch database for `DATABASE_URL` if a script contains any.
+///
+/// ### Note: Temporary Objects
+/// Temporary tables and other objects created by a script last as long as the connection,
+/// not the script. Connections from a pool are reused, so a later run of the script on the
+/// same connection would fail to create them again, or see the rows left by the last run.
+/// In Postgres, create them with `ON COMMIT DROP` and run the script `.in_transaction()`,
+/// as above. Otherwise, start the script with `DROP TABLE IF EXISTS` for each of them.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_file_script (
+    ($(#[$attr:meta])* $path:literal) => ({
+        $crate::sqlx_macros::expand_query!(attrs = [$(#[$attr])*], source_file = $path, script = true)
+    });
+    ($(#[$attr:meta])* $path:literal, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(attrs = [$(#[$attr])*], source_file = $path, args = [$($args)*], script = true)
+    })
+);
+
+/// Combines the syntaxes of [`query_file_as!`][`crate::query_file_as!`] and
+/// [`query_file_script!`][`crate::query_file_script!`].
+///
+/// Enforces requirements of both macros; see them for details.
+///
+/// ```rust,ignore
+/// let accounts = sqlx::query_file_script_as!(Account, "queries/active-accounts.sql", min_id)
+///     .in_transaction()
+///     .fetch_all(&pool)
+///     .await?;
+/// ```
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_file_script_as (
+    ($out_struct:path: FromRow, $path:literal) => ({
+        $crate::sqlx_macros::expand_query!(record_from_row = $out_struct, source_file = $path, script = true)
+    });
+    ($out_struct:path: FromRow, $path:literal, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(record_from_row = $out_struct, source_file = $path, args = [$($args)*], script = true)
+    });
+    ($out_struct:path, $path:literal) => ({
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path, script = true)
+    });
+    ($out_struct:path, $path:literal, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path, args = [$($args)*], script = true)
+    })
+);
+
 #[allow(clippy::needless_doctest_main)]
 /// Embeds migrations into the binary by expanding to a static instance of [Migrator][crate::migrate::Migrator].
 ///
@@ -765,6 +1160,36 @@ macro_rules! query_file_scalar_unchecked (
 ///
 /// See [MigrationSource][crate::migrate::MigrationSource] for details on structure of the ./migrations directory.
 ///
+/// ## Migrations Written in Rust
+/// Migrations that are easier to write in Rust, such as backfilling data, can be declared
+/// with `rust = [...]`, and are run in order of version along with those in the directory:
+///
+/// ```rust,ignore
+/// use sqlx::{Postgres, Transaction};
+///
+/// static MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!(
+///     "./migrations",
+///     rust = [
+///         // (version, description, checksum version, function)
+///         (20240102000000, "backfill slugs", "v1", backfill_slugs),
+///     ]
+/// );
+///
+/// async fn backfill_slugs(tx: &mut Transaction<'_, Postgres>) -> Result<(), sqlx::error::BoxDynError> {
+///     // ...
+///     Ok(())
+/// }
+/// ```
+///
+/// Each is run in a transaction (a savepoint if the migration is already in one) and recorded in
+/// the same `_sqlx_migrations` table. As the code can't be hashed, the checksum is taken of the
+/// declared checksum version instead; change it to mark the migration as modified.
+///
+/// `sqlx-cli` only knows about the migrations in the directory, so it must be passed
+/// `--ignore-missing` to run them against a database where Rust migrations have been applied.
+///
+/// See [`RustMigration`][crate::migrate::RustMigration] to add these to a `Migrator` at runtime.
+///
 /// ## Note: Platform-specific Line Endings
 /// Different platforms use different bytes for line endings by default:
 /// * Linux and MacOS use Line Feeds (LF:`\n`)
@@ -865,6 +1290,14 @@ macro_rules! migrate {
         $crate::sqlx_macros::migrate!($dir)
     }};
 
+    ($dir:literal, rust = [$($rust:tt)*] $(,)?) => {{
+        $crate::sqlx_macros::migrate!($dir, rust = [$($rust)*])
+    }};
+
+    (rust = [$($rust:tt)*] $(,)?) => {{
+        $crate::sqlx_macros::migrate!(rust = [$($rust)*])
+    }};
+
     () => {{
         $crate::sqlx_macros::migrate!()
     }};
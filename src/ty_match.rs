@@ -118,6 +118,51 @@ impl<T, U> MatchBorrowExt for &'_ MatchBorrow<T, U> {
     type Matched = U;
 }
 
+/// Gets the type that a field of the `#[derive(FromRow)]` struct `S` decodes the column
+/// with the hashed name `NAME` as, or `T` if `S` does not read that column by name.
+pub struct ColumnField<S, T, const NAME: u64>(PhantomData<S>, PhantomData<T>);
+
+#[allow(clippy::new_without_default)]
+impl<S, T, const NAME: u64> ColumnField<S, T, NAME> {
+    pub fn new() -> Self {
+        ColumnField(PhantomData, PhantomData)
+    }
+}
+
+pub trait ColumnFieldExt {
+    type Type;
+
+    fn field_type(&self) -> PhantomData<Self::Type> {
+        PhantomData
+    }
+}
+
+impl<S, T, const NAME: u64> ColumnFieldExt for ColumnField<S, T, NAME>
+where
+    S: sqlx_core::from_row::FromRowColumnType<NAME>,
+{
+    type Type = S::Type;
+}
+
+impl<S, T, const NAME: u64> ColumnFieldExt for &'_ ColumnField<S, T, NAME> {
+    type Type = T;
+}
+
+/// Implemented if a column of type `T` can be decoded as `Self`, ignoring nullability.
+///
+/// `FromRow` reads columns by name, so the nullability of a column cannot be overridden with
+/// `foo!` or `foo?` without also renaming it. Decoding `NULL` to a non-`Option` type
+/// fails at runtime instead.
+pub trait MatchNullable<T> {}
+
+impl<T> MatchNullable<T> for T {}
+
+impl<T> MatchNullable<Option<T>> for T {}
+
+impl<T> MatchNullable<T> for Option<T> {}
+
+pub fn match_nullable<F: MatchNullable<T>, T>(_field: PhantomData<F>, _column: PhantomData<T>) {}
+
 pub fn conjure_value<T>() -> T {
     panic!()
 }
@@ -126,6 +171,27 @@ pub fn dupe_value<T>(_t: &T) -> T {
     panic!()
 }
 
+#[test]
+fn test_match_nullable() {
+    struct Account;
+
+    impl sqlx_core::from_row::FromRowColumnType<1> for Account {
+        type Type = i64;
+    }
+
+    if false {
+        match_nullable(
+            ColumnField::<Account, i64, 1>::new().field_type(),
+            PhantomData::<Option<i64>>,
+        );
+        match_nullable(
+            (&ColumnField::<Account, String, 2>::new()).field_type(),
+            PhantomData::<String>,
+        );
+        match_nullable(PhantomData::<Option<String>>, PhantomData::<String>);
+    }
+}
+
 #[test]
 fn test_dupe_value() {
     let val = &(String::new(),);
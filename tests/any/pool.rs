@@ -268,3 +268,89 @@ async fn test_connection_maintenance() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn test_pool_resize() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+    sqlx_test::setup_if_needed();
+    let conn_options: AnyConnectOptions = std::env::var("DATABASE_URL")?.parse()?;
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(2)
+        .acquire_timeout(Duration::from_millis(500))
+        .connect_lazy_with(conn_options);
+
+    let conns = vec![pool.acquire().await?, pool.acquire().await?];
+    assert!(pool.try_acquire().is_none());
+
+    // Growing the pool should allow more connections to be opened immediately.
+    pool.set_max_connections(4).await;
+    assert_eq!(pool.max_connections(), 4);
+    assert_eq!(pool.options().get_max_connections(), 2);
+
+    let more_conns = vec![pool.acquire().await?, pool.acquire().await?];
+    assert_eq!(pool.size(), 4);
+    assert!(matches!(
+        pool.acquire().await,
+        Err(sqlx::Error::PoolTimedOut)
+    ));
+
+    // Shrinking the pool while all connections are checked out
+    // should close the surplus as they're returned.
+    pool.set_max_connections(1).await;
+    assert_eq!(pool.size(), 4);
+
+    for mut conn in conns.into_iter().chain(more_conns) {
+        conn.return_to_pool().await;
+    }
+
+    assert_eq!(pool.size(), 1);
+    assert_eq!(pool.num_idle(), 1);
+
+    let conn = pool.acquire().await?;
+    assert!(matches!(
+        pool.acquire().await,
+        Err(sqlx::Error::PoolTimedOut)
+    ));
+    drop(conn);
+
+    // Shrinking the pool should close surplus idle connections.
+    pool.set_max_connections(3).await;
+    let conns = vec![
+        pool.acquire().await?,
+        pool.acquire().await?,
+        pool.acquire().await?,
+    ];
+    for mut conn in conns {
+        conn.return_to_pool().await;
+    }
+    assert_eq!(pool.num_idle(), 3);
+
+    // ...even if the returned future isn't awaited.
+    pool.set_min_connections(1).await;
+    drop(pool.set_max_connections(1));
+    assert_eq!(pool.min_connections(), 1);
+    assert_eq!(pool.size(), 1);
+    assert_eq!(pool.num_idle(), 1);
+
+    pool.set_max_connections(0).await;
+    assert_eq!(pool.size(), 0);
+    assert_eq!(pool.num_idle(), 0);
+
+    // A child pool can't grow beyond its parent.
+    pool.set_max_connections(2).await;
+
+    let child = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(500))
+        .parent(pool.clone())
+        .connect_lazy_with(std::env::var("DATABASE_URL")?.parse()?);
+
+    child.set_max_connections(4).await;
+    assert_eq!(child.max_connections(), 2);
+
+    child.close().await;
+    pool.close().await;
+
+    Ok(())
+}
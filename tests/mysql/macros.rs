@@ -33,6 +33,25 @@ async fn macro_select_from_cte_bind() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn macro_select_list_params() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let ids = [1i32, 3];
+
+    let accounts = sqlx::query!(
+        "select * from (select 1 as id union all select 2 union all select 3) accounts where id in (:ids...) order by id"
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(accounts.len(), 2);
+    assert_eq!(accounts[0].id, 1);
+    assert_eq!(accounts[1].id, 3);
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct RawAccount {
     r#type: i32,
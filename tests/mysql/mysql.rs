@@ -1,7 +1,10 @@
 use anyhow::Context;
 use futures_util::TryStreamExt;
 use sqlx::mysql::{MySql, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlRow};
-use sqlx::{Column, Connection, Executor, Row, SqlSafeStr, Statement, TypeInfo};
+use sqlx::{
+    Column, Connection, Executor, IsolationLevel, Row, SqlSafeStr, Statement, TransactionOptions,
+    TypeInfo,
+};
 use sqlx_core::connection::ConnectOptions;
 use sqlx_mysql::MySqlConnectOptions;
 use sqlx_test::{new, setup_if_needed};
@@ -467,6 +470,41 @@ async fn test_issue_622() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_begin_with_transaction_options() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let mut tx = conn
+        .begin_with(
+            TransactionOptions::new()
+                .isolation(IsolationLevel::Serializable)
+                .read_only(true),
+        )
+        .await?;
+
+    assert!(tx.is_in_transaction());
+
+    // Writes are rejected in a read-only transaction.
+    let res = sqlx::query("INSERT INTO tweet (text) VALUES ('read only')")
+        .execute(&mut *tx)
+        .await;
+    assert!(res.is_err());
+
+    tx.rollback().await?;
+
+    let mut tx = conn
+        .begin_with(TransactionOptions::new().isolation(IsolationLevel::ReadCommitted))
+        .await?;
+
+    sqlx::query("INSERT INTO tweet (text) VALUES ('read write')")
+        .execute(&mut *tx)
+        .await?;
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_work_with_transactions() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
@@ -591,6 +629,63 @@ async fn test_shrink_buffers() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_resets_connections_on_release() -> anyhow::Result<()> {
+    use sqlx::pool::ResetMode;
+
+    for mode in [ResetMode::All, ResetMode::Session] {
+        let pool: MySqlPool = MySqlPoolOptions::new()
+            .max_connections(1)
+            .reset_on_release(mode)
+            .connect(&dotenvy::var("DATABASE_URL")?)
+            .await?;
+
+        let mut conn = pool.acquire().await?;
+        let connection_id: i64 = sqlx::query_scalar("SELECT CAST(CONNECTION_ID() AS SIGNED)")
+            .fetch_one(&mut *conn)
+            .await?;
+        conn.execute("SET @reset_test = 1").await?;
+        conn.execute("CREATE TEMPORARY TABLE reset_test (id INT)")
+            .await?;
+        let _: Option<i64> = sqlx::query_scalar("SELECT GET_LOCK('reset_test', 0)")
+            .fetch_one(&mut *conn)
+            .await?;
+        conn.return_to_pool().await;
+        drop(conn);
+
+        let mut conn = pool.acquire().await?;
+
+        // The connection was reset, not replaced.
+        let same_connection_id: i64 = sqlx::query_scalar("SELECT CAST(CONNECTION_ID() AS SIGNED)")
+            .fetch_one(&mut *conn)
+            .await?;
+        assert_eq!(same_connection_id, connection_id);
+
+        let (variable_reset, lock_released): (bool, bool) =
+            sqlx::query_as("SELECT @reset_test IS NULL, IS_USED_LOCK('reset_test') IS NULL")
+                .fetch_one(&mut *conn)
+                .await?;
+        assert!(variable_reset);
+        assert!(lock_released);
+
+        // The temporary table can be created again.
+        conn.execute("CREATE TEMPORARY TABLE reset_test (id INT)")
+            .await?;
+
+        // The reset deallocates prepared statements, so they must be re-prepared.
+        let one: i64 = sqlx::query_scalar("SELECT ?")
+            .bind(1_i64)
+            .fetch_one(&mut *conn)
+            .await?;
+        assert_eq!(one, 1);
+
+        drop(conn);
+        pool.close().await;
+    }
+
+    Ok(())
+}
+
 async fn select_statement_count(conn: &mut MySqlConnection) -> Result<i64, sqlx::Error> {
     // Fails if performance schema does not exist
     sqlx::query_scalar(
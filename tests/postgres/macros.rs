@@ -125,6 +125,36 @@ async fn test_query_file() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_query_file_script() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // the table is dropped on commit, so the script can run again on the same connection
+    for _ in 0..2 {
+        let accounts =
+            sqlx::query_file_script!("tests/postgres/test-script.sql", 3i32, "Herpinson")
+                .in_transaction()
+                .fetch_all(&mut conn)
+                .await?;
+
+        let accounts = accounts
+            .into_iter()
+            .map(|account| (account.id, account.name))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            accounts,
+            [
+                (1, "Herp".to_string()),
+                (2, "Derp; Derpinson".to_string()),
+                (3, "Herpinson".to_string()),
+            ]
+        );
+    }
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct Account {
     id: i32,
@@ -363,6 +393,74 @@ async fn test_many_args() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_named_params() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let start = 1i32;
+
+    // `:start` is bound once and referenced twice; `::int` is a cast, not a parameter
+    let row = sqlx::query!(
+        r#"SELECT :start::int + @step as "next!", :start::int as "start!", ':start' as "literal!""#,
+        step = 2i32
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(row.next, 3);
+    assert_eq!(row.start, 1);
+    assert_eq!(row.literal, ":start");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_dyn() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    for (min, max, expected) in [
+        (None, None, vec![1, 2, 3, 4]),
+        (Some(2i32), None, vec![2, 3, 4]),
+        (None, Some(2i32), vec![1, 2]),
+        (Some(2), Some(3), vec![2, 3]),
+    ] {
+        // parameters are numbered by the clauses that are enabled
+        let rows = sqlx::query_dyn!(
+            r#"SELECT n as "n!" FROM generate_series(1, 4) n WHERE true"#,
+            if let Some(min) = min {
+                "AND n >= {min}"
+            },
+            if let Some(max) = max {
+                "AND n <= {max}"
+            },
+            "ORDER BY n"
+        )
+        .fetch_all(&mut conn)
+        .await?;
+
+        assert_eq!(rows.iter().map(|row| row.n).collect::<Vec<_>>(), expected);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_named_params_without_named_args() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // without named arguments, `@v` is the absolute value operator and `[:hi]` a slice
+    let row = sqlx::query!(
+        r#"SELECT @v as "abs!", (ARRAY[1, 2, 3])[:hi] as "slice!" FROM (VALUES (-1, 2)) t(v, hi)"#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(row.abs, 1);
+    assert_eq!(row.slice, [1, 2]);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_array_from_slice() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -5,7 +5,10 @@ use sqlx::postgres::{
     PgAdvisoryLock, PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgListener,
     PgPoolOptions, PgRow, PgSeverity, Postgres, PG_COPY_MAX_DATA_LEN,
 };
-use sqlx::{Column, Connection, Executor, Row, SqlSafeStr, Statement, TypeInfo};
+use sqlx::{
+    Column, Connection, Executor, IsolationLevel, Row, SqlSafeStr, Statement, TransactionOptions,
+    TypeInfo,
+};
 use sqlx_core::sql_str::AssertSqlSafe;
 use sqlx_core::{bytes::Bytes, error::BoxDynError};
 use sqlx_test::{new, pool, setup_if_needed};
@@ -519,6 +522,73 @@ async fn it_can_work_with_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_times_out_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let res = sqlx::query("SELECT pg_sleep(10)")
+        .timeout(Duration::from_millis(100))
+        .execute(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::QueryTimedOut)), "{res:?}");
+
+    // The connection can still be used, and quick queries are unaffected by the timeout.
+    let value: i32 = sqlx::query_scalar("SELECT 1")
+        .timeout(Duration::from_secs(10))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_begin_with_transaction_options() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut tx = conn
+        .begin_with(
+            TransactionOptions::new()
+                .isolation(IsolationLevel::Serializable)
+                .read_only(true)
+                .deferrable(true),
+        )
+        .await?;
+
+    let (isolation, read_only, deferrable): (String, String, String) = sqlx::query_as(
+        "SELECT current_setting('transaction_isolation'), \
+                current_setting('transaction_read_only'), \
+                current_setting('transaction_deferrable')",
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    assert_eq!(isolation, "serializable");
+    assert_eq!(read_only, "on");
+    assert_eq!(deferrable, "on");
+
+    tx.rollback().await?;
+
+    // The options only apply to that transaction.
+    let mut tx = conn.begin_with(TransactionOptions::new()).await?;
+
+    let isolation: String = sqlx::query_scalar("SELECT current_setting('transaction_isolation')")
+        .fetch_one(&mut *tx)
+        .await?;
+
+    assert_eq!(isolation, "read committed");
+
+    // Options can't be used to begin a savepoint.
+    assert!(matches!(
+        tx.begin_with(TransactionOptions::new()).await,
+        Err(sqlx::Error::InvalidSavePointStatement)
+    ));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -1096,6 +1166,62 @@ async fn test_listener_cleanup() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_pool_reset_on_release() -> anyhow::Result<()> {
+    use sqlx::pool::{PoolOptions, ResetMode};
+
+    for mode in [ResetMode::All, ResetMode::Session] {
+        let pool = PoolOptions::<Postgres>::new()
+            .max_connections(1)
+            .reset_on_release(mode)
+            .connect(&env::var("DATABASE_URL")?)
+            .await?;
+
+        let mut conn = pool.acquire().await?;
+        conn.execute("SET application_name = 'reset_test'").await?;
+        conn.execute("CREATE TEMPORARY TABLE reset_test (id INT)")
+            .await?;
+        let _: (bool,) = sqlx::query_as("SELECT pg_try_advisory_lock($1)")
+            .bind(8_675_309_i64)
+            .fetch_one(&mut *conn)
+            .await?;
+        conn.return_to_pool().await;
+        drop(conn);
+
+        let mut conn = pool.acquire().await?;
+
+        let application_name: String = sqlx::query_scalar("SHOW application_name")
+            .fetch_one(&mut *conn)
+            .await?;
+        assert_ne!(application_name, "reset_test");
+
+        let temp_table: Option<String> =
+            sqlx::query_scalar("SELECT to_regclass('pg_temp.reset_test')::text")
+                .fetch_one(&mut *conn)
+                .await?;
+        assert_eq!(temp_table, None);
+
+        let advisory_locks: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pg_locks WHERE locktype = 'advisory' AND pid = pg_backend_pid()",
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+        assert_eq!(advisory_locks, 0);
+
+        // `DISCARD ALL` deallocates prepared statements, so they must be re-prepared.
+        let one: i32 = sqlx::query_scalar("SELECT $1::int4")
+            .bind(1_i32)
+            .fetch_one(&mut *conn)
+            .await?;
+        assert_eq!(one, 1);
+
+        drop(conn);
+        pool.close().await;
+    }
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_listener_try_recv_buffered() -> anyhow::Result<()> {
     use sqlx_core::rt::timeout;
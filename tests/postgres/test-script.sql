@@ -0,0 +1,8 @@
+-- a temporary table, which the statements after it refer to
+CREATE TEMPORARY TABLE script_accounts (id INT PRIMARY KEY, name TEXT NOT NULL) ON COMMIT DROP;
+
+INSERT INTO script_accounts (id, name) VALUES (1, 'Herp'), (2, 'Derp; Derpinson');
+
+INSERT INTO script_accounts (id, name) VALUES ($1, $2);
+
+SELECT id, name FROM script_accounts WHERE id <= $1 ORDER BY id;
@@ -96,3 +96,21 @@ async fn it_fails_with_invalid_save_point_statement() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "sqlite-bundled")]
+#[sqlx_macros::test]
+async fn it_reports_error_offset() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // the offset is into the whole query, not the statement that failed to prepare
+    let query = "  SELECT 1; SELECT nme FROM tweet";
+
+    let err = sqlx::raw_sql(query).execute(&mut conn).await.unwrap_err();
+
+    let err = err.into_database_error().unwrap();
+    let err = err.downcast_ref::<sqlx::sqlite::SqliteError>();
+
+    assert_eq!(err.offset(), query.find("nme"));
+
+    Ok(())
+}
@@ -88,6 +88,62 @@ async fn macro_select_bind() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn macro_select_named_params() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let id = 1i32;
+
+    // `:id` is bound twice; `@name` is bound to the named argument
+    let account = sqlx::query!(
+        "select id, name, ':id' as literal from accounts where id = :id and id >= :id and name = @name",
+        name = "Herp Derpinson"
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(1, account.id);
+    assert_eq!("Herp Derpinson", account.name);
+    assert_eq!(":id", account.literal);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn macro_select_list_params() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let ids = vec![1i64, 2, 3];
+
+    // a derived table, since other tests insert into the tables of the shared database
+    let ids_found = sqlx::query_scalar!(
+        r#"select id as "id!" from (select 1 as id, 'a' as text union all select 2, '?...' union all select 4, 'b')
+        where id in (?...) and text != ? order by id"#,
+        ids,
+        "?..."
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(ids_found, [1]);
+
+    // an empty list matches nothing with `IN`, and everything with `NOT IN`
+    let none: &[i64] = &[];
+
+    let counts = sqlx::query!(
+        r#"select count(*) filter (where id in (:none...)) as "in!: i64",
+            count(*) filter (where id not in (:none...)) as "not_in!: i64"
+        from (select 1 as id union all select 2)"#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(counts.r#in, 0);
+    assert_eq!(counts.not_in, 2);
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct RawAccount {
     id: i64,
@@ -95,6 +151,144 @@ struct RawAccount {
     is_active: Option<bool>,
 }
 
+#[derive(Debug, sqlx::FromRow)]
+struct AccountStatus {
+    is_active: Option<bool>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct FromRowAccount {
+    id: i64,
+    #[sqlx(rename = "name")]
+    display_name: String,
+    #[sqlx(rename = "id", try_from = "i64")]
+    id_unsigned: u64,
+    #[sqlx(flatten)]
+    status: AccountStatus,
+    #[sqlx(default)]
+    nickname: Option<String>,
+    #[sqlx(skip)]
+    skipped: bool,
+}
+
+#[sqlx_macros::test]
+async fn test_query_as_from_row() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let account = sqlx::query_as!(
+        FromRowAccount: FromRow,
+        "SELECT id, name, is_active from accounts where id = ?",
+        1i64
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(1, account.id);
+    assert_eq!(1, account.id_unsigned);
+    assert_eq!("Herp Derpinson", account.display_name);
+    assert_eq!(Some(true), account.status.is_active);
+    assert_eq!(None, account.nickname);
+    assert!(!account.skipped);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn macro_select_record_attrs() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let account = sqlx::query!(
+        #[derive(Clone, serde::Serialize)]
+        #[serde(rename_all = "camelCase")]
+        "select id, name as display_name from accounts where id = ?",
+        1i32
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(
+        serde_json::to_value(account.clone())?,
+        serde_json::json!({ "id": 1, "displayName": "Herp Derpinson" })
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn macro_query_file_script() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // the table is dropped at the start, so the script can run again on the same connection
+    for _ in 0..2 {
+        // the `INSERT` binds the first two arguments, and the `SELECT` the third
+        let accounts =
+            sqlx::query_file_script!("tests/sqlite/test-script.sql", 3i32, "Herpinson", 3i32)
+                .in_transaction()
+                .fetch_all(&mut conn)
+                .await?;
+
+        let accounts = accounts
+            .into_iter()
+            .map(|account| (account.id, account.name))
+            .collect::<Vec<(i64, String)>>();
+
+        assert_eq!(
+            accounts,
+            [
+                (1, "Herp".to_string()),
+                (2, "Derp; Derpinson".to_string()),
+                (3, "Herpinson".to_string()),
+            ]
+        );
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn macro_select_dyn() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    for (name, min_id, expected) in [
+        (None, None, 1),
+        (Some("Herp Derpinson"), None, 1),
+        (Some("Nobody"), None, 0),
+        (Some("Herp Derpinson"), Some(2i64), 0),
+    ] {
+        let accounts = sqlx::query_dyn!(
+            "select id, name from accounts where true",
+            if let Some(name) = name {
+                "and name = {name}"
+            },
+            if let Some(min_id) = min_id {
+                "and id >= {min_id}"
+            },
+            "order by id"
+        )
+        .fetch_all(&mut conn)
+        .await?;
+
+        assert_eq!(accounts.len(), expected);
+    }
+
+    let name = "Herp Derpinson";
+
+    let account = sqlx::query_dyn_as!(
+        RawAccount,
+        "select id, name, is_active from accounts",
+        if !name.is_empty() {
+            "where name = {name}"
+        }
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(1, account.id);
+    assert_eq!(name, account.name);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_query_as_raw() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
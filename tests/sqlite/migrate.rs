@@ -1,10 +1,18 @@
-use sqlx::migrate::Migrator;
+use sqlx::error::BoxDynError;
+use sqlx::migrate::{MigrateError, Migration, MigrationType, Migrator, RustMigration};
 use sqlx::pool::PoolConnection;
 use sqlx::sqlite::{Sqlite, SqliteConnection};
 use sqlx::Executor;
 use sqlx::Row;
+use sqlx::SqlSafeStr;
+use sqlx::Transaction;
 use std::path::Path;
 
+static EMBEDDED_WITH_RUST: Migrator = sqlx::migrate!(
+    "tests/sqlite/migrations_simple",
+    rust = [(20220721115400, "double payload", "v1", double_payload)]
+);
+
 #[sqlx::test(migrations = false)]
 async fn simple(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
     clean_up(&mut conn).await?;
@@ -100,12 +108,219 @@ async fn no_tx_reversible(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()
     Ok(())
 }
 
+#[sqlx::test(migrations = false)]
+async fn rust(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    // run migration, with the Rust migration between the two SQL ones
+    EMBEDDED_WITH_RUST.run(&mut conn).await?;
+
+    // check outcome
+    let res: String = conn
+        .fetch_one("SELECT some_payload FROM migrations_simple_test")
+        .await?
+        .get(0);
+    assert_eq!(res, "210_suffix");
+
+    let versions: Vec<i64> =
+        sqlx::query_scalar("SELECT version FROM _sqlx_migrations ORDER BY version")
+            .fetch_all(&mut *conn)
+            .await?;
+    assert_eq!(versions, [20220721115250, 20220721115400, 20220721115524]);
+
+    // running it a 2nd time should still work
+    EMBEDDED_WITH_RUST.run(&mut conn).await?;
+
+    // the same migration added at runtime has the same checksum
+    static DOUBLE_PAYLOAD: RustMigration<Sqlite> = RustMigration(|tx| Box::pin(double_payload(tx)));
+
+    let mut migrator = Migrator::new(Path::new("tests/sqlite/migrations_simple")).await?;
+    migrator.add_migration(Migration::rust(
+        20220721115400,
+        "double payload".into(),
+        "v1",
+        &DOUBLE_PAYLOAD,
+    ))?;
+
+    migrator.run(&mut conn).await?;
+
+    // a new checksum version marks it as modified
+    let mut migrator = Migrator::new(Path::new("tests/sqlite/migrations_simple")).await?;
+    migrator.add_migration(Migration::rust(
+        20220721115400,
+        "double payload".into(),
+        "v2",
+        &DOUBLE_PAYLOAD,
+    ))?;
+
+    assert!(matches!(
+        migrator.run(&mut conn).await,
+        Err(MigrateError::VersionMismatch(20220721115400))
+    ));
+
+    // a version can only be added once
+    assert!(matches!(
+        migrator.add_migration(Migration::rust(
+            20220721115400,
+            "double payload".into(),
+            "v3",
+            &DOUBLE_PAYLOAD,
+        )),
+        Err(MigrateError::DuplicateVersion(20220721115400))
+    ));
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn squashed(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let simple = Migrator::new(Path::new("tests/sqlite/migrations_simple")).await?;
+    let squashed = Migrator::new(Path::new("tests/sqlite/migrations_squashed")).await?;
+
+    // a database that applied only some of the squashed migrations can't run the baseline
+    simple.run_to(20220721115250, &mut conn).await?;
+
+    assert!(matches!(
+        squashed.run(&mut conn).await,
+        Err(MigrateError::PartiallySquashed(20220721115524))
+    ));
+
+    // once all of them are applied, the baseline is recorded instead of being run
+    simple.run(&mut conn).await?;
+    squashed.run(&mut conn).await?;
+
+    // running it a 2nd time should still work
+    squashed.run(&mut conn).await?;
+
+    let rows: Vec<(i64, Option<i64>)> =
+        sqlx::query_as("SELECT version, squashed_from FROM _sqlx_migrations ORDER BY version")
+            .fetch_all(&mut *conn)
+            .await?;
+    assert_eq!(
+        rows,
+        [
+            (20220721115250, None),
+            (20220721115524, Some(20220721115250))
+        ]
+    );
+
+    // on a new database, the baseline is run
+    clean_up(&mut conn).await?;
+    squashed.run(&mut conn).await?;
+
+    let res: String = conn
+        .fetch_one("SELECT some_payload FROM migrations_simple_test")
+        .await?
+        .get(0);
+    assert_eq!(res, "110_suffix");
+
+    let rows: Vec<(i64, Option<i64>)> =
+        sqlx::query_as("SELECT version, squashed_from FROM _sqlx_migrations ORDER BY version")
+            .fetch_all(&mut *conn)
+            .await?;
+    assert_eq!(rows, [(20220721115524, Some(20220721115250))]);
+
+    Ok(())
+}
+
+async fn double_payload(tx: &mut Transaction<'_, Sqlite>) -> Result<(), BoxDynError> {
+    tx.execute("UPDATE migrations_simple_test SET some_payload = some_payload * 2")
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn template(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/sqlite/migrations_template")).await?;
+    migrator.set_templating(true);
+
+    assert!(matches!(
+        migrator.run(&mut conn).await,
+        Err(MigrateError::UndefinedVar(name, 20240101000000)) if name == "sqlx_test_name"
+    ));
+
+    // a value without one set is read from the environment
+    std::env::set_var("SQLX_VAR_sqlx_test_name", "first");
+    migrator.run(&mut conn).await?;
+
+    let row: (String, String) =
+        sqlx::query_as("SELECT name, escaped FROM migrations_template_test")
+            .fetch_one(&mut *conn)
+            .await?;
+    assert_eq!(row, ("first".into(), "${not_a_var}".into()));
+
+    // a value that is set takes precedence
+    migrator.set_var("sqlx_test_name", "explicit");
+    let migration = migrator.iter().next().unwrap();
+    assert!(migrator
+        .expand(migration)?
+        .sql
+        .as_str()
+        .contains("VALUES ('explicit', '${not_a_var}')"));
+
+    std::env::remove_var("SQLX_VAR_sqlx_test_name");
+
+    // the checksum is of the template, so other values don't count as a modification
+    migrator.set_var("sqlx_test_name", "second");
+    migrator.run(&mut conn).await?;
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn out_of_order(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let migration = |version| {
+        Migration::new(
+            version,
+            format!("migration {version}").into(),
+            MigrationType::Simple,
+            "SELECT 1".into_sql_str(),
+            false,
+        )
+    };
+
+    Migrator::with_migrations(vec![migration(1), migration(3)])
+        .run(&mut conn)
+        .await?;
+
+    // migration 2 was added after migration 3 was applied, e.g. on another branch
+    let mut migrator = Migrator::with_migrations(vec![migration(1), migration(2), migration(3)]);
+    migrator.set_strict_order(true);
+
+    assert!(matches!(
+        migrator.run(&mut conn).await,
+        Err(MigrateError::OutOfOrder(2, 3))
+    ));
+
+    migrator.set_strict_order(false);
+    migrator.run(&mut conn).await?;
+
+    let applied: Vec<(i64, i64)> =
+        sqlx::query_as("SELECT version, installed_rank FROM _sqlx_migrations ORDER BY version")
+            .fetch_all(&mut *conn)
+            .await?;
+
+    assert_eq!(applied, [(1, 1), (2, 3), (3, 2)]);
+
+    Ok(())
+}
+
 /// Ensure that we have a clean initial state.
 async fn clean_up(conn: &mut SqliteConnection) -> anyhow::Result<()> {
     conn.execute("DROP TABLE migrations_simple_test").await.ok();
     conn.execute("DROP TABLE migrations_reversible_test")
         .await
         .ok();
+    conn.execute("DROP TABLE migrations_template_test")
+        .await
+        .ok();
     conn.execute("DROP TABLE _sqlx_migrations").await.ok();
 
     Ok(())
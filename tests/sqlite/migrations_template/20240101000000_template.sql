@@ -0,0 +1,3 @@
+CREATE TABLE migrations_template_test (name TEXT NOT NULL, escaped TEXT NOT NULL);
+
+INSERT INTO migrations_template_test VALUES ('${sqlx_test_name}', '$${not_a_var}');
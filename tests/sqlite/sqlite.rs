@@ -1,16 +1,19 @@
 use futures_util::TryStreamExt;
 use rand::{Rng, SeedableRng};
 use rand_xoshiro::Xoshiro256PlusPlus;
+use sqlx::pool::ResetMode;
+use sqlx::retry::RetryPolicy;
 use sqlx::sqlite::{SqliteConnectOptions, SqliteOperation, SqlitePoolOptions};
 use sqlx::SqlSafeStr;
 use sqlx::{
     query, sqlite::Sqlite, sqlite::SqliteRow, Column, ConnectOptions, Connection, Executor, Row,
     SqliteConnection, SqlitePool, Statement, TypeInfo,
 };
+use sqlx::{IsolationLevel, TransactionOptions};
 use sqlx_sqlite::LockedSqliteHandle;
 use sqlx_test::new;
 use std::future::Future;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
 
 #[sqlx_macros::test]
@@ -209,6 +212,119 @@ async fn it_executes_with_pool() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_resets_connections_on_release() -> anyhow::Result<()> {
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .reset_on_release(ResetMode::All)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    conn.execute("CREATE TEMPORARY TABLE reset_test (id INTEGER PRIMARY KEY)")
+        .await?;
+    conn.execute("CREATE TEMPORARY VIEW reset_test_view AS SELECT id FROM reset_test")
+        .await?;
+    conn.return_to_pool().await;
+    drop(conn);
+
+    let mut conn = pool.acquire().await?;
+    let (temp_objects,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM sqlite_temp_master")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(temp_objects, 0);
+
+    // The table can be created again.
+    conn.execute("CREATE TEMPORARY TABLE reset_test (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_retries_transactions_on_busy() -> anyhow::Result<()> {
+    use std::{str::FromStr, time::Duration};
+
+    let opts = SqliteConnectOptions::from_str(&dotenvy::var("DATABASE_URL")?)?
+        .busy_timeout(Duration::ZERO);
+
+    let mut locker = SqliteConnection::connect_with(&opts).await?;
+    let mut conn = SqliteConnection::connect_with(&opts).await?;
+
+    // Hold the write lock so that any write from `conn` fails with `SQLITE_BUSY`.
+    locker.execute("BEGIN IMMEDIATE").await?;
+
+    let policy = RetryPolicy::new()
+        .max_attempts(3)
+        .backoff(Duration::from_millis(1), Duration::from_millis(5));
+    let attempts = AtomicU32::new(0);
+
+    let res = conn
+        .transaction_with_retry(&policy, |tx| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+
+            Box::pin(async move {
+                sqlx::query("INSERT INTO tweet (id, text) VALUES (1000, 'retry')")
+                    .execute(&mut **tx)
+                    .await
+            })
+        })
+        .await;
+
+    let err = res.expect_err("expected the write to be blocked");
+    assert!(err.is_retryable(), "{err:?}");
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+    locker.execute("ROLLBACK").await?;
+
+    // Errors that aren't transient are not retried.
+    attempts.store(0, Ordering::SeqCst);
+
+    let res = conn
+        .transaction_with_retry(&policy, |tx| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+
+            Box::pin(async move {
+                sqlx::query("INSERT INTO tweet (id, text) VALUES (1, 'duplicate')")
+                    .execute(&mut **tx)
+                    .await
+            })
+        })
+        .await;
+
+    let err = res.expect_err("expected a unique violation");
+    assert!(!err.is_retryable(), "{err:?}");
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_times_out_queries() -> anyhow::Result<()> {
+    use std::time::Duration;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let res = sqlx::query_scalar::<_, i64>(
+        "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c) SELECT count(*) FROM c",
+    )
+    .timeout(Duration::from_millis(100))
+    .fetch_one(&mut conn)
+    .await;
+
+    assert!(matches!(res, Err(sqlx::Error::QueryTimedOut)), "{res:?}");
+
+    // The connection can still be used, and quick queries are unaffected by the timeout.
+    let value: i32 = sqlx::query_scalar("SELECT 1")
+        .timeout(Duration::from_secs(10))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
 #[cfg(sqlite_ipaddr)]
 #[sqlx_macros::test]
 async fn it_opens_with_extension() -> anyhow::Result<()> {
@@ -1372,6 +1488,32 @@ async fn it_can_use_transaction_options() -> anyhow::Result<()> {
     check_txn_state(&mut tx, SqliteTransactionState::Write).await;
     drop(tx);
 
+    let mut tx = conn.begin_with(TransactionOptions::new()).await?;
+    check_txn_state(&mut tx, SqliteTransactionState::None).await;
+    drop(tx);
+
+    let mut tx = conn
+        .begin_with(TransactionOptions::new().isolation(IsolationLevel::RepeatableRead))
+        .await?;
+    check_txn_state(&mut tx, SqliteTransactionState::Write).await;
+    drop(tx);
+
+    let mut tx = conn
+        .begin_with(TransactionOptions::new().isolation(IsolationLevel::Serializable))
+        .await?;
+    check_txn_state(&mut tx, SqliteTransactionState::Write).await;
+    drop(tx);
+
+    let mut tx = conn
+        .begin_with(
+            TransactionOptions::new()
+                .isolation(IsolationLevel::Serializable)
+                .read_only(true),
+        )
+        .await?;
+    check_txn_state(&mut tx, SqliteTransactionState::None).await;
+    drop(tx);
+
     Ok(())
 }
 
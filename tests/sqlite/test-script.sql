@@ -0,0 +1,11 @@
+-- a temporary table, which the statements after it refer to;
+-- it outlives the script, so drop it first in case the script ran on this connection before
+DROP TABLE IF EXISTS script_accounts;
+
+CREATE TEMPORARY TABLE script_accounts (id INTEGER PRIMARY KEY NOT NULL, name TEXT NOT NULL);
+
+INSERT INTO script_accounts (id, name) VALUES (1, 'Herp'), (2, 'Derp; Derpinson');
+
+INSERT INTO script_accounts (id, name) VALUES (?, ?);
+
+SELECT id, name FROM script_accounts WHERE id <= ? ORDER BY id;
@@ -0,0 +1,10 @@
+fn main() {
+    let _query = sqlx::query!(
+        r#"
+        SELECT id, name
+        FORM accounts
+        "#
+    );
+
+    let _query = sqlx::query!("SELECT nme FROM (VALUES (1, 'Herp')) accounts(id, name)");
+}